@@ -0,0 +1,39 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use mahjong_enterprise_edition_core::rule::{Shanten, Tile, TileKind};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+const HANDS_COUNT: usize = 1000;
+
+fn random_hands(tiles_count: usize) -> Vec<Vec<Tile>> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut wall = TileKind::all()
+        .flat_map(|kind| std::iter::repeat_n(Tile::new(kind), 4))
+        .collect::<Vec<_>>();
+    (0..HANDS_COUNT)
+        .map(|_| {
+            wall.shuffle(&mut rng);
+            wall[..tiles_count].to_vec()
+        })
+        .collect()
+}
+
+fn shanten(c: &mut Criterion) {
+    for &tiles_count in &[13, 14] {
+        let hands = random_hands(tiles_count);
+        c.bench_function(
+            &format!("shanten of {} random tiles x {}", tiles_count, HANDS_COUNT),
+            |b| {
+                b.iter(|| {
+                    for hand in &hands {
+                        black_box(Shanten::of(black_box(hand), &[]).value());
+                    }
+                })
+            },
+        );
+    }
+}
+
+criterion_group!(benches, shanten);
+criterion_main!(benches);
@@ -1,3 +1,25 @@
+mod config;
 mod def;
-mod player;
+mod facade;
+mod ledger;
 mod table;
+mod view;
+
+pub use config::error::RuleConfigError;
+pub use config::preset::RulePreset;
+pub use config::{
+    AbortiveDraws, GameLength, HongKongScoring, MultipleRon, PaymentStructure, RedFives,
+    RuleConfig, RuleDifference, SelfDrawPayment,
+};
+pub use def::{
+    Action, ActionPolicy, Concept, DealtResult, FuritenStatus, HandJudgementSpec, HandScore,
+    MeldConcept, ParticipantId, PlayersCount, Seat, SeatingSpec, TileDealingSpec, WinningHand,
+};
+pub use facade::error::TableError;
+pub use facade::{Table, TableStatus};
+pub use ledger::{Ledger, LedgerEntry, PaymentReason};
+pub use table::error::{ClaimError, DeclareError, DiscardError, DrawError, JoinError, SeatError};
+pub use table::{HandEnding, PhaseKind, Round, TableId};
+pub use view::{SeatView, Standing, TableView};
+
+pub(crate) use ledger::round_up;
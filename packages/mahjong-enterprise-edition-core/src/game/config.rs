@@ -0,0 +1,636 @@
+use crate::game::def::PlayersCount;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+
+pub(crate) mod error;
+pub(crate) mod preset;
+
+use error::RuleConfigError;
+
+/// Rules of a game which the table follows, independent of the tile concept.
+///
+/// Loadable from TOML or JSON. Omitted fields take the default values, or those of
+/// [`RuleConfig::three_players`] if the document says `players_count = 3`.
+///
+/// ```
+/// use mahjong_enterprise_edition_core::game::{GameLength, RuleConfig};
+///
+/// let rule = RuleConfig::from_toml_str(
+///     r#"
+///     game_length = "east_only"
+///     uma = [10000, 5000, -5000, -10000]
+///
+///     [red_fives]
+///     characters = 1
+///     circles = 2
+///     bamboos = 1
+///     "#,
+/// )
+/// .unwrap();
+/// assert_eq!(rule.game_length, GameLength::EastOnly);
+/// assert_eq!(rule.red_fives.circles, 2);
+///
+/// let rule = RuleConfig::from_json_str(r#"{ "players_count": 3 }"#).unwrap();
+/// assert_eq!(rule.uma, vec![15000, 0, -15000]);
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RuleConfig {
+    pub players_count: PlayersCount,
+    /// 持ち点
+    pub initial_point: i32,
+    /// 返し点. The difference from the initial point goes to the top as 岡.
+    pub target_point: i32,
+    pub game_length: GameLength,
+    /// 立直
+    pub allows_riichi: bool,
+    /// フリテン
+    pub has_furiten: bool,
+    /// 連荘. Otherwise the dealer passes on every hand.
+    pub has_dealer_repeat: bool,
+    /// 喰いタン
+    pub allows_open_tanyao: bool,
+    /// 一発
+    pub has_ippatsu: bool,
+    /// 裏ドラ
+    pub has_ura_dora: bool,
+    /// 流し満貫
+    pub allows_nagashi_mangan: bool,
+    /// ダブル役満, e.g. 国士無双十三面待ち and 四暗刻単騎.
+    pub has_double_yakuman: bool,
+    /// 複合役満. Otherwise only the largest yakuman counts.
+    pub stacks_yakuman: bool,
+    /// 連風牌の雀頭の符, either 2 or 4.
+    pub double_wind_pair_fu: u8,
+    /// 切り上げ満貫, 30符4飜 and 60符3飜.
+    pub rounds_up_to_mangan: bool,
+    /// 数え役満, 13飜 or more. Otherwise 三倍満.
+    pub counts_yakuman: bool,
+    /// 食い替え
+    pub allows_swap_calling: bool,
+    /// チー. Usually not allowed in 三麻.
+    pub allows_chow: bool,
+    /// 抜きドラ, e.g. North in 三麻. Which tiles are extracted is told by the hand judgement spec.
+    pub extracts_bonus_tiles: bool,
+    /// How a completed hand is paid for.
+    pub payment_structure: PaymentStructure,
+    /// How the absent player's share of a self-drawn completion is paid, with fewer than four
+    /// players.
+    pub self_draw_payment: SelfDrawPayment,
+    /// ダブロン
+    pub multiple_ron: MultipleRon,
+    /// 積み棒 to each winner of ダブロン・トリロン. Otherwise only the nearest winner from the
+    /// discarder gets them (上家取り), as well as 供託.
+    pub pays_counters_to_each_winner: bool,
+    /// 飛び. The game ends when someone's point goes below zero.
+    pub ends_on_bust: bool,
+    /// ウマ in points, from the top to the last. Should be zero-sum.
+    pub uma: Vec<i32>,
+    // Tables come last to be serialized into TOML.
+    /// 赤ドラ
+    pub red_fives: RedFives,
+    /// 途中流局
+    pub abortive_draws: AbortiveDraws,
+    /// Faan and payouts of `PaymentStructure::HongKong`.
+    pub hong_kong: HongKongScoring,
+}
+
+impl RuleConfig {
+    /// 三麻
+    pub fn three_players() -> RuleConfig {
+        RuleConfig {
+            players_count: PlayersCount::THREE,
+            initial_point: 35000,
+            target_point: 40000,
+            allows_chow: false,
+            extracts_bonus_tiles: true,
+            uma: vec![15000, 0, -15000],
+            red_fives: RedFives {
+                characters: 0,
+                circles: 1,
+                bamboos: 1,
+            },
+            abortive_draws: AbortiveDraws {
+                four_winds: false,
+                four_riichi: false,
+                triple_ron: false,
+                ..AbortiveDraws::default()
+            },
+            ..RuleConfig::default()
+        }
+    }
+
+    pub fn from_toml_str(s: &str) -> Result<RuleConfig, RuleConfigError> {
+        let document: Value = toml::from_str(s)?;
+        let rule: RuleConfig = toml::Value::try_from(Self::fill_omitted_fields(document))
+            .expect("A TOML document should be converted back to TOML")
+            .try_into()?;
+        rule.validate()?;
+        Ok(rule)
+    }
+
+    pub fn from_json_str(s: &str) -> Result<RuleConfig, RuleConfigError> {
+        let document: Value = serde_json::from_str(s)?;
+        let rule: RuleConfig = serde_json::from_value(Self::fill_omitted_fields(document))?;
+        rule.validate()?;
+        Ok(rule)
+    }
+
+    /// The document over the base rule for its players count.
+    fn fill_omitted_fields(document: Value) -> Value {
+        let base = match document.get("players_count").and_then(Value::as_u64) {
+            Some(3) => RuleConfig::three_players(),
+            _ => RuleConfig::default(),
+        };
+        let mut value = base.to_value();
+        merge(&mut value, document);
+        value
+    }
+
+    /// Checks inconsistent combinations of the rules.
+    pub fn validate(&self) -> Result<(), RuleConfigError> {
+        let players_count = self.players_count.get();
+        let is_four_players = self.players_count == PlayersCount::FOUR;
+
+        if self.initial_point < 0 {
+            return Err(RuleConfigError::NegativeInitialPoint(self.initial_point));
+        }
+        if self.target_point < self.initial_point {
+            return Err(RuleConfigError::TargetPointBelowInitialPoint {
+                target_point: self.target_point,
+                initial_point: self.initial_point,
+            });
+        }
+        if self.uma.len() != players_count {
+            return Err(RuleConfigError::UmaLengthMismatch {
+                len: self.uma.len(),
+                players_count,
+            });
+        }
+        let uma_sum = self.uma.iter().sum::<i32>();
+        if uma_sum != 0 {
+            return Err(RuleConfigError::UmaNotZeroSum(uma_sum));
+        }
+        let red_fives = &self.red_fives;
+        if [red_fives.characters, red_fives.circles, red_fives.bamboos]
+            .iter()
+            .any(|count| *count > RedFives::MAX_PER_SUIT)
+        {
+            return Err(RuleConfigError::TooManyRedFives(red_fives.clone()));
+        }
+        if is_four_players && self.self_draw_payment != SelfDrawPayment::Loss {
+            return Err(RuleConfigError::RequiresFewerPlayers("self_draw_payment"));
+        }
+        if ![2, 4].contains(&self.double_wind_pair_fu) {
+            return Err(RuleConfigError::InvalidDoubleWindPairFu(
+                self.double_wind_pair_fu,
+            ));
+        }
+        let abortive_draws = &self.abortive_draws;
+        if !is_four_players {
+            let four_players_only = [
+                ("abortive_draws.four_winds", abortive_draws.four_winds),
+                ("abortive_draws.four_riichi", abortive_draws.four_riichi),
+                ("abortive_draws.triple_ron", abortive_draws.triple_ron),
+            ];
+            if let Some((name, _)) = four_players_only.iter().find(|(_, enabled)| *enabled) {
+                return Err(RuleConfigError::RequiresFourPlayers(name));
+            }
+        }
+        if abortive_draws.triple_ron && self.multiple_ron == MultipleRon::HeadBump {
+            return Err(RuleConfigError::TripleRonWithHeadBump);
+        }
+        let hong_kong = &self.hong_kong;
+        if hong_kong.min_faan == 0
+            || hong_kong.min_faan > hong_kong.max_faan
+            || hong_kong.max_faan > HongKongScoring::MAX_FAAN
+        {
+            return Err(RuleConfigError::InvalidFaanRange {
+                min_faan: hong_kong.min_faan,
+                max_faan: hong_kong.max_faan,
+            });
+        }
+        if hong_kong.base_point <= 0 {
+            return Err(RuleConfigError::NonPositiveBasePoint(hong_kong.base_point));
+        }
+        Ok(())
+    }
+
+    /// Rules differing from `base`, keyed by the path of the field, e.g. `red_fives.circles`.
+    pub fn diff(&self, base: &RuleConfig) -> Vec<RuleDifference> {
+        let mut differences = vec![];
+        collect_differences("", &base.to_value(), &self.to_value(), &mut differences);
+        differences
+    }
+
+    fn to_value(&self) -> Value {
+        serde_json::to_value(self).expect("A rule should be serializable")
+    }
+
+    /// 岡, which goes to the top.
+    pub(crate) fn oka(&self) -> i32 {
+        (self.target_point - self.initial_point) * self.players_count.get() as i32
+    }
+}
+
+fn merge(base: &mut Value, value: Value) {
+    match (base, value) {
+        (Value::Object(base), Value::Object(value)) => {
+            for (key, value) in value {
+                match base.get_mut(&key) {
+                    Some(base) => merge(base, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, value) => *base = value,
+    }
+}
+
+fn collect_differences(
+    path: &str,
+    base: &Value,
+    value: &Value,
+    differences: &mut Vec<RuleDifference>,
+) {
+    match (base, value) {
+        (Value::Object(base), Value::Object(value)) => {
+            for (key, value) in value {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                collect_differences(&path, &base[key], value, differences);
+            }
+        }
+        _ if base != value => differences.push(RuleDifference {
+            key: path.to_string(),
+            base: base.to_string(),
+            value: value.to_string(),
+        }),
+        _ => {}
+    }
+}
+
+impl Default for RuleConfig {
+    fn default() -> Self {
+        RuleConfig {
+            players_count: PlayersCount::FOUR,
+            initial_point: 25000,
+            target_point: 30000,
+            game_length: GameLength::EastSouth,
+            allows_riichi: true,
+            has_furiten: true,
+            has_dealer_repeat: true,
+            allows_open_tanyao: true,
+            has_ippatsu: true,
+            has_ura_dora: true,
+            allows_nagashi_mangan: true,
+            has_double_yakuman: true,
+            stacks_yakuman: true,
+            double_wind_pair_fu: 4,
+            rounds_up_to_mangan: false,
+            counts_yakuman: true,
+            allows_swap_calling: false,
+            allows_chow: true,
+            extracts_bonus_tiles: false,
+            payment_structure: PaymentStructure::Japanese,
+            self_draw_payment: SelfDrawPayment::Loss,
+            multiple_ron: MultipleRon::Multiple,
+            pays_counters_to_each_winner: false,
+            ends_on_bust: true,
+            uma: vec![20000, 10000, -10000, -20000],
+            red_fives: RedFives::default(),
+            abortive_draws: AbortiveDraws::default(),
+            hong_kong: HongKongScoring::default(),
+        }
+    }
+}
+
+/// A rule differing from the base one. The values are in JSON.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct RuleDifference {
+    pub key: String,
+    pub base: String,
+    pub value: String,
+}
+
+impl fmt::Display for RuleDifference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} -> {}", self.key, self.base, self.value)
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameLength {
+    /// 東風戦
+    EastOnly,
+    /// 半荘戦
+    EastSouth,
+    /// 一荘戦, through all the four winds.
+    Full,
+}
+
+impl GameLength {
+    pub(crate) fn rounds_count(&self) -> usize {
+        match self {
+            GameLength::EastOnly => 1,
+            GameLength::EastSouth => 2,
+            GameLength::Full => 4,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentStructure {
+    /// 基本点 from the hand value, with 積み棒 and 供託.
+    Japanese,
+    /// 国標麻将. The score is in fan: every other player pays 8 points, and the discarder or
+    /// each player on self-draw pays the fan on top of it.
+    ChineseOfficial,
+    /// 香港麻雀. The score is in faan, paid by `RuleConfig::hong_kong`.
+    HongKong,
+}
+
+/// Number of red fives in each suit, replacing normal ones.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RedFives {
+    /// 萬子
+    pub characters: u8,
+    /// 筒子
+    pub circles: u8,
+    /// 索子
+    pub bamboos: u8,
+}
+
+impl RedFives {
+    const MAX_PER_SUIT: u8 = 4;
+}
+
+impl Default for RedFives {
+    fn default() -> Self {
+        RedFives {
+            characters: 1,
+            circles: 1,
+            bamboos: 1,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelfDrawPayment {
+    /// ツモ損. The absent player's share is not paid.
+    Loss,
+    /// 北家折半. The absent player's share is split among the payers.
+    Bisection,
+}
+
+impl SelfDrawPayment {
+    /// Points a payer pays for a self-drawn completion. `base_share` is what a non-dealer pays
+    /// to a non-dealer with four players.
+    pub(crate) fn payment(
+        &self,
+        players_count: PlayersCount,
+        winner_is_dealer: bool,
+        payer_is_dealer: bool,
+        base_share: i32,
+    ) -> i32 {
+        let share = if winner_is_dealer || payer_is_dealer {
+            base_share * 2
+        } else {
+            base_share
+        };
+        let absent_count = (PlayersCount::FOUR.get() - players_count.get()) as i32;
+        match self {
+            SelfDrawPayment::Loss => share,
+            // The absent players are non-dealers.
+            SelfDrawPayment::Bisection => {
+                let absent_share = if winner_is_dealer {
+                    base_share * 2
+                } else {
+                    base_share
+                };
+                let payers_count = players_count.get() as i32 - 1;
+                share + absent_share * absent_count / payers_count
+            }
+        }
+    }
+}
+
+/// What happens when several players claim completion on the same discard.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MultipleRon {
+    /// 頭ハネ. Only the nearest seat from the discarder wins.
+    HeadBump,
+    /// ダブロン・トリロン
+    Multiple,
+}
+
+/// 番 of 香港麻雀, doubling the payout for each one up to the cap (爆棚).
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HongKongScoring {
+    /// 起糊番, at least 1 since a hand without faan pays nothing.
+    pub min_faan: u8,
+    /// 滿糊, which limit hands are worth.
+    pub max_faan: u8,
+    /// Doubled for each faan, and paid by each payer on self-draw.
+    pub base_point: i32,
+    /// 全銃. The discarder pays for all the others. Otherwise the others pay half (半銃).
+    pub discarder_pays_all: bool,
+}
+
+impl HongKongScoring {
+    /// Keeps the payout within `i32`.
+    const MAX_FAAN: u8 = 20;
+
+    /// Paid by each payer on self-draw, or by the discarder under 半銃.
+    pub(crate) fn payout(&self, faan: i32) -> i32 {
+        self.base_point
+            .saturating_mul(1 << faan.clamp(0, self.max_faan as i32))
+    }
+}
+
+impl Default for HongKongScoring {
+    fn default() -> Self {
+        HongKongScoring {
+            min_faan: 3,
+            max_faan: 10,
+            base_point: 1,
+            discarder_pays_all: false,
+        }
+    }
+}
+
+/// 途中流局, each enabled or not.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AbortiveDraws {
+    /// 九種九牌
+    pub nine_terminals: bool,
+    /// 四風連打
+    pub four_winds: bool,
+    /// 四家立直
+    pub four_riichi: bool,
+    /// 三家和
+    pub triple_ron: bool,
+}
+
+impl Default for AbortiveDraws {
+    fn default() -> Self {
+        AbortiveDraws {
+            nine_terminals: true,
+            four_winds: true,
+            four_riichi: true,
+            triple_ron: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_of(json: &str) -> RuleConfigError {
+        RuleConfig::from_json_str(json).unwrap_err()
+    }
+
+    #[test]
+    fn both_formats_are_parsed_alike() {
+        let toml = RuleConfig::from_toml_str(
+            r#"
+            initial_point = 30000
+            multiple_ron = "head_bump"
+
+            [abortive_draws]
+            triple_ron = false
+            "#,
+        )
+        .unwrap();
+        let json = RuleConfig::from_json_str(
+            r#"{
+                "initial_point": 30000,
+                "multiple_ron": "head_bump",
+                "abortive_draws": { "triple_ron": false }
+            }"#,
+        )
+        .unwrap();
+        assert!(toml.diff(&json).is_empty());
+        assert_eq!(
+            json.diff(&RuleConfig::default())
+                .iter()
+                .map(|d| d.key.as_str())
+                .collect::<Vec<_>>(),
+            vec!["abortive_draws.triple_ron", "initial_point", "multiple_ron"]
+        );
+    }
+
+    #[test]
+    fn three_players_document_is_based_on_the_three_players_rule() {
+        let base = RuleConfig::three_players();
+        let rule = RuleConfig::from_toml_str("players_count = 3").unwrap();
+        assert!(rule.diff(&base).is_empty());
+        let rule = RuleConfig::from_json_str(r#"{ "players_count": 3 }"#).unwrap();
+        assert!(rule.diff(&base).is_empty());
+
+        let rule =
+            RuleConfig::from_json_str(r#"{ "players_count": 3, "red_fives": { "circles": 2 } }"#)
+                .unwrap();
+        assert_eq!(
+            rule.diff(&base),
+            vec![RuleDifference {
+                key: "red_fives.circles".to_string(),
+                base: "1".to_string(),
+                value: "2".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn malformed_document_is_refused() {
+        assert!(matches!(
+            RuleConfig::from_toml_str("uma = 1"),
+            Err(RuleConfigError::Toml(_))
+        ));
+        assert!(matches!(
+            RuleConfig::from_toml_str("unknown = 1"),
+            Err(RuleConfigError::Toml(_))
+        ));
+        assert!(matches!(error_of("{"), RuleConfigError::Json(_)));
+        assert!(matches!(
+            error_of(r#"{ "red_fives": { "unknown": 1 } }"#),
+            RuleConfigError::Json(_)
+        ));
+        assert!(matches!(
+            error_of(r#"{ "players_count": 5 }"#),
+            RuleConfigError::Json(_)
+        ));
+    }
+
+    #[test]
+    fn inconsistent_points_are_refused() {
+        assert!(matches!(
+            error_of(r#"{ "initial_point": -1 }"#),
+            RuleConfigError::NegativeInitialPoint(-1)
+        ));
+        assert!(matches!(
+            error_of(r#"{ "initial_point": 40000 }"#),
+            RuleConfigError::TargetPointBelowInitialPoint {
+                target_point: 30000,
+                initial_point: 40000,
+            }
+        ));
+        assert!(matches!(
+            error_of(r#"{ "uma": [10000, -10000] }"#),
+            RuleConfigError::UmaLengthMismatch {
+                len: 2,
+                players_count: 4,
+            }
+        ));
+        assert!(matches!(
+            error_of(r#"{ "uma": [10000, 0, 0, 0] }"#),
+            RuleConfigError::UmaNotZeroSum(10000)
+        ));
+        assert!(matches!(
+            error_of(r#"{ "hong_kong": { "base_point": 0 } }"#),
+            RuleConfigError::NonPositiveBasePoint(0)
+        ));
+    }
+
+    #[test]
+    fn inconsistent_rules_are_refused() {
+        assert!(matches!(
+            error_of(r#"{ "red_fives": { "circles": 5 } }"#),
+            RuleConfigError::TooManyRedFives(_)
+        ));
+        assert!(matches!(
+            error_of(r#"{ "self_draw_payment": "bisection" }"#),
+            RuleConfigError::RequiresFewerPlayers("self_draw_payment")
+        ));
+        assert!(matches!(
+            error_of(r#"{ "players_count": 3, "abortive_draws": { "four_riichi": true } }"#),
+            RuleConfigError::RequiresFourPlayers("abortive_draws.four_riichi")
+        ));
+        assert!(matches!(
+            error_of(r#"{ "double_wind_pair_fu": 3 }"#),
+            RuleConfigError::InvalidDoubleWindPairFu(3)
+        ));
+        assert!(matches!(
+            error_of(r#"{ "hong_kong": { "min_faan": 0 } }"#),
+            RuleConfigError::InvalidFaanRange { min_faan: 0, .. }
+        ));
+        assert!(matches!(
+            error_of(r#"{ "multiple_ron": "head_bump" }"#),
+            RuleConfigError::TripleRonWithHeadBump
+        ));
+    }
+}
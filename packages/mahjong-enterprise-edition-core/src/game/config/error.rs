@@ -0,0 +1,39 @@
+use crate::game::config::RedFives;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum RuleConfigError {
+    #[error("failed to parse the rule from TOML: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("failed to parse the rule from JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("the initial point should not be negative, got {0}")]
+    NegativeInitialPoint(i32),
+    #[error(
+        "the target point {target_point} should not be below the initial point {initial_point}"
+    )]
+    TargetPointBelowInitialPoint {
+        target_point: i32,
+        initial_point: i32,
+    },
+    #[error("the uma should have {players_count} ranks, got {len}")]
+    UmaLengthMismatch { len: usize, players_count: usize },
+    #[error("the uma should sum to zero, got {0}")]
+    UmaNotZeroSum(i32),
+    #[error("a suit has at most 4 fives, got {0:?}")]
+    TooManyRedFives(RedFives),
+    #[error("`{0}` is only for fewer than four players")]
+    RequiresFewerPlayers(&'static str),
+    #[error("`{0}` is only for four players")]
+    RequiresFourPlayers(&'static str),
+    #[error("the fu of a double wind pair should be 2 or 4, got {0}")]
+    InvalidDoubleWindPairFu(u8),
+    #[error("the faan should range from 1 to 20, got {min_faan} to {max_faan}")]
+    InvalidFaanRange { min_faan: u8, max_faan: u8 },
+    #[error("the base point should be positive, got {0}")]
+    NonPositiveBasePoint(i32),
+    #[error("triple ron never happens with head bump")]
+    TripleRonWithHeadBump,
+    #[error("unknown rule preset `{0}`")]
+    UnknownPreset(String),
+}
@@ -0,0 +1,240 @@
+use crate::game::config::error::RuleConfigError;
+use crate::game::config::{
+    AbortiveDraws, GameLength, MultipleRon, PaymentStructure, RedFives, RuleConfig,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Rulesets shipped with the crate, to pick by name or to compare house rules with.
+///
+/// ```
+/// use mahjong_enterprise_edition_core::game::{RuleConfig, RulePreset};
+///
+/// for preset in RulePreset::ALL.iter() {
+///     assert!(preset.rule().validate().is_ok());
+/// }
+///
+/// let base = "wrc".parse::<RulePreset>().unwrap().rule();
+/// let house_rule = RuleConfig {
+///     ends_on_bust: true,
+///     ..base.clone()
+/// };
+/// let differences = house_rule.diff(&base);
+/// assert_eq!(differences.len(), 1);
+/// assert_eq!(differences[0].key, "ends_on_bust");
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RulePreset {
+    /// A typical ruleset of online services, i.e. the default one.
+    OnlineStandard,
+    /// World Riichi Championship
+    Wrc,
+    /// European Mahjong Association
+    Ema,
+    /// 日本プロ麻雀連盟 A ルール
+    Jpml,
+    /// A typical professional league, e.g. M.League
+    ProLeague,
+    /// 国標麻将 (Mahjong Competition Rules), to be played with the specs in `mcr`.
+    ChineseOfficial,
+    /// 香港麻雀, the old style with faan, to be played with the specs in `hk`.
+    HongKong,
+}
+
+impl RulePreset {
+    pub const ALL: [RulePreset; 7] = [
+        RulePreset::OnlineStandard,
+        RulePreset::Wrc,
+        RulePreset::Ema,
+        RulePreset::Jpml,
+        RulePreset::ProLeague,
+        RulePreset::ChineseOfficial,
+        RulePreset::HongKong,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RulePreset::OnlineStandard => "online-standard",
+            RulePreset::Wrc => "wrc",
+            RulePreset::Ema => "ema",
+            RulePreset::Jpml => "jpml",
+            RulePreset::ProLeague => "pro-league",
+            RulePreset::ChineseOfficial => "chinese-official",
+            RulePreset::HongKong => "hong-kong",
+        }
+    }
+
+    pub fn rule(&self) -> RuleConfig {
+        match self {
+            RulePreset::OnlineStandard => RuleConfig::default(),
+            RulePreset::Wrc => RuleConfig {
+                rounds_up_to_mangan: true,
+                multiple_ron: MultipleRon::HeadBump,
+                ..tournament_rule()
+            },
+            RulePreset::Ema => tournament_rule(),
+            RulePreset::Jpml => RuleConfig {
+                has_ippatsu: false,
+                has_ura_dora: false,
+                multiple_ron: MultipleRon::HeadBump,
+                ..tournament_rule()
+            },
+            RulePreset::ProLeague => RuleConfig {
+                multiple_ron: MultipleRon::HeadBump,
+                has_double_yakuman: false,
+                stacks_yakuman: false,
+                double_wind_pair_fu: 2,
+                rounds_up_to_mangan: true,
+                ends_on_bust: false,
+                uma: vec![30000, 10000, -10000, -30000],
+                abortive_draws: AbortiveDraws {
+                    triple_ron: false,
+                    ..AbortiveDraws::default()
+                },
+                ..RuleConfig::default()
+            },
+            RulePreset::ChineseOfficial => RuleConfig {
+                has_dealer_repeat: false,
+                payment_structure: PaymentStructure::ChineseOfficial,
+                ..flower_rule()
+            },
+            RulePreset::HongKong => RuleConfig {
+                payment_structure: PaymentStructure::HongKong,
+                ..flower_rule()
+            },
+        }
+    }
+}
+
+/// 30000 points without 岡, red fives, 流し満貫, double yakuman, 数え役満, 飛び nor 途中流局. A
+/// double wind pair is 2 fu.
+fn tournament_rule() -> RuleConfig {
+    RuleConfig {
+        initial_point: 30000,
+        target_point: 30000,
+        allows_nagashi_mangan: false,
+        has_double_yakuman: false,
+        stacks_yakuman: false,
+        double_wind_pair_fu: 2,
+        counts_yakuman: false,
+        ends_on_bust: false,
+        uma: vec![15000, 5000, -5000, -15000],
+        red_fives: RedFives {
+            characters: 0,
+            circles: 0,
+            bamboos: 0,
+        },
+        abortive_draws: AbortiveDraws {
+            nine_terminals: false,
+            four_winds: false,
+            four_riichi: false,
+            triple_ron: false,
+        },
+        ..RuleConfig::default()
+    }
+}
+
+/// Rules with flowers and without 立直, フリテン nor ドラ, from zero points through the four winds.
+/// 頭ハネ, without 流し満貫, 飛び nor 途中流局. The yakuman and fu options of the Japanese rule
+/// are set to the plainest, since the hands are scored in fan.
+fn flower_rule() -> RuleConfig {
+    RuleConfig {
+        initial_point: 0,
+        target_point: 0,
+        game_length: GameLength::Full,
+        allows_riichi: false,
+        has_furiten: false,
+        has_ippatsu: false,
+        has_ura_dora: false,
+        allows_nagashi_mangan: false,
+        has_double_yakuman: false,
+        stacks_yakuman: false,
+        double_wind_pair_fu: 2,
+        counts_yakuman: false,
+        extracts_bonus_tiles: true,
+        multiple_ron: MultipleRon::HeadBump,
+        ends_on_bust: false,
+        uma: vec![0, 0, 0, 0],
+        red_fives: RedFives {
+            characters: 0,
+            circles: 0,
+            bamboos: 0,
+        },
+        abortive_draws: AbortiveDraws {
+            nine_terminals: false,
+            four_winds: false,
+            four_riichi: false,
+            triple_ron: false,
+        },
+        ..RuleConfig::default()
+    }
+}
+
+impl FromStr for RulePreset {
+    type Err = RuleConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RulePreset::ALL
+            .iter()
+            .find(|preset| preset.name() == s)
+            .copied()
+            .ok_or_else(|| RuleConfigError::UnknownPreset(s.to_string()))
+    }
+}
+
+impl fmt::Display for RulePreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys_of_diff(preset: RulePreset, base: RulePreset) -> Vec<String> {
+        preset
+            .rule()
+            .diff(&base.rule())
+            .into_iter()
+            .map(|d| d.key)
+            .collect()
+    }
+
+    #[test]
+    fn every_preset_is_valid_and_found_by_name() {
+        for preset in RulePreset::ALL.iter() {
+            assert!(preset.rule().validate().is_ok(), "{}", preset);
+            assert_eq!(preset.name().parse::<RulePreset>().unwrap(), *preset);
+        }
+        assert!(matches!(
+            "tenhou".parse::<RulePreset>(),
+            Err(RuleConfigError::UnknownPreset(name)) if name == "tenhou"
+        ));
+    }
+
+    #[test]
+    fn presets_differ_in_their_own_rules() {
+        assert_eq!(
+            keys_of_diff(RulePreset::Wrc, RulePreset::Ema),
+            vec!["multiple_ron", "rounds_up_to_mangan"]
+        );
+        assert_eq!(
+            keys_of_diff(RulePreset::ChineseOfficial, RulePreset::HongKong),
+            vec!["has_dealer_repeat", "payment_structure"]
+        );
+    }
+
+    #[test]
+    fn flower_rules_have_no_japanese_yakuman_options() {
+        for preset in [RulePreset::ChineseOfficial, RulePreset::HongKong] {
+            let rule = preset.rule();
+            assert!(!rule.has_double_yakuman);
+            assert!(!rule.stacks_yakuman);
+            assert!(!rule.counts_yakuman);
+            assert_eq!(rule.double_wind_pair_fu, 2);
+        }
+    }
+}
@@ -1,23 +1,156 @@
-pub(crate) const PLAYERS_COUNT: usize = 4;
+use crate::game::config::RuleConfig;
+use crate::game::table::Round;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use std::fmt::Debug;
 
-pub trait Concept {
-    type Tile: Copy;
-    type Meld;
+/// Tiles and melds used by a rule. Everything in a game is `Send + Sync`, so that tables can run
+/// on any thread.
+pub trait Concept: Send + Sync {
+    /// `Eq` tells physically identical tiles, e.g. a red five differs from a normal five.
+    /// Rule-level identity such as waiting tiles is judged by the specs.
+    type Tile: Copy + Eq + Debug + Send + Sync;
+    type Meld: MeldConcept<Self::Tile> + Clone + Debug + Send + Sync;
+}
+
+pub trait MeldConcept<T> {
+    fn tiles(&self) -> Vec<T>;
+
+    /// The tile taken from another player's discard. `None` for a concealed quad.
+    fn called_tile(&self) -> Option<T>;
+
+    /// 順子
+    fn is_sequence(&self) -> bool;
+
+    /// 槓子
+    fn is_quad(&self) -> bool;
+
+    /// Whether the tiles make the shape, e.g. consecutive numbers of a suit for a sequence, with
+    /// the called tile among them.
+    fn is_well_formed(&self) -> bool;
+
+    /// Whether discarding the tile right after making this meld is a swap-call (食い替え).
+    fn is_swap_calling(&self, tile: T) -> bool;
+}
+
+/// Seat at the table, fixed through the game. `East` is the first dealer (起家).
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Seat {
+    East,
+    South,
+    West,
+    North,
+}
+
+impl From<usize> for Seat {
+    fn from(value: usize) -> Self {
+        use Seat::*;
+
+        match value {
+            0 => East,
+            1 => South,
+            2 => West,
+            3 => North,
+            _ => panic!("Invalid value: {}", value),
+        }
+    }
+}
+
+impl From<Seat> for usize {
+    fn from(seat: Seat) -> Self {
+        use Seat::*;
+
+        match seat {
+            East => 0,
+            South => 1,
+            West => 2,
+            North => 3,
+        }
+    }
+}
+
+/// Number of players at a table, from 2 to 4. Seats are taken from `East` in order.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize)]
+#[serde(transparent)]
+pub struct PlayersCount(usize);
+
+impl PlayersCount {
+    pub const FOUR: PlayersCount = PlayersCount(4);
+    /// 三麻
+    pub const THREE: PlayersCount = PlayersCount(3);
+    /// 二人麻雀
+    pub const TWO: PlayersCount = PlayersCount(2);
+
+    pub fn new(count: usize) -> Option<PlayersCount> {
+        (2..=4).contains(&count).then_some(PlayersCount(count))
+    }
+
+    pub fn get(&self) -> usize {
+        self.0
+    }
+
+    pub fn seats(&self) -> impl Iterator<Item = Seat> {
+        (0..self.0).map(Seat::from)
+    }
+
+    /// The seat to the right, i.e. the next in turn order.
+    pub fn next_seat(&self, seat: Seat) -> Seat {
+        ((usize::from(seat) + 1) % self.0).into()
+    }
+}
+
+impl Default for PlayersCount {
+    fn default() -> Self {
+        PlayersCount::FOUR
+    }
+}
+
+impl<'de> Deserialize<'de> for PlayersCount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let count = usize::deserialize(deserializer)?;
+        PlayersCount::new(count).ok_or_else(|| {
+            D::Error::custom(format!("players count should be 2 to 4, got {}", count))
+        })
+    }
+}
+
+/// Identifies a participant of tables. Generated by the host, e.g. for each user session.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ParticipantId(uuid::Uuid);
+
+impl ParticipantId {
+    pub fn generate() -> ParticipantId {
+        ParticipantId(uuid::Uuid::new_v4())
+    }
+}
+
+impl From<uuid::Uuid> for ParticipantId {
+    fn from(uuid: uuid::Uuid) -> Self {
+        ParticipantId(uuid)
+    }
+}
+
+impl fmt::Display for ParticipantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "participant {}", self.0)
+    }
 }
 
 pub struct DealtResult<C: Concept> {
     pub(crate) wall_tiles: Vec<C::Tile>,
     pub(crate) supplemental_tiles: Vec<C::Tile>,
     pub(crate) reward_indication_tiles: Vec<C::Tile>,
-    pub(crate) player_tiles: [(Vec<C::Tile>, SeatOld); PLAYERS_COUNT],
+    pub(crate) player_tiles: Vec<(Vec<C::Tile>, Seat)>,
 }
 
 impl<C: Concept> DealtResult<C> {
-    pub(crate) fn new(
+    pub fn new(
         wall_tiles: Vec<C::Tile>,
         supplemental_tiles: Vec<C::Tile>,
         reward_indication_tiles: Vec<C::Tile>,
-        player_tiles: [(Vec<C::Tile>, SeatOld); PLAYERS_COUNT],
+        player_tiles: Vec<(Vec<C::Tile>, Seat)>,
     ) -> DealtResult<C> {
         DealtResult {
             wall_tiles,
@@ -28,48 +161,149 @@ impl<C: Concept> DealtResult<C> {
     }
 }
 
-pub trait TileDealingSpec<C: Concept> {
-    fn deal(&self) -> DealtResult<C>;
+pub trait SeatingSpec: Send + Sync {
+    /// The participants in order of `Seat`, i.e. East first.
+    fn arrange(&self, participants: &[ParticipantId]) -> Vec<ParticipantId>;
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
-pub(crate) enum SeatOld {
-    East,
-    South,
-    West,
-    North,
+pub trait TileDealingSpec<C: Concept>: Send + Sync {
+    /// Should deal a hand to each seat of `rule.players_count`, following e.g. `rule.red_fives`.
+    fn deal(&self, rule: &RuleConfig) -> DealtResult<C>;
 }
 
-impl SeatOld {
-    pub(crate) fn next_seat(&self) -> SeatOld {
-        ((usize::from(*self) + 1) % 4).into()
+pub trait HandJudgementSpec<C: Concept>: Send + Sync {
+    /// Tiles which complete the hand. Empty if the hand is not ready (聴牌).
+    fn waiting_tiles(&self, concealed_tiles: &[C::Tile], exposed_melds: &[C::Meld])
+        -> Vec<C::Tile>;
+
+    fn is_waiting_on(
+        &self,
+        concealed_tiles: &[C::Tile],
+        exposed_melds: &[C::Meld],
+        tile: C::Tile,
+    ) -> bool {
+        self.waiting_tiles(concealed_tiles, exposed_melds)
+            .contains(&tile)
     }
-}
 
-impl From<usize> for SeatOld {
-    fn from(value: usize) -> Self {
-        use SeatOld::*;
+    fn is_ready(&self, concealed_tiles: &[C::Tile], exposed_melds: &[C::Meld]) -> bool {
+        !self
+            .waiting_tiles(concealed_tiles, exposed_melds)
+            .is_empty()
+    }
 
-        match value {
-            0 => East,
-            1 => South,
-            2 => West,
-            3 => North,
-            _ => panic!(format!("Invalid value: {}", value)),
-        }
+    /// Whether the hand including the last drawn or called tile is completed.
+    fn is_completed(&self, concealed_tiles: &[C::Tile], exposed_melds: &[C::Meld]) -> bool {
+        (0..concealed_tiles.len()).any(|i| {
+            let mut rest = concealed_tiles.to_vec();
+            let tile = rest.remove(i);
+            self.is_waiting_on(&rest, exposed_melds, tile)
+        })
+    }
+
+    /// 么九牌
+    fn is_terminal_or_honor(&self, tile: C::Tile) -> bool;
+
+    /// 風牌, for 四風連打.
+    fn is_wind(&self, _tile: C::Tile) -> bool {
+        false
+    }
+
+    /// 抜きドラ, e.g. North in 三麻. Only extracted when the rule says so.
+    fn is_bonus_tile(&self, _tile: C::Tile) -> bool {
+        false
+    }
+
+    /// 包. Whether the last exposed meld decides a yakuman by itself, e.g. the third dragon
+    /// triplet of 大三元, so that the seat who fed it is liable for the yakuman.
+    fn is_liable_meld(&self, _exposed_melds: &[C::Meld]) -> bool {
+        false
+    }
+
+    /// 点数計算 of a completed hand.
+    fn score(&self, hand: &WinningHand<C>, rule: &RuleConfig) -> HandScore;
+
+    /// Whether the completed hand may win, i.e. it is worth something (役無し otherwise).
+    fn is_scorable(&self, hand: &WinningHand<C>, rule: &RuleConfig) -> bool {
+        self.score(hand, rule).basic_points > 0
     }
 }
 
-impl From<SeatOld> for usize {
-    fn from(seat: SeatOld) -> Self {
-        use SeatOld::*;
+/// A completed hand with what happened around it, to be scored.
+#[derive(Clone, Debug)]
+pub struct WinningHand<C: Concept> {
+    /// Excluding the winning tile.
+    pub concealed_tiles: Vec<C::Tile>,
+    pub exposed_melds: Vec<C::Meld>,
+    pub winning_tile: C::Tile,
+    /// 抜きドラ
+    pub bonus_tiles: Vec<C::Tile>,
+    pub seat: Seat,
+    pub dealer: Seat,
+    pub round: Round,
+    /// `None` for 自摸和了.
+    pub discarder: Option<Seat>,
+    pub is_ready_declared: bool,
+    /// 両立直, declared in the first turn without calls before.
+    pub is_ready_declared_first: bool,
+    /// 一発, before the own next discard after declaring ready, without calls between.
+    pub is_right_after_ready: bool,
+    /// 海底・河底
+    pub is_last_tile: bool,
+    /// 嶺上牌, self-drawn as a supplemental tile.
+    pub is_supplemental_tile: bool,
+    /// Before the own first discard without calls before, for 天和・地和.
+    pub is_first_draw: bool,
+    /// Discards not called and exposed melds of every seat, without the winning tile, for 和绝张.
+    pub visible_tiles: Vec<C::Tile>,
+    /// ドラ表示牌
+    pub reward_indication_tiles: Vec<C::Tile>,
+    /// 裏ドラ表示牌. Empty unless the hand is declared ready under `rule.has_ura_dora`.
+    pub hidden_reward_indication_tiles: Vec<C::Tile>,
+    /// 包. The seat who fed the meld deciding a yakuman.
+    pub liable_seat: Option<Seat>,
+}
 
-        match seat {
-            East => 0,
-            South => 1,
-            West => 2,
-            North => 3,
-        }
+/// What a completed hand is worth.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct HandScore {
+    /// In the unit of `RuleConfig::payment_structure`, zero without yaku or below the minimum:
+    ///
+    /// - `Japanese`: 基本点, a quarter of what a non-dealer gets on 栄和.
+    /// - `ChineseOfficial`: 番, without the 8 points paid by each other player.
+    /// - `HongKong`: 番 (faan), capped at `HongKongScoring::max_faan` and doubled into the payout.
+    pub basic_points: i32,
+    /// The part of `basic_points` the liable seat pays for, i.e. the yakuman decided by the
+    /// fed meld. Zero without `WinningHand::liable_seat`.
+    pub liable_basic_points: i32,
+}
+
+/// フリテン
+#[derive(Copy, Clone, Default, Debug)]
+pub struct FuritenStatus {
+    pub(crate) is_permanent: bool,
+    pub(crate) is_temporary: bool,
+    pub(crate) is_after_ready: bool,
+}
+
+impl FuritenStatus {
+    /// Own discarded tiles include a waiting tile.
+    pub fn is_permanent(&self) -> bool {
+        self.is_permanent
+    }
+
+    /// Missed a completion since the own last discard (同巡内フリテン).
+    pub fn is_temporary(&self) -> bool {
+        self.is_temporary
+    }
+
+    /// Missed a completion after declaring ready (立直後フリテン).
+    pub fn is_after_ready(&self) -> bool {
+        self.is_after_ready
+    }
+
+    pub fn is_furiten(&self) -> bool {
+        self.is_permanent || self.is_temporary || self.is_after_ready
     }
 }
 
@@ -79,8 +313,75 @@ pub enum Action<C: Concept> {
     MakeMeld(C::Meld),
     DeclareReady(C::Tile),
     DeclareCompletion,
+    /// 抜きドラ. A supplemental tile is drawn instead, so the turn continues.
+    ExtractBonusTile(C::Tile),
+    /// 九種九牌, an abortive draw in the first turn without calls before.
+    DeclareNineTerminals,
+    /// Shows the ready hand at exhaustive draw. `Pass` hides it (聴牌隠し), unless the hand is
+    /// declared ready, which is always shown.
+    RevealReadyHand,
 }
 
-pub trait ActionPolicy<C: Concept> {
+pub trait ActionPolicy<C: Concept>: Send + Sync {
     fn action_after_draw(&self, drawn_tile: C::Tile) -> Action<C>;
+
+    /// `DeclareCompletion` or `MakeMeld` with the discarded tile. Others are treated as `Pass`.
+    fn action_after_discard(&self, _discarded_tile: C::Tile, _furiten: FuritenStatus) -> Action<C> {
+        Action::Pass
+    }
+
+    /// Should be `Discard` of one of `discardable_tiles`. By default, the choice as if the last of
+    /// them were drawn, falling back to discarding it.
+    fn action_after_call(&self, discardable_tiles: &[C::Tile]) -> Action<C> {
+        match discardable_tiles.last() {
+            Some(&tile) => match self.action_after_draw(tile) {
+                Action::Discard(t) if discardable_tiles.contains(&t) => Action::Discard(t),
+                _ => Action::Discard(tile),
+            },
+            None => Action::Pass,
+        }
+    }
+
+    /// Whether to show the hand as ready at exhaustive draw. Only asked when the hand is ready
+    /// without declaring it, so returning `false` hides it (聴牌隠し).
+    fn declare_ready_at_exhaustive_draw(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn players_count_is_deserialized_from_2_to_4() {
+        assert_eq!(
+            serde_json::from_str::<PlayersCount>("3").unwrap(),
+            PlayersCount::THREE
+        );
+        for count in ["1", "5"] {
+            let error = serde_json::from_str::<PlayersCount>(count).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                format!("players count should be 2 to 4, got {}", count)
+            );
+        }
+    }
+
+    #[test]
+    fn seats_wrap_around_by_the_players_count() {
+        use Seat::*;
+
+        let players_count = PlayersCount::THREE;
+        assert_eq!(
+            players_count.seats().collect::<Vec<_>>(),
+            vec![East, South, West]
+        );
+        assert_eq!(players_count.next_seat(South), West);
+        assert_eq!(players_count.next_seat(West), East);
+
+        let players_count = PlayersCount::TWO;
+        assert_eq!(players_count.seats().collect::<Vec<_>>(), vec![East, South]);
+        assert_eq!(players_count.next_seat(South), East);
+    }
 }
@@ -0,0 +1,606 @@
+use crate::game::config::error::RuleConfigError;
+use crate::game::config::RuleConfig;
+use crate::game::def::{
+    Action, ActionPolicy, Concept, HandJudgementSpec, ParticipantId, SeatingSpec, TileDealingSpec,
+};
+use crate::game::table::{
+    GameEndedTable, HandPlayingTable, Phase, PhaseKind, ReadyTable, TableId, TileIndex,
+    WaitingTable,
+};
+use crate::game::view::{Standing, TableView};
+use std::fmt::Debug;
+use std::sync::Arc;
+
+pub(crate) mod error;
+
+use error::TableError;
+
+/// A table driven by participants' actions. Draws and dealing proceed automatically.
+///
+/// The flow is `join` by each player → `arrange_seats` → `start` → `submit_action` … until the hand ends,
+/// then `start_next_hand` until the game ends.
+///
+/// A table is `Send + Sync`, so it can be moved to a worker thread or shared behind a lock.
+///
+/// ```
+/// use mahjong_enterprise_edition_core::game::{ParticipantId, RuleConfig, Table};
+/// use mahjong_enterprise_edition_core::rule::{
+///     RandomSeatingSpec, StandardConcept, StandardHandJudgementSpec, StandardTileDealingSpec,
+/// };
+/// use std::sync::{Arc, Mutex};
+/// use std::thread;
+///
+/// let table = Arc::new(Mutex::new(Table::<StandardConcept>::new(
+///     RuleConfig::default(),
+///     Box::new(RandomSeatingSpec),
+///     Box::new(StandardTileDealingSpec),
+///     Arc::new(StandardHandJudgementSpec::default()),
+/// )
+/// .unwrap()));
+/// let handles: Vec<_> = (0..4)
+///     .map(|_| {
+///         let table = Arc::clone(&table);
+///         thread::spawn(move || table.lock().unwrap().join(ParticipantId::generate()))
+///     })
+///     .collect();
+/// for handle in handles {
+///     handle.join().unwrap().unwrap();
+/// }
+/// ```
+pub struct Table<C: Concept> {
+    state: Option<TableState<C>>,
+    seating_spec: Box<dyn SeatingSpec>,
+    tile_dealing_spec: Box<dyn TileDealingSpec<C>>,
+    hand_judgement_spec: Arc<dyn HandJudgementSpec<C>>,
+}
+
+enum TableState<C: Concept> {
+    Waiting(WaitingTable<C>),
+    Ready(ReadyTable<C>),
+    Playing(Box<HandPlayingTable<C>>),
+    Ended(GameEndedTable<C>),
+}
+
+/// Where the table is in its flow.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum TableStatus {
+    WaitingForParticipants,
+    ReadyToStart,
+    Playing(PhaseKind),
+    GameEnded,
+}
+
+impl<C: Concept> Table<C> {
+    /// Fails if the rule is inconsistent.
+    pub fn new(
+        rule: RuleConfig,
+        seating_spec: Box<dyn SeatingSpec>,
+        tile_dealing_spec: Box<dyn TileDealingSpec<C>>,
+        hand_judgement_spec: Arc<dyn HandJudgementSpec<C>>,
+    ) -> Result<Self, RuleConfigError> {
+        rule.validate()?;
+
+        Ok(Self {
+            state: Some(TableState::Waiting(WaitingTable::setup(rule))),
+            seating_spec,
+            tile_dealing_spec,
+            hand_judgement_spec,
+        })
+    }
+
+    pub fn id(&self) -> &TableId {
+        match self.state() {
+            TableState::Waiting(table) => table.id(),
+            TableState::Ready(table) => table.id(),
+            TableState::Playing(table) => table.id(),
+            TableState::Ended(table) => table.id(),
+        }
+    }
+
+    pub fn status(&self) -> TableStatus {
+        match self.state() {
+            TableState::Waiting(_) => TableStatus::WaitingForParticipants,
+            TableState::Ready(_) => TableStatus::ReadyToStart,
+            TableState::Playing(table) => TableStatus::Playing(table.phase().kind()),
+            TableState::Ended(_) => TableStatus::GameEnded,
+        }
+    }
+
+    pub fn join(&mut self, participant_id: ParticipantId) -> Result<(), TableError<C::Tile>> {
+        match self.state() {
+            TableState::Waiting(table) => table.validate_participant(&participant_id)?,
+            TableState::Ready(_) => return Err(TableError::AlreadyGathered),
+            TableState::Playing(_) | TableState::Ended(_) => {
+                return Err(TableError::AlreadyStarted)
+            }
+        }
+
+        let table = match self.take_state() {
+            TableState::Waiting(table) => table,
+            _ => unreachable!(),
+        }
+        .accept_participant(participant_id)
+        .expect("The participant should be validated");
+        self.state = Some(match table.be_ready() {
+            Ok(table) => TableState::Ready(table),
+            Err(table) => TableState::Waiting(table),
+        });
+        Ok(())
+    }
+
+    /// Only before the participants gather.
+    pub fn leave(&mut self, participant_id: ParticipantId) -> Result<(), TableError<C::Tile>> {
+        match self.state() {
+            TableState::Waiting(table) => table.validate_leaving(&participant_id)?,
+            TableState::Ready(_) => return Err(TableError::AlreadyGathered),
+            TableState::Playing(_) | TableState::Ended(_) => {
+                return Err(TableError::AlreadyStarted)
+            }
+        }
+
+        let table = match self.take_state() {
+            TableState::Waiting(table) => table,
+            _ => unreachable!(),
+        }
+        .send_off_participant(participant_id)
+        .expect("The participant should be validated");
+        self.state = Some(TableState::Waiting(table));
+        Ok(())
+    }
+
+    /// Arranges the seats by the seating spec. Can be done again before starting.
+    pub fn arrange_seats(&mut self) -> Result<(), TableError<C::Tile>> {
+        match self.take_state() {
+            TableState::Ready(table) => {
+                let table = table.arrange_initial_seating(self.seating_spec.as_ref());
+                self.state = Some(TableState::Ready(table));
+                Ok(())
+            }
+            state => {
+                let error = match state {
+                    TableState::Waiting(_) => TableError::NotGathered,
+                    _ => TableError::AlreadyStarted,
+                };
+                self.state = Some(state);
+                Err(error)
+            }
+        }
+    }
+
+    /// Starts the game and deals the first hand.
+    pub fn start(&mut self) -> Result<(), TableError<C::Tile>> {
+        match self.state() {
+            TableState::Waiting(_) => return Err(TableError::NotGathered),
+            TableState::Ready(table) => table.validate_start()?,
+            TableState::Playing(_) | TableState::Ended(_) => {
+                return Err(TableError::AlreadyStarted)
+            }
+        }
+
+        let table = match self.take_state() {
+            TableState::Ready(table) => table,
+            _ => unreachable!(),
+        }
+        .start_game(self.hand_judgement_spec.clone())
+        .expect("The seats should be arranged");
+        let table = Self::proceed(table.deal(self.tile_dealing_spec.as_ref()));
+        self.state = Some(TableState::Playing(Box::new(table)));
+        Ok(())
+    }
+
+    /// Settles the ended hand and deals the next one, unless the game is over.
+    pub fn start_next_hand(&mut self) -> Result<(), TableError<C::Tile>> {
+        match self.state() {
+            TableState::Playing(table) if table.is_ended() => {}
+            TableState::Playing(_) => return Err(TableError::HandNotEnded),
+            TableState::Ended(_) => return Err(TableError::GameEnded),
+            _ => return Err(TableError::NotStarted),
+        }
+
+        let table = match self.take_state() {
+            TableState::Playing(table) => *table,
+            _ => unreachable!(),
+        }
+        .finish();
+        self.state = Some(if table.is_game_over() {
+            TableState::Ended(table.end_game())
+        } else {
+            let table = Self::proceed(table.deal(self.tile_dealing_spec.as_ref()));
+            TableState::Playing(Box::new(table))
+        });
+        Ok(())
+    }
+
+    /// The final result from the top. `None` until the game ends.
+    pub fn standings(&self) -> Option<Vec<Standing>> {
+        match self.state() {
+            TableState::Ended(table) => Some(table.standings()),
+            _ => None,
+        }
+    }
+
+    /// `DeclareCompletion` means 自摸和了 in the own turn and 栄和 on a discarded tile.
+    /// `Pass` also hides a ready hand at exhaustive draw, unless it is declared ready.
+    pub fn submit_action(
+        &mut self,
+        participant_id: &ParticipantId,
+        action: Action<C>,
+    ) -> Result<(), TableError<C::Tile>> {
+        let table = match self.state() {
+            TableState::Playing(table) => table,
+            TableState::Ended(_) => return Err(TableError::GameEnded),
+            _ => return Err(TableError::NotStarted),
+        };
+        let participant_id = participant_id.clone();
+
+        match action {
+            Action::Discard(tile) => {
+                let tile_index = Self::index_of(table, &participant_id, tile)?;
+                table.validate_discard_by(tile_index, &participant_id)?;
+                self.update(|t| t.discard_tile_by(tile_index, participant_id))
+            }
+            Action::DeclareReady(tile) => {
+                let tile_index = Self::index_of(table, &participant_id, tile)?;
+                table.validate_declare_ready_by(tile_index, &participant_id)?;
+                self.update(|t| t.declare_ready_by(tile_index, participant_id))
+            }
+            Action::ExtractBonusTile(tile) => {
+                let tile_index = Self::index_of(table, &participant_id, tile)?;
+                table.validate_extract_bonus_tile_by(tile_index, &participant_id)?;
+                self.update(|t| t.extract_bonus_tile_by(tile_index, participant_id))
+            }
+            Action::DeclareNineTerminals => {
+                table.validate_declare_nine_terminals_by(&participant_id)?;
+                self.update(|t| t.declare_nine_terminals_by(participant_id))
+            }
+            Action::DeclareCompletion => match table.phase() {
+                Phase::AwaitingClaims(_) => {
+                    table.validate_claim_completion_by(&participant_id)?;
+                    self.update(|t| t.claim_completion_by(participant_id))
+                }
+                _ => {
+                    table.validate_declare_completion_by(&participant_id)?;
+                    self.update(|t| t.declare_completion_by(participant_id))
+                }
+            },
+            Action::MakeMeld(meld) => {
+                table.validate_make_meld_by(&meld, &participant_id)?;
+                self.update(|t| t.make_meld_by(meld, participant_id))
+            }
+            Action::Pass => match table.phase() {
+                Phase::AwaitingReadyDeclarations(_) => {
+                    table.validate_declare_ready_at_exhaustive_draw_by(&participant_id)?;
+                    self.update(|t| t.declare_ready_at_exhaustive_draw_by(false, participant_id))
+                }
+                _ => {
+                    table.validate_pass_by(&participant_id)?;
+                    self.update(|t| t.pass_by(participant_id))
+                }
+            },
+            Action::RevealReadyHand => {
+                table.validate_declare_ready_at_exhaustive_draw_by(&participant_id)?;
+                self.update(|t| t.declare_ready_at_exhaustive_draw_by(true, participant_id))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Submits the action chosen by the policy for the current phase.
+    pub fn submit_action_by_policy(
+        &mut self,
+        participant_id: &ParticipantId,
+        policy: &dyn ActionPolicy<C>,
+    ) -> Result<(), TableError<C::Tile>> {
+        let table = match self.state() {
+            TableState::Playing(table) => table,
+            TableState::Ended(_) => return Err(TableError::GameEnded),
+            _ => return Err(TableError::NotStarted),
+        };
+        let seat = table.get_seat_of(participant_id);
+
+        let action = match (table.phase(), seat) {
+            (Phase::AwaitingDiscard(turn), Some(seat)) if *turn == seat => {
+                let drawn_tile = table
+                    .last_tile_of(seat)
+                    .expect("The hand should have the drawn tile");
+                policy.action_after_draw(drawn_tile)
+            }
+            (Phase::AwaitingDiscardAfterCall(turn), Some(seat)) if *turn == seat => {
+                policy.action_after_call(&table.discardable_tiles_of(seat))
+            }
+            (Phase::AwaitingClaims(_), Some(seat)) => {
+                let discarded_tile = table
+                    .discarded_tile()
+                    .expect("A tile should be awaiting claims");
+                match policy.action_after_discard(discarded_tile, table.furiten_of(seat)) {
+                    action @ Action::DeclareCompletion | action @ Action::MakeMeld(_) => action,
+                    _ => Action::Pass,
+                }
+            }
+            (Phase::AwaitingReadyDeclarations(_), Some(_)) => {
+                if policy.declare_ready_at_exhaustive_draw() {
+                    Action::RevealReadyHand
+                } else {
+                    Action::Pass
+                }
+            }
+            // Let `submit_action` tell why nothing can be done.
+            _ => Action::Pass,
+        };
+
+        self.submit_action(participant_id, action)
+    }
+
+    /// `None` unless a hand is being played with the participant seated.
+    pub fn view_for(&self, participant_id: &ParticipantId) -> Option<TableView<C>> {
+        match self.state() {
+            TableState::Playing(table) => table
+                .get_seat_of(participant_id)
+                .map(|seat| table.view_for(seat)),
+            _ => None,
+        }
+    }
+
+    fn state(&self) -> &TableState<C> {
+        self.state
+            .as_ref()
+            .expect("The state should be put back after each operation")
+    }
+
+    fn take_state(&mut self) -> TableState<C> {
+        self.state
+            .take()
+            .expect("The state should be put back after each operation")
+    }
+
+    fn index_of(
+        table: &HandPlayingTable<C>,
+        participant_id: &ParticipantId,
+        tile: C::Tile,
+    ) -> Result<TileIndex, TableError<C::Tile>> {
+        table
+            .get_seat_of(participant_id)
+            .and_then(|seat| table.index_of(seat, tile))
+            .ok_or_else(|| TableError::TileNotInHand {
+                participant: participant_id.clone(),
+                tile,
+            })
+    }
+
+    /// Applies the validated operation, so that the table is never lost on errors.
+    fn update<E: Debug>(
+        &mut self,
+        operation: impl FnOnce(HandPlayingTable<C>) -> Result<HandPlayingTable<C>, E>,
+    ) {
+        let table = match self.take_state() {
+            TableState::Playing(table) => table,
+            _ => unreachable!(),
+        };
+        let table = operation(*table).expect("The operation should be validated");
+        self.state = Some(TableState::Playing(Box::new(Self::proceed(table))));
+    }
+
+    /// Draws automatically, since no decision is needed.
+    fn proceed(table: HandPlayingTable<C>) -> HandPlayingTable<C> {
+        let mut table = table;
+        while let Phase::AwaitingDraw(seat) = *table.phase() {
+            let participant_id = table.participant_at(seat).clone();
+            table = table
+                .draw_tile_by(participant_id)
+                .expect("The wall should have tiles while awaiting a draw");
+        }
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::def::Seat;
+    use crate::game::table::error::{ClaimError, JoinError};
+    use crate::game::table::tests::{dealer_ready_hand, FixedTileDealingSpec, InOrderSeatingSpec};
+    use crate::rule::{
+        tiles_of, Decomposition, StandardConcept, StandardHandJudgementSpec, WinContext, Yaku,
+        YakuEvaluator,
+    };
+
+    fn table_of(rule: RuleConfig, dealing_spec: FixedTileDealingSpec) -> Table<StandardConcept> {
+        table_judged_by(rule, dealing_spec, StandardHandJudgementSpec::default())
+    }
+
+    fn table_judged_by(
+        rule: RuleConfig,
+        dealing_spec: FixedTileDealingSpec,
+        hand_judgement_spec: StandardHandJudgementSpec,
+    ) -> Table<StandardConcept> {
+        Table::new(
+            rule,
+            Box::new(InOrderSeatingSpec),
+            Box::new(dealing_spec),
+            Arc::new(hand_judgement_spec),
+        )
+        .unwrap()
+    }
+
+    /// 門前清栄和, a house yaku.
+    struct ConcealedRon;
+
+    impl Yaku for ConcealedRon {
+        fn name(&self) -> &str {
+            "concealed-ron"
+        }
+
+        fn han(&self, is_concealed: bool) -> Option<u8> {
+            if is_concealed {
+                Some(1)
+            } else {
+                None
+            }
+        }
+
+        fn applies(&self, _decomposition: &Decomposition, context: &WinContext) -> bool {
+            !context.is_self_drawn
+        }
+    }
+
+    /// Started with the dealer to complete the hand on the first draw of 4s.
+    fn started_table(rule: RuleConfig) -> (Table<StandardConcept>, Vec<ParticipantId>) {
+        let dealing_spec = FixedTileDealingSpec {
+            wall_tiles: tiles_of("9m4s"),
+            ..dealer_ready_hand()
+        };
+        start(table_of(rule, dealing_spec))
+    }
+
+    /// Four participants join and sit in order.
+    fn start(table: Table<StandardConcept>) -> (Table<StandardConcept>, Vec<ParticipantId>) {
+        let mut table = table;
+        let participants: Vec<_> = (0..4).map(|_| ParticipantId::generate()).collect();
+        for participant in participants.iter() {
+            table.join(participant.clone()).unwrap();
+        }
+        table.arrange_seats().unwrap();
+        table.start().unwrap();
+        (table, participants)
+    }
+
+    #[test]
+    fn participants_join_and_leave_until_gathered() {
+        let mut table = table_of(RuleConfig::default(), dealer_ready_hand());
+        let participants: Vec<_> = (0..5).map(|_| ParticipantId::generate()).collect();
+        table.join(participants[0].clone()).unwrap();
+        assert!(matches!(
+            table.join(participants[0].clone()),
+            Err(TableError::Join(JoinError::AlreadyJoined(_)))
+        ));
+        table.leave(participants[0].clone()).unwrap();
+        assert!(matches!(
+            table.leave(participants[0].clone()),
+            Err(TableError::Join(JoinError::NotJoined(_)))
+        ));
+
+        for participant in participants[..4].iter() {
+            table.join(participant.clone()).unwrap();
+        }
+        assert_eq!(table.status(), TableStatus::ReadyToStart);
+        assert!(matches!(
+            table.join(participants[4].clone()),
+            Err(TableError::AlreadyGathered)
+        ));
+        assert!(matches!(
+            table.leave(participants[0].clone()),
+            Err(TableError::AlreadyGathered)
+        ));
+    }
+
+    #[test]
+    fn seats_are_arranged_after_gathering() {
+        let mut table = table_of(RuleConfig::default(), dealer_ready_hand());
+        for _ in 0..3 {
+            table.join(ParticipantId::generate()).unwrap();
+        }
+        assert!(matches!(
+            table.arrange_seats(),
+            Err(TableError::NotGathered)
+        ));
+        assert!(matches!(table.start(), Err(TableError::NotGathered)));
+        assert_eq!(table.status(), TableStatus::WaitingForParticipants);
+
+        table.join(ParticipantId::generate()).unwrap();
+        assert!(matches!(table.start(), Err(TableError::Seat(_))));
+        table.arrange_seats().unwrap();
+        table.start().unwrap();
+        assert_eq!(
+            table.status(),
+            TableStatus::Playing(PhaseKind::AwaitingDiscard)
+        );
+        assert!(matches!(
+            table.arrange_seats(),
+            Err(TableError::AlreadyStarted)
+        ));
+    }
+
+    #[test]
+    fn next_hand_is_started_after_the_hand_ends() {
+        let (mut table, participants) = started_table(RuleConfig::default());
+        assert!(matches!(
+            table.start_next_hand(),
+            Err(TableError::HandNotEnded)
+        ));
+        table
+            .submit_action(&participants[0], Action::DeclareCompletion)
+            .unwrap();
+        assert_eq!(table.status(), TableStatus::Playing(PhaseKind::Ended));
+        table.start_next_hand().unwrap();
+        assert_eq!(
+            table.status(),
+            TableStatus::Playing(PhaseKind::AwaitingDiscard)
+        );
+    }
+
+    #[test]
+    fn game_ends_instead_of_the_next_hand() {
+        let rule = RuleConfig {
+            initial_point: 10000,
+            target_point: 10000,
+            ends_on_bust: true,
+            ..RuleConfig::default()
+        };
+        let (mut table, participants) = started_table(rule);
+        table
+            .submit_action(&participants[0], Action::DeclareCompletion)
+            .unwrap();
+        // 天和 busts the others.
+        table.start_next_hand().unwrap();
+        assert_eq!(table.status(), TableStatus::GameEnded);
+        assert_eq!(table.standings().unwrap()[0].participant, participants[0]);
+        assert!(matches!(
+            table.start_next_hand(),
+            Err(TableError::GameEnded)
+        ));
+    }
+
+    #[test]
+    fn view_hides_concealed_tiles_of_the_others() {
+        let (table, participants) = started_table(RuleConfig::default());
+        let view = table.view_for(&participants[1]).unwrap();
+        assert_eq!(view.seat, Seat::South);
+        assert_eq!(view.concealed_tiles, dealer_ready_hand().player_tiles[1]);
+        let counts: Vec<_> = view.seats.iter().map(|s| s.concealed_tiles_count).collect();
+        assert_eq!(counts, vec![14, 13, 13, 13]);
+        assert!(table.view_for(&ParticipantId::generate()).is_none());
+    }
+
+    #[test]
+    fn registered_yaku_counts_at_the_table() {
+        let rule = RuleConfig::default();
+        let dealing_spec = || FixedTileDealingSpec {
+            wall_tiles: tiles_of("9m5s"),
+            player_tiles: vec![
+                tiles_of("258m258p258s1234z"),
+                tiles_of("123m789p111s11z46s"),
+                tiles_of("369m369p369s5677z"),
+                tiles_of("147m147p147s5566z"),
+            ],
+        };
+        let mut evaluator = YakuEvaluator::new(&rule);
+        evaluator.register(Box::new(ConcealedRon));
+        let claim_completion_judged_by = |spec| {
+            let (mut table, participants) =
+                start(table_judged_by(rule.clone(), dealing_spec(), spec));
+            table
+                .submit_action(&participants[0], Action::Discard(tiles_of("5s")[0]))
+                .unwrap();
+            table.submit_action(&participants[1], Action::DeclareCompletion)
+        };
+        assert!(matches!(
+            claim_completion_judged_by(StandardHandJudgementSpec::default()),
+            Err(TableError::Claim(ClaimError::NoYaku { .. }))
+        ));
+        assert!(
+            claim_completion_judged_by(StandardHandJudgementSpec::with_evaluator(evaluator))
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,32 @@
+use crate::game::def::ParticipantId;
+use crate::game::table::error::{ClaimError, DeclareError, DiscardError, JoinError, SeatError};
+use std::fmt::Debug;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum TableError<T: Debug + 'static> {
+    #[error("the table is still waiting for participants")]
+    NotGathered,
+    #[error("the participants have already gathered")]
+    AlreadyGathered,
+    #[error("the game has not started yet")]
+    NotStarted,
+    #[error("the game has already started")]
+    AlreadyStarted,
+    #[error("the game has already ended")]
+    GameEnded,
+    #[error("the hand has not ended yet")]
+    HandNotEnded,
+    #[error("{participant} doesn't have {tile:?} in the hand")]
+    TileNotInHand { participant: ParticipantId, tile: T },
+    #[error(transparent)]
+    Join(#[from] JoinError),
+    #[error(transparent)]
+    Seat(#[from] SeatError),
+    #[error(transparent)]
+    Discard(#[from] DiscardError<T>),
+    #[error(transparent)]
+    Claim(#[from] ClaimError<T>),
+    #[error(transparent)]
+    Declare(#[from] DeclareError<T>),
+}
@@ -0,0 +1,496 @@
+use crate::game::config::{PaymentStructure, RuleConfig};
+use crate::game::def::{Concept, HandScore, Seat, WinningHand};
+use serde::Serialize;
+
+/// 点数移動 of a hand, applied to the points of the players when the hand is finished.
+#[derive(Clone, Default, Eq, PartialEq, Debug, Serialize)]
+pub struct Ledger {
+    pub entries: Vec<LedgerEntry>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize)]
+pub struct LedgerEntry {
+    /// `None` for 供託 taken from the table.
+    pub payer: Option<Seat>,
+    pub payee: Seat,
+    pub point: i32,
+    pub reason: PaymentReason,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentReason {
+    /// 和了
+    Completion,
+    /// 包, for a yakuman decided by a fed meld
+    Liability,
+    /// 積み棒
+    Counters,
+    /// 供託
+    Deposits,
+    /// 不聴罰符
+    NotReadyPenalty,
+    /// 流し満貫
+    NagashiMangan,
+}
+
+/// 立直棒
+pub(crate) const READY_DEPOSIT: i32 = 1000;
+/// 積み棒 per 本場, paid by the discarder.
+const COUNTER_POINT: i32 = 300;
+/// 積み棒 per 本場, paid by each payer of 自摸和了.
+const SELF_DRAWN_COUNTER_POINT: i32 = 100;
+const NOT_READY_PENALTY: i32 = 3000;
+/// 満貫 paid by a non-dealer to a non-dealer on 自摸和了
+const MANGAN_BASE_SHARE: i32 = 2000;
+/// 底分 of 国標麻将, paid by every other player.
+const CHINESE_OFFICIAL_BASE_POINT: i32 = 8;
+
+impl Ledger {
+    /// The gain of the seat, negative for a payer.
+    pub fn balance_of(&self, seat: Seat) -> i32 {
+        self.entries
+            .iter()
+            .map(|entry| {
+                if entry.payee == seat {
+                    entry.point
+                } else if entry.payer == Some(seat) {
+                    -entry.point
+                } else {
+                    0
+                }
+            })
+            .sum()
+    }
+
+    /// 和了. `counters` is the number of 本場 and `deposits` is 供託 in points.
+    ///
+    /// Under 包, the liable seat pays the liable part in full with 積み棒 on 自摸和了, and half
+    /// of it on 栄和 from another seat, who pays the rest.
+    pub(crate) fn completion<C: Concept>(
+        rule: &RuleConfig,
+        hand: &WinningHand<C>,
+        score: &HandScore,
+        counters: u8,
+        deposits: i32,
+    ) -> Ledger {
+        match rule.payment_structure {
+            PaymentStructure::Japanese => {}
+            PaymentStructure::ChineseOfficial => {
+                return Ledger::chinese_official_completion(rule, hand, score)
+            }
+            PaymentStructure::HongKong => return Ledger::hong_kong_completion(rule, hand, score),
+        }
+        let mut ledger = Ledger::default();
+        let winner = hand.seat;
+        let dealer = hand.dealer;
+        let times = if winner == dealer { 6 } else { 4 };
+        let (liable_seat, liable_points) = match hand.liable_seat {
+            Some(seat) if seat != winner && score.liable_basic_points > 0 => (
+                Some(seat),
+                score.liable_basic_points.min(score.basic_points),
+            ),
+            _ => (None, 0),
+        };
+        let basic_points = score.basic_points - liable_points;
+        match hand.discarder {
+            Some(discarder) => {
+                let liable_share = match liable_seat {
+                    Some(liable_seat) if liable_seat != discarder => {
+                        let share = round_up(liable_points.saturating_mul(times) / 2);
+                        ledger.pay(Some(liable_seat), winner, share, PaymentReason::Liability);
+                        share
+                    }
+                    _ => 0,
+                };
+                let point = round_up(score.basic_points.saturating_mul(times)) - liable_share;
+                ledger.pay(Some(discarder), winner, point, PaymentReason::Completion);
+                ledger.pay(
+                    Some(discarder),
+                    winner,
+                    COUNTER_POINT * counters as i32,
+                    PaymentReason::Counters,
+                );
+            }
+            None => {
+                for seat in rule.players_count.seats().filter(|s| *s != winner) {
+                    let point = rule.self_draw_payment.payment(
+                        rule.players_count,
+                        winner == dealer,
+                        seat == dealer,
+                        basic_points,
+                    );
+                    ledger.pay(
+                        Some(seat),
+                        winner,
+                        round_up(point),
+                        PaymentReason::Completion,
+                    );
+                    if liable_seat.is_none() {
+                        ledger.pay(
+                            Some(seat),
+                            winner,
+                            SELF_DRAWN_COUNTER_POINT * counters as i32,
+                            PaymentReason::Counters,
+                        );
+                    }
+                }
+                if let Some(liable_seat) = liable_seat {
+                    let point = round_up(liable_points.saturating_mul(times));
+                    ledger.pay(Some(liable_seat), winner, point, PaymentReason::Liability);
+                    ledger.pay(
+                        Some(liable_seat),
+                        winner,
+                        COUNTER_POINT * counters as i32,
+                        PaymentReason::Counters,
+                    );
+                }
+            }
+        }
+        ledger.pay(None, winner, deposits, PaymentReason::Deposits);
+        ledger
+    }
+
+    /// The fan in `score.basic_points` on top of the base point, by the discarder or by each
+    /// player on self-draw. Neither 包, 積み棒 nor 供託.
+    fn chinese_official_completion<C: Concept>(
+        rule: &RuleConfig,
+        hand: &WinningHand<C>,
+        score: &HandScore,
+    ) -> Ledger {
+        let mut ledger = Ledger::default();
+        if score.basic_points <= 0 {
+            return ledger;
+        }
+        let winner = hand.seat;
+        for seat in rule.players_count.seats().filter(|s| *s != winner) {
+            let point = match hand.discarder {
+                Some(discarder) if discarder != seat => CHINESE_OFFICIAL_BASE_POINT,
+                _ => CHINESE_OFFICIAL_BASE_POINT + score.basic_points,
+            };
+            ledger.pay(Some(seat), winner, point, PaymentReason::Completion);
+        }
+        ledger
+    }
+
+    /// The faan in `score.basic_points`, doubling the payout. Under 半銃 the others pay half of
+    /// the discarder, and under 全銃 the discarder pays it as well.
+    fn hong_kong_completion<C: Concept>(
+        rule: &RuleConfig,
+        hand: &WinningHand<C>,
+        score: &HandScore,
+    ) -> Ledger {
+        let mut ledger = Ledger::default();
+        if score.basic_points <= 0 {
+            return ledger;
+        }
+        let winner = hand.seat;
+        let payout = rule.hong_kong.payout(score.basic_points);
+        for seat in rule.players_count.seats().filter(|s| *s != winner) {
+            let (payer, point) = match hand.discarder {
+                None => (seat, payout),
+                Some(discarder) if discarder == seat => (seat, payout),
+                Some(discarder) if rule.hong_kong.discarder_pays_all => (discarder, payout / 2),
+                Some(_) => (seat, payout / 2),
+            };
+            ledger.pay(Some(payer), winner, point, PaymentReason::Completion);
+        }
+        ledger
+    }
+
+    /// 不聴罰符 is 3000 points in total, split among ready and not ready seats respectively.
+    /// 流し満貫 is paid as a 満貫 self-drawn completion instead.
+    pub(crate) fn exhaustive_draw(
+        rule: &RuleConfig,
+        ready_seats: &[Seat],
+        nagashi_mangan_seats: &[Seat],
+        dealer: Seat,
+    ) -> Ledger {
+        let mut ledger = Ledger::default();
+        if rule.payment_structure != PaymentStructure::Japanese {
+            return ledger;
+        }
+        let players_count = rule.players_count;
+        if nagashi_mangan_seats.is_empty() {
+            let ready_count = ready_seats.len() as i32;
+            let not_ready_count = players_count.get() as i32 - ready_count;
+            if ready_count == 0 || not_ready_count == 0 {
+                return ledger;
+            }
+            let point = NOT_READY_PENALTY / (ready_count * not_ready_count);
+            for payer in players_count.seats().filter(|s| !ready_seats.contains(s)) {
+                for payee in ready_seats {
+                    ledger.pay(Some(payer), *payee, point, PaymentReason::NotReadyPenalty);
+                }
+            }
+        } else {
+            for winner in nagashi_mangan_seats {
+                for seat in players_count.seats().filter(|s| s != winner) {
+                    let point = rule.self_draw_payment.payment(
+                        players_count,
+                        *winner == dealer,
+                        seat == dealer,
+                        MANGAN_BASE_SHARE,
+                    );
+                    ledger.pay(Some(seat), *winner, point, PaymentReason::NagashiMangan);
+                }
+            }
+        }
+        ledger
+    }
+
+    pub(crate) fn append(&mut self, other: Ledger) {
+        self.entries.extend(other.entries);
+    }
+
+    fn pay(&mut self, payer: Option<Seat>, payee: Seat, point: i32, reason: PaymentReason) {
+        if point > 0 {
+            self.entries.push(LedgerEntry {
+                payer,
+                payee,
+                point,
+                reason,
+            });
+        }
+    }
+}
+
+/// Rounded up to 100.
+pub(crate) fn round_up(points: i32) -> i32 {
+    points.saturating_add(99) / 100 * 100
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::config::SelfDrawPayment;
+    use crate::game::def::PlayersCount;
+    use crate::rule::{winning_hand_of, StandardConcept};
+
+    /// Tiles don't matter to the ledger.
+    fn winning_hand(seat: Seat, discarder: Option<Seat>) -> WinningHand<StandardConcept> {
+        WinningHand {
+            seat,
+            ..winning_hand_of("123m456p789s1122z", "1z", discarder)
+        }
+    }
+
+    fn score(basic_points: i32) -> HandScore {
+        HandScore {
+            basic_points,
+            liable_basic_points: 0,
+        }
+    }
+
+    fn three_players(self_draw_payment: SelfDrawPayment) -> RuleConfig {
+        RuleConfig {
+            players_count: PlayersCount::THREE,
+            self_draw_payment,
+            ..RuleConfig::default()
+        }
+    }
+
+    fn balances(ledger: &Ledger, players_count: PlayersCount) -> Vec<i32> {
+        players_count
+            .seats()
+            .map(|s| ledger.balance_of(s))
+            .collect()
+    }
+
+    #[test]
+    fn ron_is_paid_by_the_discarder_with_counters_and_deposits() {
+        let rule = RuleConfig::default();
+        let hand = winning_hand(Seat::South, Some(Seat::North));
+        let ledger = Ledger::completion(&rule, &hand, &score(2000), 2, 1000);
+        assert_eq!(
+            balances(&ledger, rule.players_count),
+            vec![0, 8000 + 600 + 1000, 0, -8600]
+        );
+    }
+
+    #[test]
+    fn self_drawn_completion_is_paid_by_each() {
+        let rule = RuleConfig::default();
+        // 30符1飜
+        let hand = winning_hand(Seat::South, None);
+        let ledger = Ledger::completion(&rule, &hand, &score(240), 1, 0);
+        assert_eq!(
+            balances(&ledger, rule.players_count),
+            vec![-600, 1400, -400, -400]
+        );
+
+        let hand = winning_hand(Seat::East, None);
+        let ledger = Ledger::completion(&rule, &hand, &score(240), 0, 0);
+        assert_eq!(
+            balances(&ledger, rule.players_count),
+            vec![1500, -500, -500, -500]
+        );
+    }
+
+    #[test]
+    fn absent_share_is_split_under_bisection() {
+        let loss = three_players(SelfDrawPayment::Loss);
+        let bisection = three_players(SelfDrawPayment::Bisection);
+        let players_count = PlayersCount::THREE;
+
+        let hand = winning_hand(Seat::South, None);
+        let ledger = Ledger::completion(&loss, &hand, &score(2000), 0, 0);
+        assert_eq!(balances(&ledger, players_count), vec![-4000, 6000, -2000]);
+        let ledger = Ledger::completion(&bisection, &hand, &score(2000), 0, 0);
+        assert_eq!(balances(&ledger, players_count), vec![-5000, 8000, -3000]);
+        // 30符1飜, rounded up after splitting
+        let ledger = Ledger::completion(&bisection, &hand, &score(240), 0, 0);
+        assert_eq!(balances(&ledger, players_count), vec![-600, 1000, -400]);
+
+        let hand = winning_hand(Seat::East, None);
+        let ledger = Ledger::completion(&loss, &hand, &score(2000), 0, 0);
+        assert_eq!(balances(&ledger, players_count), vec![8000, -4000, -4000]);
+        let ledger = Ledger::completion(&bisection, &hand, &score(2000), 0, 0);
+        assert_eq!(balances(&ledger, players_count), vec![12000, -6000, -6000]);
+    }
+
+    #[test]
+    fn ron_is_not_affected_by_bisection() {
+        let rule = three_players(SelfDrawPayment::Bisection);
+        let hand = winning_hand(Seat::South, Some(Seat::West));
+        let ledger = Ledger::completion(&rule, &hand, &score(2000), 0, 0);
+        assert_eq!(balances(&ledger, rule.players_count), vec![0, 8000, -8000]);
+    }
+
+    #[test]
+    fn not_ready_penalty_is_split_by_ready_seats() {
+        use Seat::*;
+
+        let rule = RuleConfig::default();
+        let penalty_of = |ready_seats: &[Seat]| {
+            let ledger = Ledger::exhaustive_draw(&rule, ready_seats, &[], East);
+            balances(&ledger, rule.players_count)
+        };
+        assert_eq!(penalty_of(&[]), vec![0, 0, 0, 0]);
+        assert_eq!(penalty_of(&[South]), vec![-1000, 3000, -1000, -1000]);
+        assert_eq!(penalty_of(&[South, North]), vec![-1500, 1500, -1500, 1500]);
+        assert_eq!(
+            penalty_of(&[East, South, West]),
+            vec![1000, 1000, 1000, -3000]
+        );
+        assert_eq!(penalty_of(&[East, South, West, North]), vec![0, 0, 0, 0]);
+
+        let rule = three_players(SelfDrawPayment::Loss);
+        let ledger = Ledger::exhaustive_draw(&rule, &[South], &[], East);
+        assert_eq!(
+            balances(&ledger, rule.players_count),
+            vec![-1500, 3000, -1500]
+        );
+    }
+
+    #[test]
+    fn nagashi_mangan_is_paid_as_self_drawn_mangan() {
+        let rule = RuleConfig::default();
+        let ledger = Ledger::exhaustive_draw(&rule, &[Seat::South], &[Seat::West], Seat::East);
+        assert_eq!(
+            balances(&ledger, rule.players_count),
+            vec![-4000, -2000, 8000, -2000]
+        );
+        assert!(ledger
+            .entries
+            .iter()
+            .all(|e| e.reason == PaymentReason::NagashiMangan));
+    }
+
+    fn liable_hand(discarder: Option<Seat>) -> WinningHand<StandardConcept> {
+        WinningHand {
+            liable_seat: Some(Seat::West),
+            ..winning_hand(Seat::South, discarder)
+        }
+    }
+
+    #[test]
+    fn liable_seat_pays_half_on_ron_from_another_seat() {
+        let rule = RuleConfig::default();
+        let yakuman = HandScore {
+            liable_basic_points: 8000,
+            ..score(8000)
+        };
+        let hand = liable_hand(Some(Seat::North));
+        let ledger = Ledger::completion(&rule, &hand, &yakuman, 1, 0);
+        assert_eq!(
+            balances(&ledger, rule.players_count),
+            vec![0, 32300, -16000, -16300]
+        );
+
+        let hand = liable_hand(Some(Seat::West));
+        let ledger = Ledger::completion(&rule, &hand, &yakuman, 1, 0);
+        assert_eq!(
+            balances(&ledger, rule.players_count),
+            vec![0, 32300, -32300, 0]
+        );
+    }
+
+    #[test]
+    fn liable_seat_pays_all_on_self_drawn_completion() {
+        let rule = RuleConfig::default();
+        let yakuman = HandScore {
+            liable_basic_points: 8000,
+            ..score(8000)
+        };
+        let hand = liable_hand(None);
+        let ledger = Ledger::completion(&rule, &hand, &yakuman, 1, 0);
+        assert_eq!(
+            balances(&ledger, rule.players_count),
+            vec![0, 32300, -32300, 0]
+        );
+
+        // Another yakuman on top of the liable one is paid as usual.
+        let double_yakuman = HandScore {
+            basic_points: 16000,
+            ..yakuman
+        };
+        let ledger = Ledger::completion(&rule, &hand, &double_yakuman, 1, 0);
+        assert_eq!(
+            balances(&ledger, rule.players_count),
+            vec![-16000, 64300, -40300, -8000]
+        );
+    }
+
+    #[test]
+    fn chinese_official_pays_the_base_point_to_each() {
+        let rule = RuleConfig {
+            payment_structure: PaymentStructure::ChineseOfficial,
+            ..RuleConfig::default()
+        };
+        let hand = winning_hand(Seat::South, Some(Seat::West));
+        let ledger = Ledger::completion(&rule, &hand, &score(8), 1, 1000);
+        assert_eq!(balances(&ledger, rule.players_count), vec![-8, 32, -16, -8]);
+
+        let hand = winning_hand(Seat::South, None);
+        let ledger = Ledger::completion(&rule, &hand, &score(8), 0, 0);
+        assert_eq!(
+            balances(&ledger, rule.players_count),
+            vec![-16, 48, -16, -16]
+        );
+
+        // Below the minimum
+        let ledger = Ledger::completion(&rule, &hand, &score(0), 0, 0);
+        assert!(ledger.entries.is_empty());
+    }
+
+    #[test]
+    fn hong_kong_payout_follows_the_discarder_rule() {
+        let mut rule = RuleConfig {
+            payment_structure: PaymentStructure::HongKong,
+            ..RuleConfig::default()
+        };
+        let hand = winning_hand(Seat::South, Some(Seat::West));
+        // 半銃
+        let ledger = Ledger::completion(&rule, &hand, &score(3), 1, 1000);
+        assert_eq!(balances(&ledger, rule.players_count), vec![-4, 16, -8, -4]);
+
+        rule.hong_kong.discarder_pays_all = true;
+        let ledger = Ledger::completion(&rule, &hand, &score(3), 1, 1000);
+        assert_eq!(balances(&ledger, rule.players_count), vec![0, 16, -16, 0]);
+
+        let hand = winning_hand(Seat::South, None);
+        let ledger = Ledger::completion(&rule, &hand, &score(3), 0, 0);
+        assert_eq!(balances(&ledger, rule.players_count), vec![-8, 24, -8, -8]);
+    }
+}
@@ -1,95 +1,182 @@
-use crate::game::def::{Action, Concept, DealtResult, SeatOld, TileDealingSpec, PLAYERS_COUNT};
-use crate::game::player::PlayerOld;
-use arrayvec::ArrayVec;
+use crate::game::config::{MultipleRon, RuleConfig};
+use crate::game::def::{
+    Concept, DealtResult, FuritenStatus, HandJudgementSpec, MeldConcept, ParticipantId,
+    PlayersCount, Seat, SeatingSpec, TileDealingSpec, WinningHand,
+};
+use crate::game::ledger::{Ledger, READY_DEPOSIT};
+use crate::game::view::{SeatView, Standing, TableView};
 use itertools::Itertools;
-use std::cell::Cell;
-use std::cell::RefCell;
 use std::collections::HashMap;
+use std::fmt;
 use std::marker::PhantomData;
-use std::rc::Rc;
-use thiserror::Error;
+use std::sync::Arc;
 
-struct WaitingTable<C: Concept> {
+pub(crate) mod error;
+
+use error::{ClaimError, DeclareError, DiscardError, DrawError, JoinError, SeatError};
+
+/// 九種九牌
+const NINE_TERMINALS_KINDS_COUNT: usize = 9;
+
+pub(crate) struct WaitingTable<C: Concept> {
     concept: PhantomData<C>,
     id: TableId,
+    rule: RuleConfig,
     participants: Participants,
 }
 
 impl<C: Concept> WaitingTable<C> {
-    fn setup() -> Self {
+    pub(crate) fn setup(rule: RuleConfig) -> Self {
         Self {
             id: TableId::generate(),
+            rule,
             participants: Participants::nobody(),
             concept: PhantomData,
         }
     }
 
-    fn accept_participant(self, new_participant: ParticipantId) -> Result<Self, TableError> {
+    pub(crate) fn id(&self) -> &TableId {
+        &self.id
+    }
+
+    pub(crate) fn validate_participant(
+        &self,
+        new_participant: &ParticipantId,
+    ) -> Result<(), JoinError> {
+        self.participants
+            .validate(new_participant, self.rule.players_count)
+    }
+
+    pub(crate) fn accept_participant(
+        self,
+        new_participant: ParticipantId,
+    ) -> Result<Self, JoinError> {
         Ok(Self {
-            participants: self.participants.receive(new_participant)?,
+            participants: self
+                .participants
+                .receive(new_participant, self.rule.players_count)?,
             ..self
         })
     }
 
-    fn be_ready(self) -> Option<ReadyTable<C>> {
-        self.participants
-            .gathered()
-            .then(|| ReadyTable::<C>::setup(self.id, self.participants))
+    pub(crate) fn validate_leaving(&self, a_participant: &ParticipantId) -> Result<(), JoinError> {
+        self.participants.validate_sending_off(a_participant)
+    }
+
+    pub(crate) fn send_off_participant(
+        self,
+        a_participant: ParticipantId,
+    ) -> Result<Self, JoinError> {
+        Ok(Self {
+            participants: self.participants.send_off(a_participant)?,
+            ..self
+        })
+    }
+
+    pub(crate) fn be_ready(self) -> Result<ReadyTable<C>, Self> {
+        if self.participants.gathered(self.rule.players_count) {
+            Ok(ReadyTable::<C>::setup(
+                self.id,
+                self.rule,
+                self.participants,
+            ))
+        } else {
+            Err(self)
+        }
     }
 }
 
-struct ReadyTable<C: Concept> {
+pub(crate) struct ReadyTable<C: Concept> {
     concept: PhantomData<C>,
     id: TableId,
+    rule: RuleConfig,
     participants: Participants,
     seating_list: Option<SeatingList>,
 }
 
 impl<C: Concept> ReadyTable<C> {
-    fn setup(id: TableId, participants: Participants) -> Self {
+    fn setup(id: TableId, rule: RuleConfig, participants: Participants) -> Self {
         Self {
             id,
+            rule,
             participants,
             seating_list: None,
             concept: PhantomData,
         }
     }
 
-    /// TODO accepts seating_spec
-    fn arrange_initial_seating(self) -> Self {
-        unimplemented!()
+    pub(crate) fn id(&self) -> &TableId {
+        &self.id
     }
 
-    fn start_game(self) -> Option<HandPreparingTable<C>> {
-        if let Some(seating_list) = self.seating_list {
-            Some(HandPreparingTable::<C>::new(TableInfo::new(
-                self.id,
-                self.participants,
-                seating_list,
-            )))
-        } else {
-            None
+    pub(crate) fn arrange_initial_seating(self, seating_spec: &dyn SeatingSpec) -> Self {
+        let arranged = seating_spec.arrange(&self.participants.0);
+        let players_count = self.rule.players_count.get();
+        if arranged.len() != players_count
+            || arranged.iter().any(|p| !self.participants.0.contains(p))
+            || arranged.iter().unique().count() != players_count
+        {
+            panic!("Wrong result of `seating_spec`: should be a permutation of the participants")
+        }
+
+        Self {
+            seating_list: Some(SeatingList(
+                arranged
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, p)| (p, Seat::from(i)))
+                    .collect(),
+            )),
+            ..self
         }
     }
+
+    pub(crate) fn validate_start(&self) -> Result<(), SeatError> {
+        self.seating_list
+            .as_ref()
+            .map(|_| ())
+            .ok_or(SeatError::NotArranged)
+    }
+
+    pub(crate) fn start_game(
+        self,
+        hand_judgement_spec: Arc<dyn HandJudgementSpec<C>>,
+    ) -> Result<HandPreparingTable<C>, SeatError> {
+        self.validate_start()?;
+
+        Ok(HandPreparingTable::<C>::new(TableInfo::new(
+            self.id,
+            self.rule,
+            self.participants,
+            self.seating_list.unwrap(),
+            hand_judgement_spec,
+        )))
+    }
 }
 
 // TODO name
 struct TableInfo<C: Concept> {
-    concept: PhantomData<C>,
     id: TableId,
+    rule: RuleConfig,
     players: Players,
     seating_list: SeatingList,
+    hand_judgement_spec: Arc<dyn HandJudgementSpec<C>>,
 }
 
-static INITIAL_POINT: u16 = 25000; // TODO const generics
-
 impl<C: Concept> TableInfo<C> {
-    fn new(id: TableId, participants: Participants, seating_list: SeatingList) -> Self {
+    fn new(
+        id: TableId,
+        rule: RuleConfig,
+        participants: Participants,
+        seating_list: SeatingList,
+        hand_judgement_spec: Arc<dyn HandJudgementSpec<C>>,
+    ) -> Self {
         Self {
-            concept: PhantomData,
             id,
-            players: Players::form(participants, INITIAL_POINT),
+            players: Players::form(participants, rule.initial_point),
+            rule,
             seating_list,
+            hand_judgement_spec,
         }
     }
 }
@@ -97,7 +184,7 @@ impl<C: Concept> TableInfo<C> {
 // Round = 場
 // Hand  = 局
 
-struct HandPreparingTable<C: Concept> {
+pub(crate) struct HandPreparingTable<C: Concept> {
     table_info: TableInfo<C>,
     progress: Progress, // TODO WIP
 }
@@ -110,16 +197,103 @@ impl<C: Concept> HandPreparingTable<C> {
         }
     }
 
-    // TODO accepts dealing_spec
-    fn deal(self) -> HandPlayingTable<C> {
-        unimplemented!()
+    fn next(table_info: TableInfo<C>, progress: Progress) -> Self {
+        Self {
+            table_info,
+            progress,
+        }
     }
+
+    /// 終局. The game is over after the last round, or when someone goes bust if the rule says so.
+    pub(crate) fn is_game_over(&self) -> bool {
+        let rule = &self.table_info.rule;
+        let is_last_round_passed =
+            self.progress.passed_rounds_count == rule.game_length.rounds_count();
+        let is_anyone_bust =
+            rule.ends_on_bust && self.table_info.players.0.iter().any(|p| p.point < 0);
+        is_last_round_passed || is_anyone_bust
+    }
+
+    pub(crate) fn end_game(self) -> GameEndedTable<C> {
+        if !self.is_game_over() {
+            panic!("Should end after the game is over")
+        }
+
+        GameEndedTable {
+            table_info: self.table_info,
+        }
+    }
+
+    pub(crate) fn deal(self, tile_dealing_spec: &dyn TileDealingSpec<C>) -> HandPlayingTable<C> {
+        let DealtResult {
+            wall_tiles,
+            supplemental_tiles,
+            reward_indication_tiles,
+            player_tiles,
+        } = tile_dealing_spec.deal(&self.table_info.rule);
+
+        let seats = player_tiles.iter().map(|(_, s)| *s).sorted().collect_vec();
+        if seats != self.table_info.rule.players_count.seats().collect_vec() {
+            panic!("Wrong result of `tile_dealing_spec`: should deal to each seat once")
+        }
+
+        HandPlayingTable::something_new(
+            self.table_info,
+            self.progress,
+            WallTiles(wall_tiles),
+            SupplementalTiles(supplemental_tiles),
+            RewardIndicationTiles(reward_indication_tiles),
+            Hands(
+                player_tiles
+                    .iter()
+                    .map(|(tiles, seat)| (*seat, Hand::new(tiles.clone())))
+                    .collect(),
+            ),
+        )
+    }
+}
+
+pub(crate) struct GameEndedTable<C: Concept> {
+    table_info: TableInfo<C>,
 }
 
-struct HandPlayingTable<C: Concept> {
+impl<C: Concept> GameEndedTable<C> {
+    pub(crate) fn id(&self) -> &TableId {
+        &self.table_info.id
+    }
+
+    /// From the top. Ties go to the seat nearer to the initial dealer (上家取り).
+    pub(crate) fn standings(&self) -> Vec<Standing> {
+        let rule = &self.table_info.rule;
+        let seating_list = &self.table_info.seating_list;
+        self.table_info
+            .players
+            .0
+            .iter()
+            .map(|p| {
+                let seat = seating_list
+                    .get_seat_of(&p.id)
+                    .expect("Every player should be seated");
+                (p, seat)
+            })
+            .sorted_by_key(|(p, seat)| (-p.point, *seat))
+            .enumerate()
+            .map(|(rank, (p, seat))| Standing {
+                participant: p.id.clone(),
+                seat,
+                point: p.point,
+                score: p.point - rule.target_point
+                    + rule.uma[rank]
+                    + if rank == 0 { rule.oka() } else { 0 },
+            })
+            .collect()
+    }
+}
+
+pub(crate) struct HandPlayingTable<C: Concept> {
     table_info: TableInfo<C>,
     progress: Progress, // TODO WIP
-    turn: Turn,
+    phase: Phase<C>,
     wall_tiles: WallTiles<C>,
     supplemental_tiles: SupplementalTiles<C>,
     reward_indication_tiles: RewardIndicationTiles<C>,
@@ -139,8 +313,8 @@ impl<C: Concept> HandPlayingTable<C> {
     ) -> Self {
         Self {
             table_info,
+            phase: Phase::AwaitingDraw(progress.dealer()),
             progress,
-            turn: Turn::get_initial(),
             wall_tiles,
             supplemental_tiles,
             reward_indication_tiles,
@@ -149,120 +323,1341 @@ impl<C: Concept> HandPlayingTable<C> {
         }
     }
 
-    // TODO care Error
-    fn draw_tile_by(self, participant_id: ParticipantId) -> Result<Self, TableError> {
-        // TODO この前提を独立したオブジェクトで表現する
-        if !self.is_turn_of(participant_id.clone())? {
-            Err(TableError::NotParticipantsTurnError)?;
+    pub(crate) fn id(&self) -> &TableId {
+        &self.table_info.id
+    }
+
+    pub(crate) fn phase(&self) -> &Phase<C> {
+        &self.phase
+    }
+
+    pub(crate) fn get_seat_of(&self, participant_id: &ParticipantId) -> Option<Seat> {
+        self.table_info.seating_list.get_seat_of(participant_id)
+    }
+
+    pub(crate) fn participant_at(&self, seat: Seat) -> &ParticipantId {
+        self.table_info
+            .seating_list
+            .get_participant_at(seat)
+            .expect("Every seat should be taken")
+    }
+
+    /// The index of the physically identical tile in the concealed tiles.
+    pub(crate) fn index_of(&self, seat: Seat, tile: C::Tile) -> Option<TileIndex> {
+        self.hands
+            .get(seat)
+            .concealed_tiles
+            .iter()
+            .position(|t| *t == tile)
+            .map(|i| TileIndex(i as u8))
+    }
+
+    /// The last tile in the hand, which is the drawn one while awaiting a discard.
+    pub(crate) fn last_tile_of(&self, seat: Seat) -> Option<C::Tile> {
+        self.hands.get(seat).concealed_tiles.last().copied()
+    }
+
+    pub(crate) fn discarded_tile(&self) -> Option<C::Tile> {
+        match self.phase {
+            Phase::AwaitingClaims(ref window) => Some(window.discarded_tile),
+            _ => None,
         }
+    }
+
+    /// Tiles in the hand which can be discarded now, i.e. except swap-calling ones.
+    pub(crate) fn discardable_tiles_of(&self, seat: Seat) -> Vec<C::Tile> {
+        self.hands
+            .get(seat)
+            .concealed_tiles
+            .iter()
+            .filter(|t| !self.is_swap_calling(seat, **t))
+            .copied()
+            .collect()
+    }
 
+    /// Never in フリテン without the rule.
+    pub(crate) fn furiten_of(&self, seat: Seat) -> FuritenStatus {
+        if !self.table_info.rule.has_furiten {
+            return FuritenStatus::default();
+        }
+        let hand = self.hands.get(seat);
+        let spec = &self.table_info.hand_judgement_spec;
+        FuritenStatus {
+            is_permanent: self.discards.get(seat).iter().any(|(tile, _)| {
+                spec.is_waiting_on(&hand.concealed_tiles, &hand.exposed_melds, *tile)
+            }),
+            is_temporary: hand.missed_completion,
+            is_after_ready: hand.missed_completion_after_ready,
+        }
+    }
+
+    /// Before the own first discard without calls before.
+    fn is_first_turn_of(&self, seat: Seat) -> bool {
+        self.discards.get(seat).is_empty()
+            && self
+                .table_info
+                .rule
+                .players_count
+                .seats()
+                .all(|seat| self.hands.get(seat).exposed_melds.is_empty())
+    }
+
+    fn is_ready(&self, seat: Seat) -> bool {
+        let hand = self.hands.get(seat);
+        self.table_info
+            .hand_judgement_spec
+            .is_ready(&hand.concealed_tiles, &hand.exposed_melds)
+    }
+
+    /// Whether the completed hand of the seat is worth something, e.g. with yaku.
+    fn is_scorable(&self, seat: Seat, discarder: Option<Seat>) -> bool {
+        let hand = self.winning_hand_of(seat, discarder);
+        self.table_info
+            .hand_judgement_spec
+            .is_scorable(&hand, &self.table_info.rule)
+    }
+
+    fn is_swap_calling(&self, seat: Seat, tile: C::Tile) -> bool {
+        match self.phase {
+            Phase::AwaitingDiscardAfterCall(turn) if turn == seat => {
+                !self.table_info.rule.allows_swap_calling
+                    && self
+                        .hands
+                        .get(seat)
+                        .exposed_melds
+                        .last()
+                        .is_some_and(|meld| meld.is_swap_calling(tile))
+            }
+            _ => false,
+        }
+    }
+
+    pub(crate) fn validate_draw_by(
+        &self,
+        participant_id: &ParticipantId,
+    ) -> Result<Seat, DrawError> {
         let seat = self
-            .table_info
-            .seating_list
-            .get_seat_of(participant_id.clone())
-            .unwrap();
+            .get_seat_of(participant_id)
+            .ok_or_else(|| DrawError::UnknownParticipant(participant_id.clone()))?;
+        match self.phase {
+            Phase::AwaitingDraw(turn) if turn == seat => {}
+            Phase::AwaitingDraw(turn) => {
+                return Err(DrawError::NotParticipantsTurn {
+                    participant: participant_id.clone(),
+                    seat,
+                    turn,
+                })
+            }
+            _ => {
+                return Err(DrawError::UnexpectedPhase {
+                    participant: participant_id.clone(),
+                    seat,
+                    phase: self.phase.kind(),
+                })
+            }
+        }
+        if self.wall_tiles.is_exhausted() {
+            return Err(DrawError::WallExhausted);
+        }
 
-        let (wall_tiles, drawn_tile) = self
-            .wall_tiles
-            .pick()
-            .ok_or(TableError::WallExhaustedError)?;
+        Ok(seat)
+    }
+
+    pub(crate) fn draw_tile_by(self, participant_id: ParticipantId) -> Result<Self, DrawError> {
+        let seat = self.validate_draw_by(&participant_id)?;
+
+        let (wall_tiles, drawn_tile) = self.wall_tiles.pick().ok_or(DrawError::WallExhausted)?;
         let hands = self
             .hands
             .append(drawn_tile)
             .to_hand_of(seat)
-            .ok_or(TableError::UnknownError)?;
+            .expect("Every seat should have a hand");
         Ok(Self {
+            phase: Phase::AwaitingDiscard(seat),
             wall_tiles,
             hands,
             ..self
         })
     }
 
-    fn discard_tile_by(
+    pub(crate) fn validate_discard_by(
+        &self,
+        tile_index: TileIndex,
+        participant_id: &ParticipantId,
+    ) -> Result<Seat, DiscardError<C::Tile>> {
+        let seat = self
+            .get_seat_of(participant_id)
+            .ok_or_else(|| DiscardError::UnknownParticipant(participant_id.clone()))?;
+        match self.phase {
+            Phase::AwaitingDiscard(turn) | Phase::AwaitingDiscardAfterCall(turn)
+                if turn == seat => {}
+            Phase::AwaitingDiscard(turn) | Phase::AwaitingDiscardAfterCall(turn) => {
+                return Err(DiscardError::NotParticipantsTurn {
+                    participant: participant_id.clone(),
+                    seat,
+                    turn,
+                })
+            }
+            _ => {
+                return Err(DiscardError::UnexpectedPhase {
+                    participant: participant_id.clone(),
+                    seat,
+                    phase: self.phase.kind(),
+                })
+            }
+        }
+        let hand = self.hands.get(seat);
+        let tile = match hand.concealed_tiles.get(tile_index.0 as usize) {
+            Some(tile) => *tile,
+            None => {
+                return Err(DiscardError::InvalidTileIndex {
+                    participant: participant_id.clone(),
+                    seat,
+                    index: tile_index.0,
+                    hand_size: hand.concealed_tiles.len(),
+                })
+            }
+        };
+        if self.is_swap_calling(seat, tile) {
+            return Err(DiscardError::SwapCalling {
+                participant: participant_id.clone(),
+                seat,
+                tile,
+            });
+        }
+
+        Ok(seat)
+    }
+
+    pub(crate) fn discard_tile_by(
         self,
         tile_index: TileIndex,
         participant_id: ParticipantId,
-    ) -> Result<Self, TableError> {
-        // TODO この前提を独立したオブジェクトで表現する
-        if !self.is_turn_of(participant_id.clone())? {
-            Err(TableError::NotParticipantsTurnError)?;
-        }
+    ) -> Result<Self, DiscardError<C::Tile>> {
+        let seat = self.validate_discard_by(tile_index, &participant_id)?;
+        Ok(self.discard_tile_at(tile_index, seat, false))
+    }
 
-        let seat = self
+    /// 立直
+    pub(crate) fn validate_declare_ready_by(
+        &self,
+        tile_index: TileIndex,
+        participant_id: &ParticipantId,
+    ) -> Result<Seat, DeclareError<C::Tile>> {
+        let seat = self.validate_declaration_by(participant_id)?;
+        let hand = self.hands.get(seat);
+        let mut rest = hand.concealed_tiles.clone();
+        if tile_index.0 as usize >= rest.len() {
+            return Err(DeclareError::InvalidTileIndex {
+                participant: participant_id.clone(),
+                seat,
+                index: tile_index.0,
+                hand_size: rest.len(),
+            });
+        }
+        let tile = rest.remove(tile_index.0 as usize);
+
+        if !self.table_info.rule.allows_riichi {
+            Err(DeclareError::ReadyNotAllowed {
+                participant: participant_id.clone(),
+                seat,
+            })
+        } else if hand.is_ready_declared {
+            Err(DeclareError::AlreadyDeclaredReady {
+                participant: participant_id.clone(),
+                seat,
+            })
+        } else if hand.exposed_melds.iter().any(|m| m.called_tile().is_some()) {
+            Err(DeclareError::OpenHand {
+                participant: participant_id.clone(),
+                seat,
+            })
+        } else if self.wall_tiles.0.len() < self.table_info.rule.players_count.get() {
+            Err(DeclareError::WallNearlyExhausted {
+                participant: participant_id.clone(),
+                seat,
+            })
+        } else if self.table_info.players.point_of(participant_id) < READY_DEPOSIT {
+            Err(DeclareError::InsufficientPoints {
+                participant: participant_id.clone(),
+                seat,
+                point: self.table_info.players.point_of(participant_id),
+            })
+        } else if !self
             .table_info
-            .seating_list
-            .get_seat_of(participant_id.clone())
-            .unwrap();
+            .hand_judgement_spec
+            .is_ready(&rest, &hand.exposed_melds)
+        {
+            Err(DeclareError::NotReadyAfterDiscard {
+                participant: participant_id.clone(),
+                seat,
+                tile,
+            })
+        } else {
+            Ok(seat)
+        }
+    }
+
+    pub(crate) fn declare_ready_by(
+        self,
+        tile_index: TileIndex,
+        participant_id: ParticipantId,
+    ) -> Result<Self, DeclareError<C::Tile>> {
+        let seat = self.validate_declare_ready_by(tile_index, &participant_id)?;
+        Ok(self.discard_tile_at(tile_index, seat, true))
+    }
+
+    /// The own discard ends 同巡内フリテン. Not declaring 自摸和了 is not missing a completion,
+    /// which only happens by passing on a discarded tile.
+    fn discard_tile_at(self, tile_index: TileIndex, seat: Seat, declares_ready: bool) -> Self {
+        let is_first_turn = self.is_first_turn_of(seat);
 
         let (hands, discarded_tile) = self
             .hands
             .discard(tile_index)
             .from_hand_of(seat)
-            .ok_or(TableError::UnknownError)?; // TODO use proper Error
+            .expect("Tile index should be validated");
+        let hands = hands.update(seat, |hand| {
+            hand.missed_completion = false;
+            if declares_ready {
+                hand.is_ready_declared = true;
+                hand.is_ready_declared_first = is_first_turn;
+            }
+            hand.is_right_after_ready = declares_ready;
+            hand.has_drawn_supplemental_tile = false;
+        });
         let discards = self
             .discards
             .append(discarded_tile)
             .to_hand_of(seat)
-            .ok_or(TableError::UnknownError)?;
-        Ok(Self {
+            .expect("Every seat should have discards");
+        Self {
+            phase: Phase::AwaitingClaims(ClaimWindow::open(
+                seat,
+                discarded_tile,
+                self.table_info.rule.players_count,
+            )),
             hands,
             discards,
             ..self
+        }
+    }
+
+    /// 抜きドラ. The tile is set aside and a supplemental tile is drawn instead.
+    pub(crate) fn validate_extract_bonus_tile_by(
+        &self,
+        tile_index: TileIndex,
+        participant_id: &ParticipantId,
+    ) -> Result<Seat, DeclareError<C::Tile>> {
+        let seat = self.validate_declaration_by(participant_id)?;
+        let hand = self.hands.get(seat);
+        let tile = match hand.concealed_tiles.get(tile_index.0 as usize) {
+            Some(tile) => *tile,
+            None => {
+                return Err(DeclareError::InvalidTileIndex {
+                    participant: participant_id.clone(),
+                    seat,
+                    index: tile_index.0,
+                    hand_size: hand.concealed_tiles.len(),
+                })
+            }
+        };
+
+        if !self.table_info.rule.extracts_bonus_tiles
+            || !self.table_info.hand_judgement_spec.is_bonus_tile(tile)
+        {
+            Err(DeclareError::NotBonusTile {
+                participant: participant_id.clone(),
+                seat,
+                tile,
+            })
+        } else if self.supplemental_tiles.0.is_empty() {
+            Err(DeclareError::SupplementalTilesExhausted {
+                participant: participant_id.clone(),
+                seat,
+            })
+        } else {
+            Ok(seat)
+        }
+    }
+
+    pub(crate) fn extract_bonus_tile_by(
+        self,
+        tile_index: TileIndex,
+        participant_id: ParticipantId,
+    ) -> Result<Self, DeclareError<C::Tile>> {
+        let seat = self.validate_extract_bonus_tile_by(tile_index, &participant_id)?;
+
+        let (hands, bonus_tile) = self
+            .hands
+            .discard(tile_index)
+            .from_hand_of(seat)
+            .expect("Tile index should be validated");
+        let (supplemental_tiles, drawn_tile) = self
+            .supplemental_tiles
+            .pick()
+            .expect("Supplemental tiles should be validated");
+        let hands = hands.update(seat, |hand| {
+            hand.bonus_tiles.push(bonus_tile);
+            hand.append_tile(drawn_tile);
+            hand.has_drawn_supplemental_tile = true;
+        });
+        Ok(Self {
+            hands,
+            supplemental_tiles,
+            ..self
         })
     }
 
-    fn is_turn_of(&self, participant_id: ParticipantId) -> Result<bool, TableError> {
-        let seat = self
-            .table_info
-            .seating_list
-            .get_seat_of(participant_id)
-            .ok_or(TableError::UnknownParticipantError)?;
+    /// 自摸和了
+    pub(crate) fn validate_declare_completion_by(
+        &self,
+        participant_id: &ParticipantId,
+    ) -> Result<Seat, DeclareError<C::Tile>> {
+        let seat = self.validate_declaration_by(participant_id)?;
+        let hand = self.hands.get(seat);
+        if !self
+            .table_info
+            .hand_judgement_spec
+            .is_completed(&hand.concealed_tiles, &hand.exposed_melds)
+        {
+            return Err(DeclareError::NotCompleted {
+                participant: participant_id.clone(),
+                seat,
+            });
+        }
+        if !self.is_scorable(seat, None) {
+            return Err(DeclareError::NoYaku {
+                participant: participant_id.clone(),
+                seat,
+            });
+        }
+
+        Ok(seat)
+    }
+
+    pub(crate) fn declare_completion_by(
+        self,
+        participant_id: ParticipantId,
+    ) -> Result<Self, DeclareError<C::Tile>> {
+        let seat = self.validate_declare_completion_by(&participant_id)?;
+
+        Ok(Self {
+            phase: Phase::Ended(HandEnding::Completion {
+                winners: vec![seat],
+                discarder: None,
+            }),
+            ..self
+        })
+    }
+
+    /// 九種九牌. Kinds are told by `Eq`, as no terminal nor honor differs physically.
+    pub(crate) fn validate_declare_nine_terminals_by(
+        &self,
+        participant_id: &ParticipantId,
+    ) -> Result<Seat, DeclareError<C::Tile>> {
+        let seat = self.validate_declaration_by(participant_id)?;
+        if !self.table_info.rule.abortive_draws.nine_terminals {
+            return Err(DeclareError::NineTerminalsNotAllowed {
+                participant: participant_id.clone(),
+                seat,
+            });
+        }
+        let spec = &self.table_info.hand_judgement_spec;
+        let mut kinds = vec![];
+        for tile in self.hands.get(seat).concealed_tiles.iter() {
+            if spec.is_terminal_or_honor(*tile) && !kinds.contains(tile) {
+                kinds.push(*tile);
+            }
+        }
+        if !self.is_first_turn_of(seat) || kinds.len() < NINE_TERMINALS_KINDS_COUNT {
+            return Err(DeclareError::NotNineTerminals {
+                participant: participant_id.clone(),
+                seat,
+            });
+        }
+
+        Ok(seat)
+    }
+
+    pub(crate) fn declare_nine_terminals_by(
+        self,
+        participant_id: ParticipantId,
+    ) -> Result<Self, DeclareError<C::Tile>> {
+        let seat = self.validate_declare_nine_terminals_by(&participant_id)?;
+
+        Ok(Self {
+            phase: Phase::Ended(HandEnding::NineTerminals { seat }),
+            ..self
+        })
+    }
+
+    /// Declarations in the own turn right after drawing.
+    fn validate_declaration_by(
+        &self,
+        participant_id: &ParticipantId,
+    ) -> Result<Seat, DeclareError<C::Tile>> {
+        let seat = self
+            .get_seat_of(participant_id)
+            .ok_or_else(|| DeclareError::UnknownParticipant(participant_id.clone()))?;
+        match self.phase {
+            Phase::AwaitingDiscard(turn) if turn == seat => Ok(seat),
+            Phase::AwaitingDiscard(turn) => Err(DeclareError::NotParticipantsTurn {
+                participant: participant_id.clone(),
+                seat,
+                turn,
+            }),
+            _ => Err(DeclareError::UnexpectedPhase {
+                participant: participant_id.clone(),
+                seat,
+                phase: self.phase.kind(),
+            }),
+        }
+    }
+
+    /// 栄和
+    pub(crate) fn validate_claim_completion_by(
+        &self,
+        participant_id: &ParticipantId,
+    ) -> Result<Seat, ClaimError<C::Tile>> {
+        let (seat, window) = self.get_claim_window_for(participant_id)?;
+        let hand = self.hands.get(seat);
+        let tile = window.discarded_tile;
+        if !self.table_info.hand_judgement_spec.is_waiting_on(
+            &hand.concealed_tiles,
+            &hand.exposed_melds,
+            tile,
+        ) {
+            return Err(ClaimError::NotWaiting {
+                participant: participant_id.clone(),
+                seat,
+                tile,
+            });
+        }
+        let furiten = self.furiten_of(seat);
+        if furiten.is_furiten() {
+            return Err(ClaimError::Furiten {
+                participant: participant_id.clone(),
+                seat,
+                tile,
+                furiten,
+            });
+        }
+        if !self.is_scorable(seat, Some(window.discarder)) {
+            return Err(ClaimError::NoYaku {
+                participant: participant_id.clone(),
+                seat,
+                tile,
+            });
+        }
+
+        Ok(seat)
+    }
+
+    pub(crate) fn claim_completion_by(
+        self,
+        participant_id: ParticipantId,
+    ) -> Result<Self, ClaimError<C::Tile>> {
+        let seat = self.validate_claim_completion_by(&participant_id)?;
+        Ok(self.respond_by(seat, Response::Completion))
+    }
+
+    /// ポン・チー
+    pub(crate) fn validate_make_meld_by(
+        &self,
+        meld: &C::Meld,
+        participant_id: &ParticipantId,
+    ) -> Result<Seat, ClaimError<C::Tile>> {
+        let (seat, window) = self.get_claim_window_for(participant_id)?;
+        let participant = participant_id.clone();
+        let tile = window.discarded_tile;
+        let hand = self.hands.get(seat);
+        // TODO 大明槓
+        if meld.is_quad() {
+            Err(ClaimError::QuadNotSupported {
+                participant,
+                seat,
+                tile,
+            })
+        } else if hand.is_ready_declared {
+            Err(ClaimError::ReadyDeclared {
+                participant,
+                seat,
+                tile,
+            })
+        } else if meld.called_tile() != Some(tile) {
+            Err(ClaimError::MeldWithoutDiscardedTile {
+                participant,
+                seat,
+                tile,
+            })
+        } else if !meld.is_well_formed() {
+            Err(ClaimError::MalformedMeld {
+                participant,
+                seat,
+                tile,
+            })
+        } else if meld.is_sequence() && !self.table_info.rule.allows_chow {
+            Err(ClaimError::SequenceNotAllowed {
+                participant,
+                seat,
+                tile,
+            })
+        } else if meld.is_sequence()
+            && seat
+                != self
+                    .table_info
+                    .rule
+                    .players_count
+                    .next_seat(window.discarder)
+        {
+            Err(ClaimError::SequenceFromNonAdjacentSeat {
+                participant,
+                seat,
+                tile,
+                discarder: window.discarder,
+            })
+        } else {
+            match hand.rest_after_exposing(meld) {
+                None => Err(ClaimError::MissingMeldTiles {
+                    participant,
+                    seat,
+                    tile,
+                }),
+                Some(rest)
+                    if !self.table_info.rule.allows_swap_calling
+                        && rest.iter().all(|t| meld.is_swap_calling(*t)) =>
+                {
+                    Err(ClaimError::NoDiscardableTileAfterMeld {
+                        participant,
+                        seat,
+                        tile,
+                    })
+                }
+                Some(_) => Ok(seat),
+            }
+        }
+    }
+
+    pub(crate) fn make_meld_by(
+        self,
+        meld: C::Meld,
+        participant_id: ParticipantId,
+    ) -> Result<Self, ClaimError<C::Tile>> {
+        let seat = self.validate_make_meld_by(&meld, &participant_id)?;
+        Ok(self.respond_by(seat, Response::Meld(meld)))
+    }
+
+    pub(crate) fn validate_pass_by(
+        &self,
+        participant_id: &ParticipantId,
+    ) -> Result<Seat, ClaimError<C::Tile>> {
+        self.get_claim_window_for(participant_id)
+            .map(|(seat, _)| seat)
+    }
+
+    pub(crate) fn pass_by(
+        self,
+        participant_id: ParticipantId,
+    ) -> Result<Self, ClaimError<C::Tile>> {
+        let seat = self.validate_pass_by(&participant_id)?;
+        Ok(self.respond_by(seat, Response::Pass))
+    }
+
+    /// The seat of the participant who can respond to the discarded tile now.
+    fn get_claim_window_for(
+        &self,
+        participant_id: &ParticipantId,
+    ) -> Result<(Seat, &ClaimWindow<C>), ClaimError<C::Tile>> {
+        let seat = self
+            .get_seat_of(participant_id)
+            .ok_or_else(|| ClaimError::UnknownParticipant(participant_id.clone()))?;
+        let window = match self.phase {
+            Phase::AwaitingClaims(ref window) => window,
+            _ => {
+                return Err(ClaimError::UnexpectedPhase {
+                    participant: participant_id.clone(),
+                    seat,
+                    phase: self.phase.kind(),
+                })
+            }
+        };
+        if seat == window.discarder {
+            return Err(ClaimError::OwnDiscard {
+                participant: participant_id.clone(),
+                seat,
+                tile: window.discarded_tile,
+            });
+        } else if window.has_responded(seat) {
+            return Err(ClaimError::AlreadyResponded {
+                participant: participant_id.clone(),
+                seat,
+                tile: window.discarded_tile,
+            });
+        }
+
+        Ok((seat, window))
+    }
+
+    /// Responding other than a completion on a waiting tile counts as missing it.
+    fn respond_by(self, seat: Seat, response: Response<C>) -> Self {
+        let misses_completion = !matches!(response, Response::Completion)
+            && self.discarded_tile().is_some_and(|tile| {
+                let hand = self.hands.get(seat);
+                self.table_info.hand_judgement_spec.is_waiting_on(
+                    &hand.concealed_tiles,
+                    &hand.exposed_melds,
+                    tile,
+                )
+            });
+        let hands = if misses_completion {
+            self.hands.update(seat, Hand::miss_completion)
+        } else {
+            self.hands
+        };
+        let window = match self.phase {
+            Phase::AwaitingClaims(window) => window.accept(seat, response),
+            _ => unreachable!("Responses should be validated"),
+        };
+        if !window.is_closed() {
+            return Self {
+                phase: Phase::AwaitingClaims(window),
+                hands,
+                ..self
+            };
+        }
+
+        let discarder = window.discarder;
+        match window.resolve(&self.table_info.rule) {
+            Resolution::Completion(winners) => Self {
+                phase: Phase::Ended(HandEnding::Completion {
+                    winners,
+                    discarder: Some(discarder),
+                }),
+                hands,
+                ..self
+            },
+            Resolution::TripleRon(claimants) => Self {
+                phase: Phase::Ended(HandEnding::TripleRon {
+                    claimants,
+                    discarder,
+                }),
+                hands,
+                ..self
+            },
+            Resolution::Meld(seat, meld) => {
+                let discards = self
+                    .discards
+                    .mark_last_tile_called_from(discarder)
+                    .expect("The discarder should have discarded the tile");
+                let hands = hands
+                    .expose(meld)
+                    .in_hand_of(seat)
+                    .expect("The meld should be validated")
+                    .update_each(|hand| hand.is_right_after_ready = false);
+                let is_liable_meld = self
+                    .table_info
+                    .hand_judgement_spec
+                    .is_liable_meld(&hands.get(seat).exposed_melds);
+                let hands = if is_liable_meld {
+                    hands.update(seat, |hand| hand.liable_seat = Some(discarder))
+                } else {
+                    hands
+                };
+                Self {
+                    phase: Phase::AwaitingDiscardAfterCall(seat),
+                    hands,
+                    discards,
+                    ..self
+                }
+                .establish_ready_of(discarder)
+            }
+            Resolution::Nothing if self.wall_tiles.is_exhausted() => {
+                let spec = &self.table_info.hand_judgement_spec;
+                let declarations = self
+                    .table_info
+                    .rule
+                    .players_count
+                    .seats()
+                    .map(|seat| {
+                        let hand = hands.get(seat);
+                        let is_ready = spec.is_ready(&hand.concealed_tiles, &hand.exposed_melds);
+                        // A hand declared ready can't be hidden (聴牌隠し).
+                        let declaration = match (is_ready, hand.is_ready_declared) {
+                            (false, _) => Some(false),
+                            (true, true) => Some(true),
+                            (true, false) => None,
+                        };
+                        (seat, declaration)
+                    })
+                    .collect();
+                Self {
+                    phase: Phase::AwaitingReadyDeclarations(declarations),
+                    hands,
+                    ..self
+                }
+                .establish_ready_of(discarder)
+                .end_exhaustive_draw_if_declared()
+            }
+            Resolution::Nothing => Self {
+                phase: Phase::AwaitingDraw(self.table_info.rule.players_count.next_seat(discarder)),
+                hands,
+                ..self
+            }
+            .establish_ready_of(discarder)
+            .end_abortively_if_any(),
+        }
+    }
+
+    /// 立直 is established when the declaring discard passes without 栄和.
+    fn establish_ready_of(self, seat: Seat) -> Self {
+        let hand = self.hands.get(seat);
+        if !hand.is_ready_declared || hand.is_ready_deposited {
+            return self;
+        }
+
+        let mut this = self;
+        let participant_id = this.participant_at(seat).clone();
+        this.table_info
+            .players
+            .gain(&participant_id, -READY_DEPOSIT);
+        this.progress.deposits += READY_DEPOSIT;
+        Self {
+            hands: this
+                .hands
+                .update(seat, |hand| hand.is_ready_deposited = true),
+            ..this
+        }
+    }
+
+    /// 四風連打 and 四家立直, once the discard passes.
+    fn end_abortively_if_any(self) -> Self {
+        let rule = &self.table_info.rule;
+        let seats = rule.players_count.seats().collect_vec();
+        let first_discards = seats
+            .iter()
+            .filter_map(|seat| match self.discards.get(*seat) {
+                [(tile, _)] => Some(*tile),
+                _ => None,
+            })
+            .collect_vec();
+        let is_four_winds = rule.abortive_draws.four_winds
+            && first_discards.len() == seats.len()
+            && seats
+                .iter()
+                .all(|seat| self.hands.get(*seat).exposed_melds.is_empty())
+            && self
+                .table_info
+                .hand_judgement_spec
+                .is_wind(first_discards[0])
+            && first_discards.iter().all(|tile| *tile == first_discards[0]);
+        let is_four_riichi = rule.abortive_draws.four_riichi
+            && seats
+                .iter()
+                .all(|seat| self.hands.get(*seat).is_ready_deposited);
+
+        let ending = if is_four_winds {
+            HandEnding::FourWinds
+        } else if is_four_riichi {
+            HandEnding::FourRiichi
+        } else {
+            return self;
+        };
+        Self {
+            phase: Phase::Ended(ending),
+            ..self
+        }
+    }
+
+    fn end_exhaustive_draw_if_declared(self) -> Self {
+        let declarations = match self.phase {
+            Phase::AwaitingReadyDeclarations(ref declarations) => declarations,
+            _ => unreachable!("Should be at exhaustive draw"),
+        };
+        if declarations.values().any(Option::is_none) {
+            return self;
+        }
+
+        let seats = self.table_info.rule.players_count.seats().collect_vec();
+        let ready_seats = seats
+            .iter()
+            .copied()
+            .filter(|seat| declarations[seat] == Some(true))
+            .collect_vec();
+        let spec = &self.table_info.hand_judgement_spec;
+        // 流し満貫
+        let nagashi_mangan_seats = seats
+            .iter()
+            .copied()
+            .filter(|seat| {
+                let discarded_tiles = self.discards.get(*seat);
+                self.table_info.rule.allows_nagashi_mangan
+                    && !discarded_tiles.is_empty()
+                    && discarded_tiles
+                        .iter()
+                        .all(|(tile, called)| !*called && spec.is_terminal_or_honor(*tile))
+            })
+            .collect_vec();
+        Self {
+            phase: Phase::Ended(HandEnding::ExhaustiveDraw {
+                ready_seats,
+                nagashi_mangan_seats,
+            }),
+            ..self
+        }
+    }
+
+    /// 流局時の聴牌宣言. A ready hand can be hidden (聴牌隠し).
+    pub(crate) fn validate_declare_ready_at_exhaustive_draw_by(
+        &self,
+        participant_id: &ParticipantId,
+    ) -> Result<Seat, DeclareError<C::Tile>> {
+        let seat = self
+            .get_seat_of(participant_id)
+            .ok_or_else(|| DeclareError::UnknownParticipant(participant_id.clone()))?;
+        match self.phase {
+            Phase::AwaitingReadyDeclarations(ref declarations) => match declarations[&seat] {
+                None => Ok(seat),
+                Some(_) if !self.is_ready(seat) => Err(DeclareError::NotReady {
+                    participant: participant_id.clone(),
+                    seat,
+                }),
+                Some(_) => Err(DeclareError::AlreadyDeclared {
+                    participant: participant_id.clone(),
+                    seat,
+                }),
+            },
+            _ => Err(DeclareError::UnexpectedPhase {
+                participant: participant_id.clone(),
+                seat,
+                phase: self.phase.kind(),
+            }),
+        }
+    }
+
+    pub(crate) fn declare_ready_at_exhaustive_draw_by(
+        self,
+        reveals: bool,
+        participant_id: ParticipantId,
+    ) -> Result<Self, DeclareError<C::Tile>> {
+        let seat = self.validate_declare_ready_at_exhaustive_draw_by(&participant_id)?;
+        let phase = match self.phase {
+            Phase::AwaitingReadyDeclarations(mut declarations) => {
+                declarations.insert(seat, Some(reveals));
+                Phase::AwaitingReadyDeclarations(declarations)
+            }
+            _ => unreachable!("Declarations should be validated"),
+        };
+
+        Ok(Self { phase, ..self }.end_exhaustive_draw_if_declared())
+    }
+
+    pub(crate) fn is_ended(&self) -> bool {
+        matches!(self.phase, Phase::Ended(_))
+    }
+
+    /// Settles the ended hand and prepares the next one.
+    pub(crate) fn finish(self) -> HandPreparingTable<C> {
+        let ending = match self.phase {
+            Phase::Ended(ref ending) => ending.clone(),
+            _ => panic!("Should finish after the hand ended"),
+        };
+        let dealer = self.progress.dealer();
+        let ledger = self.ledger(&ending);
+
+        let rule = &self.table_info.rule;
+        let players_count = rule.players_count;
+        let progress = match ending {
+            HandEnding::ExhaustiveDraw { ready_seats, .. } => self.progress.after_exhaustive_draw(
+                rule.has_dealer_repeat && ready_seats.contains(&dealer),
+                players_count,
+            ),
+            HandEnding::Completion { winners, .. } => self.progress.after_completion(
+                rule.has_dealer_repeat && winners.contains(&dealer),
+                players_count,
+            ),
+            HandEnding::TripleRon { .. }
+            | HandEnding::NineTerminals { .. }
+            | HandEnding::FourWinds
+            | HandEnding::FourRiichi => self.progress.after_abortive_draw(),
+        };
+
+        let mut table_info = self.table_info;
+        for seat in players_count.seats() {
+            let participant_id = table_info
+                .seating_list
+                .get_participant_at(seat)
+                .expect("Every seat should be taken")
+                .clone();
+            table_info
+                .players
+                .gain(&participant_id, ledger.balance_of(seat));
+        }
+
+        HandPreparingTable::next(table_info, progress)
+    }
+
+    /// 点数移動 of the ended hand. 供託 goes to the first winner from the discarder, and so do
+    /// 積み棒 unless `rule.pays_counters_to_each_winner`.
+    fn ledger(&self, ending: &HandEnding) -> Ledger {
+        let rule = &self.table_info.rule;
+        let dealer = self.progress.dealer();
+        match ending {
+            HandEnding::ExhaustiveDraw {
+                ready_seats,
+                nagashi_mangan_seats,
+            } => Ledger::exhaustive_draw(rule, ready_seats, nagashi_mangan_seats, dealer),
+            HandEnding::Completion { winners, discarder } => {
+                let mut ledger = Ledger::default();
+                for (i, winner) in winners.iter().enumerate() {
+                    let hand = self.winning_hand_of(*winner, *discarder);
+                    let score = self.table_info.hand_judgement_spec.score(&hand, rule);
+                    let counters = if i == 0 || rule.pays_counters_to_each_winner {
+                        self.progress.deals_count
+                    } else {
+                        0
+                    };
+                    let deposits = if i == 0 { self.progress.deposits } else { 0 };
+                    ledger.append(Ledger::completion(rule, &hand, &score, counters, deposits));
+                }
+                ledger
+            }
+            HandEnding::TripleRon { .. }
+            | HandEnding::NineTerminals { .. }
+            | HandEnding::FourWinds
+            | HandEnding::FourRiichi => Ledger::default(),
+        }
+    }
+
+    fn winning_hand_of(&self, seat: Seat, discarder: Option<Seat>) -> WinningHand<C> {
+        let hand = self.hands.get(seat);
+        let mut concealed_tiles = hand.concealed_tiles.clone();
+        let winning_tile = match discarder {
+            Some(discarder) => self
+                .discards
+                .get(discarder)
+                .last()
+                .map(|(tile, _)| *tile)
+                .expect("The discarder should have discarded the tile"),
+            None => concealed_tiles
+                .pop()
+                .expect("The winner should have drawn the tile"),
+        };
+        let rule = &self.table_info.rule;
+        let visible_tiles = rule
+            .players_count
+            .seats()
+            .flat_map(|s| {
+                let discards = self.discards.get(s);
+                let discards = match discarder {
+                    Some(discarder) if discarder == s => &discards[..discards.len() - 1],
+                    _ => discards,
+                };
+                let exposed_tiles = self
+                    .hands
+                    .get(s)
+                    .exposed_melds
+                    .iter()
+                    .flat_map(|m| m.tiles());
+                discards
+                    .iter()
+                    .filter(|(_, called)| !called)
+                    .map(|(tile, _)| *tile)
+                    .chain(exposed_tiles)
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        // TODO 槓ドラ
+        let indication_tiles = &self.reward_indication_tiles.0;
+        let hidden_indication_tiles = indication_tiles.iter().skip(indication_tiles.len() / 2);
+        WinningHand {
+            concealed_tiles,
+            exposed_melds: hand.exposed_melds.clone(),
+            winning_tile,
+            bonus_tiles: hand.bonus_tiles.clone(),
+            seat,
+            dealer: self.progress.dealer(),
+            round: self.progress.current_hand.0,
+            discarder,
+            is_ready_declared: hand.is_ready_declared,
+            is_ready_declared_first: hand.is_ready_declared_first,
+            is_right_after_ready: hand.is_right_after_ready,
+            is_last_tile: self.wall_tiles.is_exhausted(),
+            is_supplemental_tile: discarder.is_none() && hand.has_drawn_supplemental_tile,
+            is_first_draw: self.is_first_turn_of(seat),
+            visible_tiles,
+            reward_indication_tiles: indication_tiles.iter().take(1).copied().collect(),
+            hidden_reward_indication_tiles: if hand.is_ready_declared && rule.has_ura_dora {
+                hidden_indication_tiles.take(1).copied().collect()
+            } else {
+                vec![]
+            },
+            liable_seat: hand.liable_seat,
+        }
+    }
+
+    pub(crate) fn view_for(&self, seat: Seat) -> TableView<C> {
+        let hand = self.hands.get(seat);
+        TableView {
+            table_id: self.table_info.id.clone(),
+            seat,
+            phase: self.phase.kind(),
+            turn: self.phase.turn(),
+            round: self.progress.current_hand.0,
+            hand_number: self.progress.current_hand.1,
+            deals_count: self.progress.deals_count,
+            deposits: self.progress.deposits,
+            dealer: self.progress.dealer(),
+            wall_tiles_count: self.wall_tiles.0.len(),
+            supplemental_tiles_count: self.supplemental_tiles.0.len(),
+            // TODO 槓ドラ
+            reward_indication_tiles: self
+                .reward_indication_tiles
+                .0
+                .iter()
+                .take(1)
+                .copied()
+                .collect(),
+            concealed_tiles: hand.concealed_tiles.clone(),
+            discardable_tiles: self.discardable_tiles_of(seat),
+            furiten: self.furiten_of(seat),
+            ending: match self.phase {
+                Phase::Ended(ref ending) => Some(ending.clone()),
+                _ => None,
+            },
+            ledger: match self.phase {
+                Phase::Ended(ref ending) => Some(self.ledger(ending)),
+                _ => None,
+            },
+            winning_hands: match self.phase {
+                Phase::Ended(HandEnding::Completion {
+                    ref winners,
+                    discarder,
+                }) => winners
+                    .iter()
+                    .map(|winner| self.winning_hand_of(*winner, discarder))
+                    .collect(),
+                _ => vec![],
+            },
+            discarded_tile: match self.phase {
+                Phase::AwaitingClaims(ref window) => {
+                    Some((window.discarder, window.discarded_tile))
+                }
+                _ => None,
+            },
+            seats: self
+                .table_info
+                .rule
+                .players_count
+                .seats()
+                .map(|s| {
+                    let hand = self.hands.get(s);
+                    let participant = self.participant_at(s).clone();
+                    SeatView {
+                        seat: s,
+                        point: self.table_info.players.point_of(&participant),
+                        participant,
+                        concealed_tiles_count: hand.concealed_tiles.len(),
+                        exposed_melds: hand.exposed_melds.clone(),
+                        discarded_tiles: self.discards.get(s).to_vec(),
+                        bonus_tiles: hand.bonus_tiles.clone(),
+                        is_ready_declared: hand.is_ready_declared,
+                        liable_seat: hand.liable_seat,
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct TableId(uuid::Uuid);
+
+impl TableId {
+    fn generate() -> TableId {
+        TableId(uuid::Uuid::new_v4())
+    }
+}
+
+impl fmt::Display for TableId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "table {}", self.0)
+    }
+}
+
+pub(crate) enum Phase<C: Concept> {
+    /// 自摸待ち
+    AwaitingDraw(Seat),
+    /// 打牌、または自摸和了などの宣言待ち
+    AwaitingDiscard(Seat),
+    /// 鳴いた後の打牌待ち
+    AwaitingDiscardAfterCall(Seat),
+    /// 打牌に対する栄和・鳴き待ち
+    AwaitingClaims(ClaimWindow<C>),
+    /// 流局時の聴牌宣言待ち. `None` until the seat declares.
+    AwaitingReadyDeclarations(HashMap<Seat, Option<bool>>),
+    Ended(HandEnding),
+}
+
+impl<C: Concept> Phase<C> {
+    pub(crate) fn kind(&self) -> PhaseKind {
+        match self {
+            Phase::AwaitingDraw(_) => PhaseKind::AwaitingDraw,
+            Phase::AwaitingDiscard(_) => PhaseKind::AwaitingDiscard,
+            Phase::AwaitingDiscardAfterCall(_) => PhaseKind::AwaitingDiscardAfterCall,
+            Phase::AwaitingClaims(_) => PhaseKind::AwaitingClaims,
+            Phase::AwaitingReadyDeclarations(_) => PhaseKind::AwaitingReadyDeclarations,
+            Phase::Ended(_) => PhaseKind::Ended,
+        }
+    }
+
+    /// The seat whose action is awaited, if only one.
+    pub(crate) fn turn(&self) -> Option<Seat> {
+        match *self {
+            Phase::AwaitingDraw(seat)
+            | Phase::AwaitingDiscard(seat)
+            | Phase::AwaitingDiscardAfterCall(seat) => Some(seat),
+            _ => None,
+        }
+    }
+}
 
-        Ok(!self.turn.is_turn_of(seat))
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum PhaseKind {
+    AwaitingDraw,
+    AwaitingDiscard,
+    AwaitingDiscardAfterCall,
+    AwaitingClaims,
+    AwaitingReadyDeclarations,
+    Ended,
+}
+
+impl fmt::Display for PhaseKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            PhaseKind::AwaitingDraw => "awaiting a draw",
+            PhaseKind::AwaitingDiscard => "awaiting a discard or a declaration",
+            PhaseKind::AwaitingDiscardAfterCall => "awaiting a discard after a call",
+            PhaseKind::AwaitingClaims => "awaiting claims on the discarded tile",
+            PhaseKind::AwaitingReadyDeclarations => {
+                "awaiting ready declarations at exhaustive draw"
+            }
+            PhaseKind::Ended => "the hand has ended",
+        };
+        write!(f, "{}", description)
     }
 }
 
-// TODO メソッド毎に細かく分ける
-#[derive(Error, Debug)]
-enum TableError {
-    #[error("")] // TODO
-    ParticipantsExceededError(u8),
-    #[error("")] // TODO
-    UnknownParticipantError,
-    #[error("")] // TODO
-    NotParticipantsTurnError,
-    #[error("")] // TODO
-    WallExhaustedError,
-    #[error("")] // TODO
-    UnknownError,
+pub(crate) struct ClaimWindow<C: Concept> {
+    discarder: Seat,
+    discarded_tile: C::Tile,
+    players_count: PlayersCount,
+    responses: HashMap<Seat, Response<C>>,
 }
 
-struct TableId(uuid::Uuid);
+enum Response<C: Concept> {
+    Pass,
+    Completion,
+    Meld(C::Meld),
+}
 
-impl TableId {
-    fn generate() -> TableId {
-        TableId(uuid::Uuid::new_v4())
-    }
+enum Resolution<C: Concept> {
+    Completion(Vec<Seat>),
+    TripleRon(Vec<Seat>),
+    Meld(Seat, C::Meld),
+    Nothing,
 }
 
-struct Turn(Seat);
+impl<C: Concept> ClaimWindow<C> {
+    fn open(discarder: Seat, discarded_tile: C::Tile, players_count: PlayersCount) -> Self {
+        Self {
+            discarder,
+            discarded_tile,
+            players_count,
+            responses: HashMap::new(),
+        }
+    }
+
+    fn has_responded(&self, seat: Seat) -> bool {
+        self.responses.contains_key(&seat)
+    }
 
-impl Turn {
-    fn get_initial() -> Self {
-        Self(Seat::East)
+    fn accept(self, seat: Seat, response: Response<C>) -> Self {
+        let mut this = self;
+        this.responses.insert(seat, response);
+        this
     }
 
-    fn is_turn_of(&self, seat: Seat) -> bool {
-        self.0 == seat
+    fn is_closed(&self) -> bool {
+        self.responses.len() == self.players_count.get() - 1
+    }
+
+    /// A completion takes priority over melds, and a triplet takes priority over a sequence.
+    /// Completions by several seats follow `rule.multiple_ron`, nearest from the discarder first.
+    fn resolve(self, rule: &RuleConfig) -> Resolution<C> {
+        let mut responses = self.responses;
+        let players_count = self.players_count;
+        let seats = (1..players_count.get())
+            .scan(self.discarder, |seat, _| {
+                *seat = players_count.next_seat(*seat);
+                Some(*seat)
+            })
+            .collect_vec();
+
+        let winners = seats
+            .iter()
+            .filter(|s| matches!(responses.get(s), Some(Response::Completion)))
+            .copied()
+            .collect_vec();
+        if winners.len() == 3 && rule.abortive_draws.triple_ron {
+            return Resolution::TripleRon(winners);
+        }
+        if !winners.is_empty() {
+            return Resolution::Completion(match rule.multiple_ron {
+                MultipleRon::HeadBump => winners.into_iter().take(1).collect(),
+                MultipleRon::Multiple => winners,
+            });
+        }
+
+        let caller = seats
+            .iter()
+            .find(|s| matches!(responses.get(s), Some(Response::Meld(m)) if !m.is_sequence()))
+            .or_else(|| {
+                seats
+                    .iter()
+                    .find(|s| matches!(responses.get(s), Some(Response::Meld(_))))
+            });
+        match caller.and_then(|s| responses.remove(s).map(|r| (*s, r))) {
+            Some((seat, Response::Meld(meld))) => Resolution::Meld(seat, meld),
+            _ => Resolution::Nothing,
+        }
     }
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash)]
-enum Seat {
-    East,
-    South,
-    West,
-    North,
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum HandEnding {
+    /// 流局
+    ExhaustiveDraw {
+        ready_seats: Vec<Seat>,
+        nagashi_mangan_seats: Vec<Seat>,
+    },
+    /// 和了. `discarder` is `None` for 自摸和了. Winners of ダブロン・トリロン are listed from the
+    /// nearest to the discarder.
+    Completion {
+        winners: Vec<Seat>,
+        discarder: Option<Seat>,
+    },
+    /// 三家和, an abortive draw. Nobody wins.
+    TripleRon {
+        claimants: Vec<Seat>,
+        discarder: Seat,
+    },
+    /// 九種九牌, an abortive draw declared by the seat.
+    NineTerminals { seat: Seat },
+    /// 四風連打, an abortive draw.
+    FourWinds,
+    /// 四家立直, an abortive draw. 立直棒 stay as 供託.
+    FourRiichi,
 }
 
 struct WallTiles<C: Concept>(Vec<C::Tile>);
@@ -272,29 +1667,123 @@ impl<C: Concept> WallTiles<C> {
         let mut this = self;
         this.0.pop().map(|tile| (Self(this.0), tile))
     }
+
+    fn is_exhausted(&self) -> bool {
+        self.0.is_empty()
+    }
 }
 
+/// 嶺上牌
 struct SupplementalTiles<C: Concept>(Vec<C::Tile>);
 
+impl<C: Concept> SupplementalTiles<C> {
+    fn pick(self) -> Option<(Self, C::Tile)> {
+        let mut this = self;
+        this.0.pop().map(|tile| (Self(this.0), tile))
+    }
+}
+
+/// ドラ表示牌. The first half indicates ドラ and the second half 裏ドラ in the same order.
 struct RewardIndicationTiles<C: Concept>(Vec<C::Tile>);
 
-// TODO with consist of Closed Hand, Melds
 // TODO with condition （海底、槍槓）
-#[derive(Clone)]
-struct Hand<C: Concept>(Vec<C::Tile>);
+struct Hand<C: Concept> {
+    concealed_tiles: Vec<C::Tile>,
+    exposed_melds: Vec<C::Meld>,
+    /// 抜きドラ
+    bonus_tiles: Vec<C::Tile>,
+    is_ready_declared: bool,
+    /// 両立直
+    is_ready_declared_first: bool,
+    /// 一発, until the own next discard or any call.
+    is_right_after_ready: bool,
+    /// 立直棒 is put once the declaring discard passes without 栄和.
+    is_ready_deposited: bool,
+    /// 嶺上牌, until the own next discard.
+    has_drawn_supplemental_tile: bool,
+    missed_completion: bool,
+    missed_completion_after_ready: bool,
+    /// 包. Kept once a meld decides a yakuman.
+    liable_seat: Option<Seat>,
+}
 
 impl<C: Concept> Hand<C> {
+    fn new(concealed_tiles: Vec<C::Tile>) -> Self {
+        Self {
+            concealed_tiles,
+            exposed_melds: vec![],
+            bonus_tiles: vec![],
+            is_ready_declared: false,
+            is_ready_declared_first: false,
+            is_right_after_ready: false,
+            is_ready_deposited: false,
+            has_drawn_supplemental_tile: false,
+            missed_completion: false,
+            missed_completion_after_ready: false,
+            liable_seat: None,
+        }
+    }
+
     fn append_tile(&mut self, tile: C::Tile) {
-        self.0.push(tile);
+        self.concealed_tiles.push(tile);
+    }
+
+    fn remove_tile(&mut self, tile_index: TileIndex) -> Option<C::Tile> {
+        let index = tile_index.0 as usize;
+        (index < self.concealed_tiles.len()).then(|| self.concealed_tiles.remove(index))
+    }
+
+    fn expose(&mut self, meld: C::Meld) -> Option<()> {
+        self.concealed_tiles = self.rest_after_exposing(&meld)?;
+        self.exposed_melds.push(meld);
+        Some(())
+    }
+
+    /// The concealed tiles after exposing the meld, or `None` if the hand lacks its tiles.
+    fn rest_after_exposing(&self, meld: &C::Meld) -> Option<Vec<C::Tile>> {
+        let mut rest = self.concealed_tiles.clone();
+        let mut meld_tiles = meld.tiles();
+        if let Some(called_tile) = meld.called_tile() {
+            let position = meld_tiles.iter().position(|t| *t == called_tile)?;
+            meld_tiles.remove(position);
+        }
+        for tile in meld_tiles {
+            let position = rest.iter().position(|t| *t == tile)?;
+            rest.remove(position);
+        }
+        Some(rest)
+    }
+
+    fn miss_completion(&mut self) {
+        self.missed_completion = true;
+        if self.is_ready_declared {
+            self.missed_completion_after_ready = true;
+        }
     }
 }
 
 struct Hands<C: Concept>(HashMap<Seat, Hand<C>>);
 
 impl<C: Concept> Hands<C> {
+    fn get(&self, seat: Seat) -> &Hand<C> {
+        self.0.get(&seat).expect("Every seat should have a hand")
+    }
+
+    fn update(self, seat: Seat, f: impl FnOnce(&mut Hand<C>)) -> Self {
+        let mut this = self;
+        this.0.entry(seat).and_modify(f);
+        Self(this.0)
+    }
+
+    fn update_each(self, f: impl Fn(&mut Hand<C>)) -> Self {
+        let mut this = self;
+        this.0.values_mut().for_each(f);
+        Self(this.0)
+    }
+
     fn append_tile_to(self, tile: C::Tile, seat: Seat) -> Option<Self> {
         let mut this = self;
-        this.0.entry(seat).and_modify(|hand| hand.append_tile(tile));
+        this.0.get_mut(&seat)?.append_tile(tile);
         Some(Self(this.0))
     }
 
@@ -303,16 +1792,30 @@ impl<C: Concept> Hands<C> {
     }
 
     fn discard_tile_from(self, tile_index: TileIndex, seat: Seat) -> Option<(Self, C::Tile)> {
-        unimplemented!()
+        let mut this = self;
+        let tile = this.0.get_mut(&seat)?.remove_tile(tile_index)?;
+        Some((Self(this.0), tile))
     }
 
     fn discard(self, tile_index: TileIndex) -> HandsDiscardIntermediateState<C> {
         HandsDiscardIntermediateState(self, tile_index)
     }
+
+    fn expose_meld_in(self, meld: C::Meld, seat: Seat) -> Option<Self> {
+        let mut this = self;
+        this.0.get_mut(&seat)?.expose(meld)?;
+        Some(Self(this.0))
+    }
+
+    fn expose(self, meld: C::Meld) -> HandsExposeIntermediateState<C> {
+        HandsExposeIntermediateState(self, meld)
+    }
 }
 
 struct HandsAppendIntermediateState<C: Concept>(Hands<C>, C::Tile);
 
+// Named to read fluently, e.g. `hands.append(tile).to_hand_of(seat)`.
+#[allow(clippy::wrong_self_convention)]
 impl<C: Concept> HandsAppendIntermediateState<C> {
     fn to_hand_of(self, seat: Seat) -> Option<Hands<C>> {
         self.0.append_tile_to(self.1, seat)
@@ -321,13 +1824,24 @@ impl<C: Concept> HandsAppendIntermediateState<C> {
 
 struct HandsDiscardIntermediateState<C: Concept>(Hands<C>, TileIndex);
 
+// Named to read fluently, e.g. `hands.append(tile).to_hand_of(seat)`.
+#[allow(clippy::wrong_self_convention)]
 impl<C: Concept> HandsDiscardIntermediateState<C> {
     fn from_hand_of(self, seat: Seat) -> Option<(Hands<C>, C::Tile)> {
         self.0.discard_tile_from(self.1, seat)
     }
 }
 
-struct DiscardedTiles<C: Concept>(Vec<C::Tile>);
+struct HandsExposeIntermediateState<C: Concept>(Hands<C>, C::Meld);
+
+impl<C: Concept> HandsExposeIntermediateState<C> {
+    fn in_hand_of(self, seat: Seat) -> Option<Hands<C>> {
+        self.0.expose_meld_in(self.1, seat)
+    }
+}
+
+/// Each tile with whether it was called by another player.
+struct DiscardedTiles<C: Concept>(Vec<(C::Tile, bool)>);
 
 struct Discards<C: Concept>(HashMap<Seat, DiscardedTiles<C>>);
 
@@ -336,59 +1850,102 @@ impl<C: Concept> Discards<C> {
         Self(HashMap::new())
     }
 
+    fn get(&self, seat: Seat) -> &[(C::Tile, bool)] {
+        self.0.get(&seat).map_or(&[], |d| &d.0)
+    }
+
     fn append_tile_to(self, tile: C::Tile, seat: Seat) -> Option<Self> {
-        unimplemented!()
+        let mut this = self;
+        this.0
+            .entry(seat)
+            .or_insert_with(|| DiscardedTiles(vec![]))
+            .0
+            .push((tile, false));
+        Some(Self(this.0))
     }
 
     fn append(self, tile: C::Tile) -> DiscardsAppendIntermediateState<C> {
         DiscardsAppendIntermediateState(self, tile)
     }
+
+    fn mark_last_tile_called_from(self, seat: Seat) -> Option<Self> {
+        let mut this = self;
+        this.0.get_mut(&seat)?.0.last_mut()?.1 = true;
+        Some(Self(this.0))
+    }
 }
 
 struct DiscardsAppendIntermediateState<C: Concept>(Discards<C>, C::Tile);
 
+// Named to read fluently, e.g. `hands.append(tile).to_hand_of(seat)`.
+#[allow(clippy::wrong_self_convention)]
 impl<C: Concept> DiscardsAppendIntermediateState<C> {
     fn to_hand_of(self, seat: Seat) -> Option<Discards<C>> {
         self.0.append_tile_to(self.1, seat)
     }
 }
 
-#[derive(Clone, Eq, PartialEq, Hash)]
-struct ParticipantId(uuid::Uuid); // TODO make this external
-
 struct Participants(Vec<ParticipantId>);
 
-static MAX_PARTICIPANT: u8 = 4; // TODO const generics
-
 impl Participants {
     fn nobody() -> Self {
         Self(vec![])
     }
 
-    fn receive(self, new_participant: ParticipantId) -> Result<Self, TableError> {
-        (self.0.len() >= MAX_PARTICIPANT as usize)
-            .then(|| Self([self.0, vec![new_participant]].concat()))
-            .ok_or(TableError::ParticipantsExceededError(MAX_PARTICIPANT).into())
+    fn validate(
+        &self,
+        new_participant: &ParticipantId,
+        players_count: PlayersCount,
+    ) -> Result<(), JoinError> {
+        if self.0.contains(new_participant) {
+            Err(JoinError::AlreadyJoined(new_participant.clone()))
+        } else if self.0.len() >= players_count.get() {
+            Err(JoinError::ParticipantsExceeded {
+                participant: new_participant.clone(),
+                max: players_count.get(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    fn receive(
+        self,
+        new_participant: ParticipantId,
+        players_count: PlayersCount,
+    ) -> Result<Self, JoinError> {
+        self.validate(&new_participant, players_count)?;
+        Ok(Self([self.0, vec![new_participant]].concat()))
+    }
+
+    fn validate_sending_off(&self, a_participant: &ParticipantId) -> Result<(), JoinError> {
+        if self.0.contains(a_participant) {
+            Ok(())
+        } else {
+            Err(JoinError::NotJoined(a_participant.clone()))
+        }
     }
 
-    fn send_off(self, a_participant: ParticipantId) -> Result<Self, TableError> {
-        unimplemented!()
+    fn send_off(self, a_participant: ParticipantId) -> Result<Self, JoinError> {
+        self.validate_sending_off(&a_participant)?;
+        Ok(Self(
+            self.0.into_iter().filter(|p| *p != a_participant).collect(),
+        ))
     }
 
-    fn gathered(&self) -> bool {
-        // TODO 三麻
-        self.0.len() == MAX_PARTICIPANT as usize
+    fn gathered(&self, players_count: PlayersCount) -> bool {
+        self.0.len() == players_count.get()
     }
 }
 
 struct Player {
     id: ParticipantId,
-    point: u16, // TODO use Point VO
+    point: i32, // TODO use Point VO
 }
 
 impl Player {
     // TODO name
-    fn something_new(id: ParticipantId, initial_point: u16) -> Self {
+    fn something_new(id: ParticipantId, initial_point: i32) -> Self {
         Self {
             id,
             point: initial_point,
@@ -400,7 +1957,7 @@ struct Players(Vec<Player>);
 
 impl Players {
     // TODO name
-    fn form(participants: Participants, initial_point: u16) -> Self {
+    fn form(participants: Participants, initial_point: i32) -> Self {
         // TODO refactor
         Self(
             participants
@@ -410,202 +1967,695 @@ impl Players {
                 .collect(),
         )
     }
+
+    fn point_of(&self, participant_id: &ParticipantId) -> i32 {
+        self.0
+            .iter()
+            .find(|p| p.id == *participant_id)
+            .map_or(0, |p| p.point)
+    }
+
+    fn gain(&mut self, participant_id: &ParticipantId, point: i32) {
+        if let Some(player) = self.0.iter_mut().find(|p| p.id == *participant_id) {
+            player.point += point;
+        }
+    }
 }
 
-#[derive(Clone)]
 struct SeatingList(HashMap<ParticipantId, Seat>);
 
 impl SeatingList {
-    fn get_seat_of(&self, participant_id: ParticipantId) -> Option<Seat> {
-        self.0.get(&participant_id).map(|s| *s)
+    fn get_seat_of(&self, participant_id: &ParticipantId) -> Option<Seat> {
+        self.0.get(participant_id).copied()
     }
-}
 
-struct TileIndex(u8);
+    fn get_participant_at(&self, seat: Seat) -> Option<&ParticipantId> {
+        self.0.iter().find(|(_, s)| **s == seat).map(|(p, _)| p)
+    }
+}
 
-/**
- * 以上、作り直し部分のおわり。
- */
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct TileIndex(u8);
 
-pub(crate) struct TableContent<C: Concept> {
-    tile_dealing_spec: Rc<Box<dyn TileDealingSpec<C>>>,
-    wall_tiles: RefCell<Vec<C::Tile>>,
-    supplemental_tiles: RefCell<Vec<C::Tile>>,
-    reward_indication_tiles: RefCell<Vec<C::Tile>>,
-    progress: Cell<Progress>,
-    participants: RefCell<Option<ArrayVec<[ParticipantOld<C>; PLAYERS_COUNT]>>>,
+#[derive(Copy, Clone)]
+struct Progress {
+    current_hand: (Round, usize),
+    /// Rounds played through, since the round cycles in a game with all the four.
+    passed_rounds_count: usize,
+    deals_count: u8,
+    /// 供託, carried over until someone wins.
+    deposits: i32,
 }
 
-impl<C: Concept> TableContent<C> {
-    pub(crate) fn player_at(&self, seat: SeatOld) -> Rc<PlayerOld<C>> {
-        if let Some(ref participants) = *self.participants.borrow() {
-            participants.get(usize::from(seat)).unwrap().player.clone()
-        } else {
-            panic!();
+impl Progress {
+    fn get_initial() -> Progress {
+        Progress {
+            current_hand: (Round::East, 1),
+            passed_rounds_count: 0,
+            deals_count: 0,
+            deposits: 0,
         }
     }
 
-    pub(crate) fn start_game(&self, initial_point: i32) {
-        {
-            if let Some(ref participants) = *self.participants.borrow() {
-                for participant in participants.iter() {
-                    participant.player.set_initial_point(initial_point);
-                }
+    fn dealer(&self) -> Seat {
+        (self.current_hand.1 - 1).into()
+    }
+
+    /// 親が聴牌なら連荘、そうでなければ親流れ。いずれも本場は積まれる。
+    fn after_exhaustive_draw(self, is_dealer_ready: bool, players_count: PlayersCount) -> Progress {
+        Progress {
+            deals_count: self.deals_count.saturating_add(1),
+            ..self.next_hand_unless(is_dealer_ready, players_count)
+        }
+    }
+
+    /// 途中流局は親の連荘で本場が積まれる。
+    fn after_abortive_draw(self) -> Progress {
+        Progress {
+            deals_count: self.deals_count.saturating_add(1),
+            ..self
+        }
+    }
+
+    /// 親の和了なら連荘で本場が積まれ、子の和了なら親流れで本場はなくなる。
+    fn after_completion(self, is_dealer_won: bool, players_count: PlayersCount) -> Progress {
+        Progress {
+            deals_count: if is_dealer_won {
+                self.deals_count.saturating_add(1)
             } else {
-                panic!("Should call after join_users")
-            }
+                0
+            },
+            deposits: 0,
+            ..self.next_hand_unless(is_dealer_won, players_count)
         }
+    }
 
-        self.progress.replace(Progress::get_initial());
+    /// A round has as many hands as players.
+    fn next_hand_unless(self, is_dealer_continuing: bool, players_count: PlayersCount) -> Progress {
+        let (round, hand) = self.current_hand;
+        if is_dealer_continuing {
+            self
+        } else if hand == players_count.get() {
+            Progress {
+                current_hand: (round.next(), 1),
+                passed_rounds_count: self.passed_rounds_count + 1,
+                ..self
+            }
+        } else {
+            Progress {
+                current_hand: (round, hand + 1),
+                ..self
+            }
+        }
     }
+}
 
-    pub(crate) fn do_hand(&self) {
-        self.deal_tiles();
+/// 場
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Round {
+    East,
+    South,
+    West,
+    North,
+}
 
-        let dealer: SeatOld = self.progress.get().current_hand.1.into();
+impl Round {
+    fn next(&self) -> Round {
+        use Round::*;
 
-        let mut turn = dealer;
-        let result = loop {
-            if self.wall_tiles.borrow().is_empty() {
-                break HandResult::ExhaustiveDraw;
-            } else {
-                let action = {
-                    if let Some(ref participants) = *self.participants.borrow() {
-                        let turn = participants.get(usize::from(turn)).unwrap();
-                        turn.player.draw()
-                    } else {
-                        panic!()
-                    }
-                };
+        match self {
+            East => South,
+            South => West,
+            West => North,
+            North => East,
+        }
+    }
+}
 
-                match action {
-                    Action::Discard(tile) => {
-                        // TODO 他家の鳴きなど
-                        let used_in_meld = false;
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::game::config::AbortiveDraws;
+    use crate::rule::{
+        chow_of, tiles_of, Meld, NormalYaku, ScoreExplanation, StandardConcept,
+        StandardHandJudgementSpec, Tile,
+    };
 
-                        if let Some(ref participants) = *self.participants.borrow() {
-                            let turn = participants.get(usize::from(turn)).unwrap();
-                            turn.player.append_to_discarded_tiles(tile, used_in_meld);
-                        } else {
-                            panic!()
-                        };
+    pub(crate) struct InOrderSeatingSpec;
 
-                        turn = turn.next_seat();
-                    }
-                    // TODO action による分岐など
-                    _ => unimplemented!(),
-                }
-            }
-        };
+    impl SeatingSpec for InOrderSeatingSpec {
+        fn arrange(&self, participants: &[ParticipantId]) -> Vec<ParticipantId> {
+            participants.to_vec()
+        }
+    }
 
-        match result {
-            HandResult::ExhaustiveDraw => {
-                if let Some(ref participants) = *self.participants.borrow() {
-                    let personal_results = participants
-                        .iter()
-                        .map(|p| (p.seat, p.player.check_hand_ready()))
-                        .collect_vec();
-
-                    let n_ready = personal_results.iter().filter(|(_, b)| *b).count();
-                    let points_to_exchange = match n_ready {
-                        0 | 4 => (0, 0),
-                        1 => (3000, 1000),
-                        2 => (1500, 1500),
-                        3 => (1000, 3000),
-                        _ => panic!(),
-                    };
+    /// Deals the hands from East, and the wall to be drawn from the last.
+    pub(crate) struct FixedTileDealingSpec {
+        pub(crate) wall_tiles: Vec<Tile>,
+        pub(crate) player_tiles: Vec<Vec<Tile>>,
+    }
 
-                    for (seat, ready) in personal_results.iter() {
-                        let p = participants.get(usize::from(*seat)).unwrap();
-                        if *ready {
-                            p.player.gain_point(points_to_exchange.0);
-                        } else {
-                            p.player.lose_point(points_to_exchange.1);
-                        }
-                    }
+    impl TileDealingSpec<StandardConcept> for FixedTileDealingSpec {
+        fn deal(&self, _rule: &RuleConfig) -> DealtResult<StandardConcept> {
+            DealtResult::new(
+                self.wall_tiles.clone(),
+                tiles_of("1234m"),
+                tiles_of("1111222233m"),
+                self.player_tiles
+                    .iter()
+                    .enumerate()
+                    .map(|(i, tiles)| (tiles.clone(), Seat::from(i)))
+                    .collect(),
+            )
+        }
+    }
 
-                // TODO 流局処理残り
-                } else {
-                    panic!()
-                }
-            }
+    fn start_hand(
+        rule: RuleConfig,
+        dealing_spec: &FixedTileDealingSpec,
+    ) -> (HandPlayingTable<StandardConcept>, Vec<ParticipantId>) {
+        let participants: Vec<_> = (0..rule.players_count.get())
+            .map(|_| ParticipantId::generate())
+            .collect();
+        let mut table = WaitingTable::<StandardConcept>::setup(rule);
+        for participant in participants.iter() {
+            table = table.accept_participant(participant.clone()).unwrap();
         }
+        let table = table
+            .be_ready()
+            .unwrap_or_else(|_| panic!("The participants should be gathered"))
+            .arrange_initial_seating(&InOrderSeatingSpec)
+            .start_game(Arc::new(StandardHandJudgementSpec::default()))
+            .unwrap()
+            .deal(dealing_spec);
+        (table, participants)
     }
 
-    fn deal_tiles(&self) {
-        {
-            if self.participants.borrow().is_none() {
-                panic!("Should call after join_users")
+    fn pass_claims(
+        table: HandPlayingTable<StandardConcept>,
+        participants: &[ParticipantId],
+    ) -> HandPlayingTable<StandardConcept> {
+        participants.iter().fold(table, |table, participant| {
+            match table.validate_pass_by(participant) {
+                Ok(_) => table.pass_by(participant.clone()).unwrap(),
+                Err(_) => table,
             }
+        })
+    }
+
+    /// The dealer completes the hand on the first draw of 4s, or declares ready discarding 7z and
+    /// does on the next draw after the others discard honors.
+    pub(crate) fn dealer_ready_hand() -> FixedTileDealingSpec {
+        FixedTileDealingSpec {
+            wall_tiles: tiles_of("9m4s3z2z1z7z"),
+            player_tiles: vec![
+                tiles_of("123m456m789p1123s"),
+                tiles_of("258m258p258s1234z"),
+                tiles_of("369m369p369s5677z"),
+                tiles_of("147m147p147s5566z"),
+            ],
         }
+    }
 
-        let DealtResult {
-            wall_tiles,
-            supplemental_tiles,
-            reward_indication_tiles,
-            player_tiles,
-        } = self.tile_dealing_spec.deal();
+    /// The dealer draws 5s and discards it to South.
+    fn discard_to(south_tiles: &str) -> (HandPlayingTable<StandardConcept>, Vec<ParticipantId>) {
+        discard_to_each(
+            RuleConfig::default(),
+            [south_tiles, "369m369p369s5677z", "147m147p147s5566z"],
+        )
+    }
 
-        {
-            let groups = player_tiles.iter().group_by(|(_, s)| s);
-            let a = groups.into_iter().collect_vec();
-            if a.len() != PLAYERS_COUNT {
-                panic!("Wrong arg `player_tiles`: seats should be unique")
-            }
+    /// The dealer draws 5s and discards it to South, West and North.
+    fn discard_to_each(
+        rule: RuleConfig,
+        [south_tiles, west_tiles, north_tiles]: [&str; 3],
+    ) -> (HandPlayingTable<StandardConcept>, Vec<ParticipantId>) {
+        let dealing_spec = FixedTileDealingSpec {
+            wall_tiles: tiles_of("9m5s"),
+            player_tiles: vec![
+                tiles_of("258m258p258s1234z"),
+                tiles_of(south_tiles),
+                tiles_of(west_tiles),
+                tiles_of(north_tiles),
+            ],
+        };
+        let (table, participants) = start_hand(rule, &dealing_spec);
+        let table = table.draw_tile_by(participants[0].clone()).unwrap();
+        let index = table.index_of(Seat::East, tiles_of("5s")[0]).unwrap();
+        let table = table
+            .discard_tile_by(index, participants[0].clone())
+            .unwrap();
+        (table, participants)
+    }
+
+    /// Each seat draws and discards the tile in turn, and the others pass.
+    fn draw_and_discard_each(
+        table: HandPlayingTable<StandardConcept>,
+        participants: &[ParticipantId],
+        tiles: &str,
+        declares_ready: bool,
+    ) -> HandPlayingTable<StandardConcept> {
+        let mut table = table;
+        for tile in tiles_of(tiles) {
+            let seat = table.phase().turn().unwrap();
+            let participant = table.participant_at(seat).clone();
+            table = table.draw_tile_by(participant.clone()).unwrap();
+            let index = table.index_of(seat, tile).unwrap();
+            table = if declares_ready {
+                table.declare_ready_by(index, participant).unwrap()
+            } else {
+                table.discard_tile_by(index, participant).unwrap()
+            };
+            table = pass_claims(table, participants);
         }
+        table
+    }
 
-        self.wall_tiles.replace(wall_tiles);
-        self.supplemental_tiles.replace(supplemental_tiles);
-        self.reward_indication_tiles
-            .replace(reward_indication_tiles);
-        if let Some(ref participants) = *self.participants.borrow() {
-            for (i, (tiles, _)) in player_tiles.iter().sorted_by_key(|t| t.1).enumerate() {
-                let participant = participants.get(i).unwrap();
-                participant.player.accept_deal(tiles.clone());
-            }
+    #[test]
+    fn participants_join_once_up_to_the_players_count() {
+        let participants: Vec<_> = (0..5).map(|_| ParticipantId::generate()).collect();
+        let mut table = WaitingTable::<StandardConcept>::setup(RuleConfig::default());
+        for participant in participants[..4].iter() {
+            table = table.accept_participant(participant.clone()).unwrap();
         }
+        assert!(matches!(
+            table.validate_participant(&participants[0]),
+            Err(JoinError::AlreadyJoined(_))
+        ));
+        let error = table.validate_participant(&participants[4]).unwrap_err();
+        assert!(matches!(
+            error,
+            JoinError::ParticipantsExceeded { max: 4, .. }
+        ));
+        assert!(error
+            .to_string()
+            .ends_with("the table already has 4 participants"));
+        assert!(matches!(
+            table.validate_leaving(&participants[4]),
+            Err(JoinError::NotJoined(_))
+        ));
     }
 
-    pub(crate) fn pop_new_tile(&self) -> Option<C::Tile> {
-        self.wall_tiles.borrow_mut().pop()
+    #[test]
+    fn game_is_not_started_before_seating() {
+        let mut table = WaitingTable::<StandardConcept>::setup(RuleConfig::default());
+        for _ in 0..4 {
+            table = table.accept_participant(ParticipantId::generate()).unwrap();
+        }
+        let table = table
+            .be_ready()
+            .unwrap_or_else(|_| panic!("The participants should be gathered"));
+        assert!(matches!(
+            table.validate_start(),
+            Err(SeatError::NotArranged)
+        ));
     }
-}
 
-#[derive(Copy, Clone)]
-struct Progress {
-    current_hand: (Round, usize),
-    deals_count: u8,
-}
+    #[test]
+    fn ron_without_yaku_is_refused() {
+        let (table, participants) = discard_to("123m789p111s11z46s");
+        let result = table.validate_claim_completion_by(&participants[1]);
+        assert!(matches!(result, Err(ClaimError::NoYaku { .. })));
+    }
 
-impl Progress {
-    pub fn initial() -> Progress {
-        Progress {
-            current_hand: (Round::East, 1),
-            deals_count: 0,
-        }
+    #[test]
+    fn ron_with_yaku_is_accepted() {
+        let (table, participants) = discard_to("234m678p222s88p46s");
+        let result = table.validate_claim_completion_by(&participants[1]);
+        assert!(matches!(result, Ok(Seat::South)));
     }
 
-    pub fn get_initial() -> Progress {
-        Progress {
-            current_hand: (Round::East, 1),
-            deals_count: 0,
-        }
+    #[test]
+    fn action_out_of_phase_is_refused() {
+        let (table, participants) = discard_to("123m456p111z67s88s");
+        assert!(matches!(
+            table.validate_discard_by(TileIndex(0), &participants[1]),
+            Err(DiscardError::UnexpectedPhase {
+                phase: PhaseKind::AwaitingClaims,
+                ..
+            })
+        ));
+
+        let table = pass_claims(table, &participants);
+        assert!(matches!(
+            table.validate_claim_completion_by(&participants[2]),
+            Err(ClaimError::UnexpectedPhase {
+                phase: PhaseKind::AwaitingDraw,
+                ..
+            })
+        ));
+
+        let table = table.draw_tile_by(participants[1].clone()).unwrap();
+        assert!(matches!(
+            table.validate_draw_by(&participants[1]),
+            Err(DrawError::UnexpectedPhase {
+                phase: PhaseKind::AwaitingDiscard,
+                ..
+            })
+        ));
     }
-}
 
-struct ParticipantOld<C: Concept> {
-    player: Rc<PlayerOld<C>>,
-    seat: SeatOld,
-}
+    #[test]
+    fn swap_calling_is_not_discarded_after_chow() {
+        let (table, participants) = discard_to("123m456p111z67s88s");
+        let table = table
+            .make_meld_by(chow_of("567s", "5s"), participants[1].clone())
+            .unwrap();
+        let table = pass_claims(table, &participants);
+        assert!(matches!(
+            table.phase(),
+            Phase::AwaitingDiscardAfterCall(Seat::South)
+        ));
+        let index = table.index_of(Seat::South, tiles_of("8s")[0]).unwrap();
+        assert!(matches!(
+            table.validate_discard_by(index, &participants[1]),
+            Err(DiscardError::SwapCalling {
+                seat: Seat::South,
+                ..
+            })
+        ));
+        let index = table.index_of(Seat::South, tiles_of("1z")[0]).unwrap();
+        assert!(table.validate_discard_by(index, &participants[1]).is_ok());
+    }
 
-#[derive(Copy, Clone)]
-enum Round {
-    East,
-    South,
-    West,
-    North,
-}
+    #[test]
+    fn malformed_meld_is_refused() {
+        let (table, participants) = discard_to("159m159p159s1234z");
+        assert!(matches!(
+            table.validate_make_meld_by(&chow_of("5s1m9p", "5s"), &participants[1]),
+            Err(ClaimError::MalformedMeld { .. })
+        ));
+        let tiles = tiles_of("5s5m5p");
+        let pong = Meld::Pong {
+            tiles: [tiles[0], tiles[1], tiles[2]],
+            called_tile: tiles[0],
+        };
+        assert!(matches!(
+            table.validate_make_meld_by(&pong, &participants[2]),
+            Err(ClaimError::MalformedMeld { .. })
+        ));
+    }
+
+    #[test]
+    fn meld_leaving_only_swap_calling_tiles_is_refused() {
+        // As if after three calls
+        let (table, participants) = discard_to("678s5s");
+        assert!(matches!(
+            table.validate_make_meld_by(&chow_of("567s", "5s"), &participants[1]),
+            Err(ClaimError::NoDiscardableTileAfterMeld { .. })
+        ));
+
+        let rule = RuleConfig {
+            allows_swap_calling: true,
+            ..RuleConfig::default()
+        };
+        let (table, participants) =
+            discard_to_each(rule, ["678s5s", "369m369p369s5677z", "147m147p147s5566z"]);
+        assert!(table
+            .validate_make_meld_by(&chow_of("567s", "5s"), &participants[1])
+            .is_ok());
+    }
+
+    #[test]
+    fn completion_on_first_draw_is_told() {
+        let dealing_spec = FixedTileDealingSpec {
+            wall_tiles: tiles_of("9m4s"),
+            ..dealer_ready_hand()
+        };
+        let (table, participants) = start_hand(RuleConfig::default(), &dealing_spec);
+        let table = table.draw_tile_by(participants[0].clone()).unwrap();
+        let hand = table.winning_hand_of(Seat::East, None);
+        assert!(hand.is_first_draw);
+        let score = StandardHandJudgementSpec::default().score(&hand, &table.table_info.rule);
+        assert_eq!(score.basic_points, 8000);
+    }
+
+    #[test]
+    fn north_is_extracted_for_a_supplemental_tile() {
+        let dealing_spec = FixedTileDealingSpec {
+            wall_tiles: tiles_of("9m4s"),
+            player_tiles: vec![
+                tiles_of("19m456p789p1134z5s"),
+                tiles_of("19m258p258s1235z"),
+                tiles_of("19m369p369s2567z"),
+            ],
+        };
+        let (table, participants) = start_hand(RuleConfig::three_players(), &dealing_spec);
+        let table = table.draw_tile_by(participants[0].clone()).unwrap();
+        let index = table.index_of(Seat::East, tiles_of("4z")[0]).unwrap();
+        let table = table
+            .extract_bonus_tile_by(index, participants[0].clone())
+            .unwrap();
+        assert!(matches!(table.phase(), Phase::AwaitingDiscard(Seat::East)));
+        let hand = table.hands.get(Seat::East);
+        assert_eq!(hand.bonus_tiles, tiles_of("4z"));
+        assert_eq!(hand.concealed_tiles.len(), 14);
+        assert_eq!(hand.concealed_tiles.last(), tiles_of("4m").last());
+        assert!(table.winning_hand_of(Seat::East, None).is_supplemental_tile);
+    }
+
+    #[test]
+    fn winning_discard_is_not_visible() {
+        let (table, _) = discard_to("123m456p789s1122z");
+        let hand = table.winning_hand_of(Seat::South, Some(Seat::East));
+        assert_eq!(hand.winning_tile, tiles_of("5s")[0]);
+        assert!(hand.visible_tiles.is_empty());
+    }
 
-enum HandResult {
-    ExhaustiveDraw,
+    #[test]
+    fn double_ready_and_ippatsu_are_told() {
+        let assert_yaku = |rule: RuleConfig, has_ippatsu: bool| {
+            let (table, participants) = start_hand(rule, &dealer_ready_hand());
+            let table = table.draw_tile_by(participants[0].clone()).unwrap();
+            let index = table.index_of(Seat::East, tiles_of("7z")[0]).unwrap();
+            let table = table
+                .declare_ready_by(index, participants[0].clone())
+                .unwrap();
+            let table = pass_claims(table, &participants);
+            let table = draw_and_discard_each(table, &participants, "1z2z3z", false);
+            let table = table.draw_tile_by(participants[0].clone()).unwrap();
+
+            let hand = table.winning_hand_of(Seat::East, None);
+            assert!(hand.is_ready_declared_first);
+            assert!(hand.is_right_after_ready);
+            assert!(!hand.is_first_draw);
+            assert_eq!(hand.visible_tiles, tiles_of("7z1z2z3z"));
+            let explanation = ScoreExplanation::of(&hand, &table.table_info.rule).unwrap();
+            let has_yaku = |yaku| explanation.yaku.iter().any(|(y, _)| *y == yaku);
+            assert!(has_yaku(NormalYaku::DoubleRiichi));
+            assert!(!has_yaku(NormalYaku::Riichi));
+            assert_eq!(has_yaku(NormalYaku::Ippatsu), has_ippatsu);
+        };
+        assert_yaku(RuleConfig::default(), true);
+        assert_yaku(
+            RuleConfig {
+                has_ippatsu: false,
+                ..RuleConfig::default()
+            },
+            false,
+        );
+    }
+
+    #[test]
+    fn ready_is_not_declared_without_points_for_the_deposit() {
+        let rule = RuleConfig {
+            initial_point: READY_DEPOSIT - 100,
+            ..RuleConfig::default()
+        };
+        let (table, participants) = start_hand(rule, &dealer_ready_hand());
+        let table = table.draw_tile_by(participants[0].clone()).unwrap();
+        let index = table.index_of(Seat::East, tiles_of("7z")[0]).unwrap();
+        assert!(matches!(
+            table.validate_declare_ready_by(index, &participants[0]),
+            Err(DeclareError::InsufficientPoints { point: 900, .. })
+        ));
+    }
+
+    #[test]
+    fn skipping_self_drawn_completion_is_not_furiten() {
+        let dealing_spec = FixedTileDealingSpec {
+            wall_tiles: tiles_of("9m4s"),
+            ..dealer_ready_hand()
+        };
+        let (table, participants) = start_hand(RuleConfig::default(), &dealing_spec);
+        let table = table.draw_tile_by(participants[0].clone()).unwrap();
+        let index = table.index_of(Seat::East, tiles_of("4s")[0]).unwrap();
+        let table = table
+            .discard_tile_by(index, participants[0].clone())
+            .unwrap();
+        let furiten = table.furiten_of(Seat::East);
+        assert!(!furiten.is_temporary());
+        assert!(!furiten.is_after_ready());
+    }
+
+    #[test]
+    fn hand_declared_ready_is_shown_at_exhaustive_draw() {
+        let dealing_spec = FixedTileDealingSpec {
+            wall_tiles: tiles_of("9999m7z"),
+            ..dealer_ready_hand()
+        };
+        let (table, participants) = start_hand(RuleConfig::default(), &dealing_spec);
+        let table = table.draw_tile_by(participants[0].clone()).unwrap();
+        let index = table.index_of(Seat::East, tiles_of("7z")[0]).unwrap();
+        let table = table
+            .declare_ready_by(index, participants[0].clone())
+            .unwrap();
+        let table = pass_claims(table, &participants);
+        let table = draw_and_discard_each(table, &participants, "9m9m9m9m", false);
+        assert!(matches!(
+            table.phase(),
+            Phase::Ended(HandEnding::ExhaustiveDraw { ready_seats, .. })
+                if *ready_seats == vec![Seat::East]
+        ));
+    }
+
+    #[test]
+    fn nine_terminals_is_declared_in_the_first_turn() {
+        let dealing_spec = FixedTileDealingSpec {
+            wall_tiles: tiles_of("9m5m"),
+            player_tiles: vec![
+                tiles_of("19m19p19s1234z23m5p"),
+                tiles_of("258m258p258s2356z"),
+                tiles_of("369m369p369s2567z"),
+                tiles_of("147m147p147s2567z"),
+            ],
+        };
+        let (table, participants) = start_hand(RuleConfig::default(), &dealing_spec);
+        let table = table.draw_tile_by(participants[0].clone()).unwrap();
+        let table = table
+            .declare_nine_terminals_by(participants[0].clone())
+            .unwrap_or_else(|_| panic!("The hand should have 10 kinds"));
+        assert!(matches!(
+            table.phase(),
+            Phase::Ended(HandEnding::NineTerminals { seat: Seat::East })
+        ));
+
+        let mut rule = RuleConfig::default();
+        rule.abortive_draws.nine_terminals = false;
+        let (table, participants) = start_hand(rule, &dealing_spec);
+        let table = table.draw_tile_by(participants[0].clone()).unwrap();
+        assert!(matches!(
+            table.validate_declare_nine_terminals_by(&participants[0]),
+            Err(DeclareError::NineTerminalsNotAllowed { .. })
+        ));
+    }
+
+    #[test]
+    fn four_winds_ends_the_hand() {
+        let dealing_spec = FixedTileDealingSpec {
+            wall_tiles: tiles_of("9999m1z1z1z1z"),
+            player_tiles: vec![
+                tiles_of("123m456m789p1123s"),
+                tiles_of("258m258p258s2356z"),
+                tiles_of("369m369p369s2567z"),
+                tiles_of("147m147p147s2567z"),
+            ],
+        };
+        let (table, participants) = start_hand(RuleConfig::default(), &dealing_spec);
+        let table = draw_and_discard_each(table, &participants, "1z1z1z1z", false);
+        assert!(matches!(table.phase(), Phase::Ended(HandEnding::FourWinds)));
+    }
+
+    #[test]
+    fn four_riichi_ends_the_hand() {
+        let dealing_spec = FixedTileDealingSpec {
+            wall_tiles: tiles_of("9999m1z7z7z7z"),
+            player_tiles: vec![
+                tiles_of("123m456m789p1123s"),
+                tiles_of("123p456p789s1123m"),
+                tiles_of("123s456s789m1123p"),
+                tiles_of("234m345p456s5566z"),
+            ],
+        };
+        let (table, participants) = start_hand(RuleConfig::default(), &dealing_spec);
+        let table = draw_and_discard_each(table, &participants, "7z7z7z1z", true);
+        assert!(matches!(
+            table.phase(),
+            Phase::Ended(HandEnding::FourRiichi)
+        ));
+        assert_eq!(table.progress.deposits, 4 * READY_DEPOSIT);
+    }
+
+    /// South, West and North are all waiting on 5s with 断么九.
+    const WAITING_ON_FIVE: [&str; 3] = [
+        "234m678p222s88p46s",
+        "345m567p333s77p46s",
+        "678m234p777s22m46s",
+    ];
+
+    /// Each of the seats claims completion on the discard, and the others pass.
+    fn claim_completion_by_each(
+        table: HandPlayingTable<StandardConcept>,
+        participants: &[ParticipantId],
+        seats: &[usize],
+    ) -> HandPlayingTable<StandardConcept> {
+        let table = seats.iter().fold(table, |table, i| {
+            table
+                .claim_completion_by(participants[*i].clone())
+                .unwrap_or_else(|_| panic!("The hand should be completed"))
+        });
+        pass_claims(table, participants)
+    }
+
+    #[test]
+    fn multiple_ron_follows_the_rule() {
+        let (table, participants) = discard_to_each(RuleConfig::default(), WAITING_ON_FIVE);
+        let table = claim_completion_by_each(table, &participants, &[1, 2]);
+        assert!(matches!(
+            table.phase(),
+            Phase::Ended(HandEnding::Completion { winners, discarder: Some(Seat::East) })
+                if *winners == vec![Seat::South, Seat::West]
+        ));
+
+        let rule = RuleConfig {
+            multiple_ron: MultipleRon::HeadBump,
+            abortive_draws: AbortiveDraws {
+                triple_ron: false,
+                ..AbortiveDraws::default()
+            },
+            ..RuleConfig::default()
+        };
+        let (table, participants) = discard_to_each(rule, WAITING_ON_FIVE);
+        // The nearest from the discarder wins, not the first to claim.
+        let table = claim_completion_by_each(table, &participants, &[3, 2]);
+        assert!(matches!(
+            table.phase(),
+            Phase::Ended(HandEnding::Completion { winners, .. }) if *winners == vec![Seat::West]
+        ));
+    }
+
+    #[test]
+    fn triple_ron_is_abortive_if_the_rule_says() {
+        let (table, participants) = discard_to_each(RuleConfig::default(), WAITING_ON_FIVE);
+        let table = claim_completion_by_each(table, &participants, &[1, 2, 3]);
+        assert!(matches!(
+            table.phase(),
+            Phase::Ended(HandEnding::TripleRon { claimants, discarder: Seat::East })
+                if claimants.len() == 3
+        ));
+
+        let mut rule = RuleConfig::default();
+        rule.abortive_draws.triple_ron = false;
+        let (table, participants) = discard_to_each(rule, WAITING_ON_FIVE);
+        let table = claim_completion_by_each(table, &participants, &[1, 2, 3]);
+        assert!(matches!(
+            table.phase(),
+            Phase::Ended(HandEnding::Completion { winners, .. }) if winners.len() == 3
+        ));
+    }
+
+    #[test]
+    fn dealer_rotates_among_three_players() {
+        let players_count = PlayersCount::THREE;
+        let mut progress = Progress::get_initial();
+        let mut dealers = vec![];
+        for _ in 0..3 {
+            dealers.push(progress.dealer());
+            progress = progress.after_completion(false, players_count);
+        }
+        assert_eq!(dealers, vec![Seat::East, Seat::South, Seat::West]);
+        assert_eq!(progress.current_hand, (Round::South, 1));
+        assert_eq!(progress.passed_rounds_count, 1);
+        assert_eq!(progress.dealer(), Seat::East);
+    }
 }
@@ -0,0 +1,282 @@
+use crate::game::def::{FuritenStatus, ParticipantId, Seat};
+use crate::game::table::PhaseKind;
+use std::fmt::Debug;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum JoinError {
+    #[error("{participant} can't join: the table already has {max} participants")]
+    ParticipantsExceeded {
+        participant: ParticipantId,
+        max: usize,
+    },
+    #[error("{0} has already joined the table")]
+    AlreadyJoined(ParticipantId),
+    #[error("{0} has not joined the table")]
+    NotJoined(ParticipantId),
+}
+
+#[derive(Error, Debug)]
+pub enum SeatError {
+    #[error("the seats are not arranged yet")]
+    NotArranged,
+    #[error("{0} is not a participant of the table")]
+    UnknownParticipant(ParticipantId),
+}
+
+#[derive(Error, Debug)]
+pub enum DrawError {
+    #[error("{0} is not seated at the table")]
+    UnknownParticipant(ParticipantId),
+    #[error("{participant} ({seat:?}) can't draw: it is {turn:?}'s turn")]
+    NotParticipantsTurn {
+        participant: ParticipantId,
+        seat: Seat,
+        turn: Seat,
+    },
+    #[error("{participant} ({seat:?}) can't draw while {phase}")]
+    UnexpectedPhase {
+        participant: ParticipantId,
+        seat: Seat,
+        phase: PhaseKind,
+    },
+    #[error("no tile is left in the wall")]
+    WallExhausted,
+}
+
+#[derive(Error, Debug)]
+pub enum DiscardError<T: Debug> {
+    #[error("{0} is not seated at the table")]
+    UnknownParticipant(ParticipantId),
+    #[error("{participant} ({seat:?}) can't discard: it is {turn:?}'s turn")]
+    NotParticipantsTurn {
+        participant: ParticipantId,
+        seat: Seat,
+        turn: Seat,
+    },
+    #[error("{participant} ({seat:?}) can't discard while {phase}")]
+    UnexpectedPhase {
+        participant: ParticipantId,
+        seat: Seat,
+        phase: PhaseKind,
+    },
+    #[error(
+        "{participant} ({seat:?}) has no tile at index {index}: the hand has {hand_size} tiles"
+    )]
+    InvalidTileIndex {
+        participant: ParticipantId,
+        seat: Seat,
+        index: u8,
+        hand_size: usize,
+    },
+    #[error("{participant} ({seat:?}) can't discard {tile:?}: swap-calling is not allowed")]
+    SwapCalling {
+        participant: ParticipantId,
+        seat: Seat,
+        tile: T,
+    },
+}
+
+/// Errors on responding to a discarded tile, i.e. 栄和, ポン, チー or passing.
+#[derive(Error, Debug)]
+pub enum ClaimError<T: Debug> {
+    #[error("{0} is not seated at the table")]
+    UnknownParticipant(ParticipantId),
+    #[error("{participant} ({seat:?}) can't claim while {phase}")]
+    UnexpectedPhase {
+        participant: ParticipantId,
+        seat: Seat,
+        phase: PhaseKind,
+    },
+    #[error("{participant} ({seat:?}) can't claim {tile:?} discarded by themself")]
+    OwnDiscard {
+        participant: ParticipantId,
+        seat: Seat,
+        tile: T,
+    },
+    #[error("{participant} ({seat:?}) has already responded to {tile:?}")]
+    AlreadyResponded {
+        participant: ParticipantId,
+        seat: Seat,
+        tile: T,
+    },
+    #[error("{participant} ({seat:?}) can't win on {tile:?}: the hand is not waiting for it")]
+    NotWaiting {
+        participant: ParticipantId,
+        seat: Seat,
+        tile: T,
+    },
+    #[error(
+        "{participant} ({seat:?}) can't win on {tile:?}: the hand is in furiten ({furiten:?})"
+    )]
+    Furiten {
+        participant: ParticipantId,
+        seat: Seat,
+        tile: T,
+        furiten: FuritenStatus,
+    },
+    /// 役無し, or short of the minimum of the rule, e.g. 8番 of 国標麻将.
+    #[error("{participant} ({seat:?}) can't win on {tile:?}: the hand has no yaku")]
+    NoYaku {
+        participant: ParticipantId,
+        seat: Seat,
+        tile: T,
+    },
+    #[error("{participant} ({seat:?}) can't make a meld with {tile:?} after declaring ready")]
+    ReadyDeclared {
+        participant: ParticipantId,
+        seat: Seat,
+        tile: T,
+    },
+    #[error("{participant} ({seat:?}) can't make the meld: it doesn't use the discarded {tile:?}")]
+    MeldWithoutDiscardedTile {
+        participant: ParticipantId,
+        seat: Seat,
+        tile: T,
+    },
+    #[error("{participant} ({seat:?}) can't make the meld with {tile:?}: the tiles don't make its shape")]
+    MalformedMeld {
+        participant: ParticipantId,
+        seat: Seat,
+        tile: T,
+    },
+    #[error(
+        "{participant} ({seat:?}) can't make a sequence with {tile:?}: not allowed by the rule"
+    )]
+    SequenceNotAllowed {
+        participant: ParticipantId,
+        seat: Seat,
+        tile: T,
+    },
+    #[error("{participant} ({seat:?}) can't make a sequence with {tile:?} discarded by {discarder:?}: only the next player can")]
+    SequenceFromNonAdjacentSeat {
+        participant: ParticipantId,
+        seat: Seat,
+        tile: T,
+        discarder: Seat,
+    },
+    #[error("{participant} ({seat:?}) can't make the meld with {tile:?}: the hand lacks the other tiles")]
+    MissingMeldTiles {
+        participant: ParticipantId,
+        seat: Seat,
+        tile: T,
+    },
+    #[error("{participant} ({seat:?}) can't make the meld with {tile:?}: no tile could be discarded without swap-calling")]
+    NoDiscardableTileAfterMeld {
+        participant: ParticipantId,
+        seat: Seat,
+        tile: T,
+    },
+    #[error("{participant} ({seat:?}) can't make a quad with {tile:?}: not supported yet")]
+    QuadNotSupported {
+        participant: ParticipantId,
+        seat: Seat,
+        tile: T,
+    },
+}
+
+/// Errors on declarations, e.g. 自摸和了, 立直 and 聴牌 at exhaustive draw.
+#[derive(Error, Debug)]
+pub enum DeclareError<T: Debug> {
+    #[error("{0} is not seated at the table")]
+    UnknownParticipant(ParticipantId),
+    #[error("{participant} ({seat:?}) can't declare: it is {turn:?}'s turn")]
+    NotParticipantsTurn {
+        participant: ParticipantId,
+        seat: Seat,
+        turn: Seat,
+    },
+    #[error("{participant} ({seat:?}) can't declare while {phase}")]
+    UnexpectedPhase {
+        participant: ParticipantId,
+        seat: Seat,
+        phase: PhaseKind,
+    },
+    #[error("{participant} ({seat:?}) can't declare completion: the hand is not completed")]
+    NotCompleted {
+        participant: ParticipantId,
+        seat: Seat,
+    },
+    /// 役無し, or short of the minimum of the rule, e.g. 8番 of 国標麻将.
+    #[error("{participant} ({seat:?}) can't declare completion: the hand has no yaku")]
+    NoYaku {
+        participant: ParticipantId,
+        seat: Seat,
+    },
+    #[error(
+        "{participant} ({seat:?}) has no tile at index {index}: the hand has {hand_size} tiles"
+    )]
+    InvalidTileIndex {
+        participant: ParticipantId,
+        seat: Seat,
+        index: u8,
+        hand_size: usize,
+    },
+    #[error("{participant} ({seat:?}) can't declare ready: the rule has no riichi")]
+    ReadyNotAllowed {
+        participant: ParticipantId,
+        seat: Seat,
+    },
+    #[error("{participant} ({seat:?}) has already declared ready")]
+    AlreadyDeclaredReady {
+        participant: ParticipantId,
+        seat: Seat,
+    },
+    #[error("{participant} ({seat:?}) can't declare ready: the hand has called melds")]
+    OpenHand {
+        participant: ParticipantId,
+        seat: Seat,
+    },
+    #[error("{participant} ({seat:?}) can't declare ready: the wall has too few tiles")]
+    WallNearlyExhausted {
+        participant: ParticipantId,
+        seat: Seat,
+    },
+    #[error(
+        "{participant} ({seat:?}) can't declare ready: {point} points are short of the deposit"
+    )]
+    InsufficientPoints {
+        participant: ParticipantId,
+        seat: Seat,
+        point: i32,
+    },
+    #[error("{participant} ({seat:?}) can't declare ready: the hand is not ready after discarding {tile:?}")]
+    NotReadyAfterDiscard {
+        participant: ParticipantId,
+        seat: Seat,
+        tile: T,
+    },
+    #[error("{participant} ({seat:?}) can't reveal the hand: it is not ready")]
+    NotReady {
+        participant: ParticipantId,
+        seat: Seat,
+    },
+    #[error("{participant} ({seat:?}) has already declared at the exhaustive draw")]
+    AlreadyDeclared {
+        participant: ParticipantId,
+        seat: Seat,
+    },
+    #[error("{participant} ({seat:?}) can't extract {tile:?}: not a bonus tile by the rule")]
+    NotBonusTile {
+        participant: ParticipantId,
+        seat: Seat,
+        tile: T,
+    },
+    #[error("{participant} ({seat:?}) can't extract a bonus tile: no supplemental tile is left")]
+    SupplementalTilesExhausted {
+        participant: ParticipantId,
+        seat: Seat,
+    },
+    #[error("{participant} ({seat:?}) can't declare nine terminals: the rule has no such draw")]
+    NineTerminalsNotAllowed {
+        participant: ParticipantId,
+        seat: Seat,
+    },
+    #[error(
+        "{participant} ({seat:?}) can't declare nine terminals: not 9 kinds of terminals and honors in the first turn"
+    )]
+    NotNineTerminals {
+        participant: ParticipantId,
+        seat: Seat,
+    },
+}
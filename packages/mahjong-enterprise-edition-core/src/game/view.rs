@@ -0,0 +1,60 @@
+use crate::game::def::{Concept, FuritenStatus, ParticipantId, Seat, WinningHand};
+use crate::game::ledger::Ledger;
+use crate::game::table::{HandEnding, PhaseKind, Round, TableId};
+
+/// What a participant can see at the table. Others' concealed tiles are hidden.
+pub struct TableView<C: Concept> {
+    pub table_id: TableId,
+    pub seat: Seat,
+    pub phase: PhaseKind,
+    /// The seat whose action is awaited, if only one.
+    pub turn: Option<Seat>,
+    pub round: Round,
+    /// 局. Starts from 1 in each round.
+    pub hand_number: usize,
+    /// 本場
+    pub deals_count: u8,
+    /// 供託 in points
+    pub deposits: i32,
+    pub dealer: Seat,
+    pub wall_tiles_count: usize,
+    pub supplemental_tiles_count: usize,
+    /// ドラ表示牌
+    pub reward_indication_tiles: Vec<C::Tile>,
+    pub concealed_tiles: Vec<C::Tile>,
+    pub discardable_tiles: Vec<C::Tile>,
+    pub furiten: FuritenStatus,
+    /// The tile awaiting claims, with its discarder.
+    pub discarded_tile: Option<(Seat, C::Tile)>,
+    pub seats: Vec<SeatView<C>>,
+    /// How the hand ended, until the next hand starts.
+    pub ending: Option<HandEnding>,
+    /// 点数移動 of the ended hand, applied when the next hand starts.
+    pub ledger: Option<Ledger>,
+    /// The completed hands of the ended hand, in the order of `HandEnding::Completion::winners`.
+    pub winning_hands: Vec<WinningHand<C>>,
+}
+
+pub struct SeatView<C: Concept> {
+    pub seat: Seat,
+    pub participant: ParticipantId,
+    pub point: i32,
+    pub concealed_tiles_count: usize,
+    pub exposed_melds: Vec<C::Meld>,
+    /// Each tile with whether it was called by another player.
+    pub discarded_tiles: Vec<(C::Tile, bool)>,
+    /// 抜きドラ
+    pub bonus_tiles: Vec<C::Tile>,
+    pub is_ready_declared: bool,
+    /// 包. The seat who fed the meld deciding a yakuman.
+    pub liable_seat: Option<Seat>,
+}
+
+/// The final result of a participant.
+pub struct Standing {
+    pub participant: ParticipantId,
+    pub seat: Seat,
+    pub point: i32,
+    /// The point after 返し点, 岡 and ウマ.
+    pub score: i32,
+}
@@ -0,0 +1,10 @@
+//! 香港麻雀, the old style with faan, on the tiles and melds of [`crate::mcr`].
+//!
+//! Play with [`crate::game::RulePreset::HongKong`], [`crate::mcr::McrTileDealingSpec`] and
+//! [`HkHandJudgementSpec`]. Flowers are extracted as bonus tiles.
+
+mod faan;
+mod hand_judgement;
+
+pub use faan::{evaluate, Faan, FaanEvaluation};
+pub use hand_judgement::HkHandJudgementSpec;
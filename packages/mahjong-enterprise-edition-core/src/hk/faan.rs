@@ -0,0 +1,431 @@
+use crate::game::{MeldConcept, WinningHand};
+use crate::mcr::{
+    decompose, is_nine_gates, kinds_of, number_of, wind_of_round, wind_of_seat, Flower, McrConcept,
+    McrDecomposition, McrHandForm, Tile,
+};
+use crate::rule::{GroupShape, TileKind, Wind, WinningPosition, TILE_KINDS_COUNT};
+use itertools::Itertools;
+use serde::Serialize;
+
+/// 番 of 香港麻雀. 搶槓 is not counted, as no 加槓 is made at the table.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Faan {
+    // 滿糊
+    /// 十三么
+    ThirteenOrphans,
+    /// 大三元
+    BigThreeDragons,
+    /// 大四喜
+    BigFourWinds,
+    /// 小四喜
+    LittleFourWinds,
+    /// 字一色
+    AllHonors,
+    /// 清么九
+    AllTerminals,
+    /// 坎坎糊
+    FourConcealedPungs,
+    /// 九子連環
+    NineGates,
+    /// 十八羅漢
+    FourKongs,
+    /// 天糊
+    HeavenlyHand,
+    /// 地糊
+    EarthlyHand,
+    // 番
+    /// 清一色
+    FullFlush,
+    /// 小三元
+    LittleThreeDragons,
+    /// 混一色
+    HalfFlush,
+    /// 對對糊
+    AllPungs,
+    /// 花么九
+    AllTerminalsAndHonors,
+    /// 平糊
+    AllChows,
+    /// 門前清
+    ConcealedHand,
+    /// 自摸
+    SelfDrawn,
+    /// 三元牌
+    DragonPung,
+    /// 門風
+    SeatWind,
+    /// 圈風
+    PrevalentWind,
+    /// 海底撈月
+    LastTile,
+    /// 槓上開花
+    OutWithReplacementTile,
+    /// 無花
+    NoFlowers,
+    /// 正花, each flower of the own seat.
+    SeatFlower,
+    /// 一台花, each set of 梅蘭菊竹 or 春夏秋冬.
+    FlowerSet,
+}
+
+impl Faan {
+    /// `None` for a limit hand (滿糊), worth `HongKongScoring::max_faan`.
+    pub fn faan(&self) -> Option<u8> {
+        use Faan::*;
+
+        match self {
+            ThirteenOrphans | BigThreeDragons | BigFourWinds | LittleFourWinds | AllHonors
+            | AllTerminals | FourConcealedPungs | NineGates | FourKongs | HeavenlyHand
+            | EarthlyHand => None,
+            FullFlush => Some(7),
+            LittleThreeDragons => Some(5),
+            HalfFlush | AllPungs => Some(3),
+            FlowerSet => Some(2),
+            AllTerminalsAndHonors
+            | AllChows
+            | ConcealedHand
+            | SelfDrawn
+            | DragonPung
+            | SeatWind
+            | PrevalentWind
+            | LastTile
+            | OutWithReplacementTile
+            | NoFlowers
+            | SeatFlower => Some(1),
+        }
+    }
+}
+
+/// The faan of a completed hand, in the decomposition worth the most.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+pub struct FaanEvaluation {
+    pub decomposition: McrDecomposition,
+    /// Repeated for each time counted, e.g. two `DragonPung`s.
+    pub faans: Vec<Faan>,
+}
+
+impl FaanEvaluation {
+    /// Capped at `max_faan` (爆棚).
+    pub fn total(&self, max_faan: u8) -> u8 {
+        self.faans
+            .iter()
+            .map(|f| f.faan().unwrap_or(max_faan))
+            .fold(0u8, u8::saturating_add)
+            .min(max_faan)
+    }
+}
+
+/// Evaluates the faan of a winning hand. `None` if the hand is not completed in a form of
+/// 香港麻雀, i.e. four groups and a pair or 十三么.
+pub fn evaluate(hand: &WinningHand<McrConcept>, max_faan: u8) -> Option<FaanEvaluation> {
+    let seat_wind = wind_of_seat(hand.seat, hand.dealer);
+    let mut concealed_histogram = [0; TILE_KINDS_COUNT];
+    for kind in kinds_of(&hand.concealed_tiles) {
+        concealed_histogram[kind.index()] += 1;
+    }
+    let context = FaanContext {
+        seat_wind,
+        prevalent_wind: wind_of_round(hand.round),
+        is_self_drawn: hand.discarder.is_none(),
+        is_dealer: hand.seat == hand.dealer,
+        is_first_draw: hand.is_first_draw,
+        is_concealed: hand
+            .exposed_melds
+            .iter()
+            .all(|m| m.0.called_tile().is_none()),
+        is_nine_gates: is_nine_gates(&concealed_histogram),
+    };
+    let flowers = hand
+        .bonus_tiles
+        .iter()
+        .filter_map(|tile| match tile {
+            Tile::Flower(flower) => Some(*flower),
+            Tile::Normal(_) => None,
+        })
+        .collect_vec();
+    let mut extra_faans = flower_faans(&flowers, seat_wind);
+    if hand.is_last_tile {
+        extra_faans.push(Faan::LastTile);
+    }
+    if hand.is_supplemental_tile {
+        extra_faans.push(Faan::OutWithReplacementTile);
+    }
+
+    decompose(
+        &hand.concealed_tiles,
+        &hand.exposed_melds,
+        hand.winning_tile,
+    )
+    .into_iter()
+    .filter(|d| is_hong_kong_form(&d.form))
+    .map(|decomposition| {
+        let faans = faans_of(&decomposition, &context);
+        let faans = if faans.iter().any(|f| f.faan().is_none()) {
+            faans
+        } else {
+            faans.into_iter().chain(extra_faans.clone()).collect()
+        };
+        FaanEvaluation {
+            decomposition,
+            faans,
+        }
+    })
+    .max_by_key(|evaluation| evaluation.total(max_faan))
+}
+
+pub(crate) fn is_hong_kong_form(form: &McrHandForm) -> bool {
+    matches!(
+        form,
+        McrHandForm::Normal { .. } | McrHandForm::ThirteenOrphans { .. }
+    )
+}
+
+struct FaanContext {
+    seat_wind: Wind,
+    prevalent_wind: Wind,
+    is_self_drawn: bool,
+    is_dealer: bool,
+    /// Before the own first discard without calls before.
+    is_first_draw: bool,
+    /// No melds called, though 暗槓 may be exposed.
+    is_concealed: bool,
+    /// Of the concealed tiles excluding the winning one.
+    is_nine_gates: bool,
+}
+
+/// A limit hand alone, or the other faan except flowers, 海底撈月 and 槓上開花.
+fn faans_of(decomposition: &McrDecomposition, context: &FaanContext) -> Vec<Faan> {
+    let (pair, groups) = match &decomposition.form {
+        McrHandForm::Normal { pair, groups } => (*pair, groups),
+        _ => return vec![Faan::ThirteenOrphans],
+    };
+    let kinds = decomposition.form.kinds();
+    let pungs = groups
+        .iter()
+        .filter(|g| g.shape != GroupShape::Sequence)
+        .collect_vec();
+    let is_dragon = |kind: &TileKind| matches!(kind, TileKind::Dragon(_));
+    let is_wind = |kind: &TileKind| matches!(kind, TileKind::Wind(_));
+    let dragon_pungs = pungs.iter().filter(|g| is_dragon(&g.kind)).count();
+    let wind_pungs = pungs.iter().filter(|g| is_wind(&g.kind)).count();
+    // A triplet completed by a discard is not concealed.
+    let concealed_pungs = pungs
+        .iter()
+        .filter(|g| g.is_concealed)
+        .filter(|g| {
+            context.is_self_drawn
+                || g.shape == GroupShape::Quad
+                || !matches!(
+                    decomposition.winning_position,
+                    Some(WinningPosition::Group(i)) if groups[i] == ***g
+                )
+        })
+        .count();
+    let suits = kinds
+        .iter()
+        .filter_map(|k| number_of(*k))
+        .map(|(suit, _)| suit)
+        .unique()
+        .count();
+    let has_honors = kinds.iter().any(TileKind::is_honor);
+
+    let mut limits = vec![];
+    if dragon_pungs == 3 {
+        limits.push(Faan::BigThreeDragons);
+    }
+    match (wind_pungs, is_wind(&pair)) {
+        (4, _) => limits.push(Faan::BigFourWinds),
+        (3, true) => limits.push(Faan::LittleFourWinds),
+        _ => {}
+    }
+    if kinds.iter().all(TileKind::is_honor) {
+        limits.push(Faan::AllHonors);
+    }
+    if kinds.iter().all(TileKind::is_terminal) {
+        limits.push(Faan::AllTerminals);
+    }
+    if concealed_pungs == 4 {
+        limits.push(Faan::FourConcealedPungs);
+    }
+    if context.is_concealed && context.is_nine_gates {
+        limits.push(Faan::NineGates);
+    }
+    if pungs.iter().filter(|g| g.shape == GroupShape::Quad).count() == 4 {
+        limits.push(Faan::FourKongs);
+    }
+    if context.is_first_draw {
+        if !context.is_dealer {
+            limits.push(Faan::EarthlyHand);
+        } else if context.is_self_drawn {
+            limits.push(Faan::HeavenlyHand);
+        }
+    }
+    if !limits.is_empty() {
+        return limits;
+    }
+
+    let mut faans = vec![];
+    match (suits, has_honors) {
+        (1, false) => faans.push(Faan::FullFlush),
+        (1, true) => faans.push(Faan::HalfFlush),
+        _ => {}
+    }
+    if dragon_pungs == 2 && is_dragon(&pair) {
+        faans.push(Faan::LittleThreeDragons);
+    }
+    if pungs.len() == 4 {
+        faans.push(Faan::AllPungs);
+    }
+    if kinds.iter().all(TileKind::is_terminal_or_honor) {
+        faans.push(Faan::AllTerminalsAndHonors);
+    }
+    let is_valued_pair = match pair {
+        TileKind::Dragon(_) => true,
+        TileKind::Wind(wind) => wind == context.seat_wind || wind == context.prevalent_wind,
+        TileKind::Suited(_, _) => false,
+    };
+    if pungs.is_empty() && !is_valued_pair {
+        faans.push(Faan::AllChows);
+    }
+    if context.is_concealed {
+        faans.push(Faan::ConcealedHand);
+    }
+    if context.is_self_drawn {
+        faans.push(Faan::SelfDrawn);
+    }
+    for pung in pungs.iter() {
+        match pung.kind {
+            TileKind::Dragon(_) => faans.push(Faan::DragonPung),
+            TileKind::Wind(wind) => {
+                if wind == context.seat_wind {
+                    faans.push(Faan::SeatWind);
+                }
+                if wind == context.prevalent_wind {
+                    faans.push(Faan::PrevalentWind);
+                }
+            }
+            TileKind::Suited(_, _) => {}
+        }
+    }
+    faans
+}
+
+/// 正花 of the own seat, unless in 一台花.
+fn flower_faans(flowers: &[Flower], seat_wind: Wind) -> Vec<Faan> {
+    if flowers.is_empty() {
+        return vec![Faan::NoFlowers];
+    }
+    let mut faans = vec![];
+    for is_season in [false, true].iter() {
+        let set = flowers
+            .iter()
+            .filter(|f| f.is_season() == *is_season)
+            .collect_vec();
+        if set.len() == 4 {
+            faans.push(Faan::FlowerSet);
+        } else if set.iter().any(|f| f.wind() == seat_wind) {
+            faans.push(Faan::SeatFlower);
+        }
+    }
+    faans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Seat;
+    use crate::mcr::winning_hand_of;
+
+    fn faans_of(hand: &WinningHand<McrConcept>) -> Vec<Faan> {
+        evaluate(hand, 10).unwrap().faans
+    }
+
+    fn with_flowers(flowers: &[Flower]) -> WinningHand<McrConcept> {
+        WinningHand {
+            bonus_tiles: flowers.iter().map(|f| Tile::Flower(*f)).collect(),
+            ..winning_hand_of("123m456m789p234s5s", "5s", None)
+        }
+    }
+
+    #[test]
+    fn flowers_count_by_seat_or_by_set() {
+        use Faan::*;
+
+        assert_eq!(
+            faans_of(&with_flowers(&[])),
+            vec![AllChows, ConcealedHand, SelfDrawn, NoFlowers]
+        );
+        // 蘭 and 夏 belong to South.
+        assert_eq!(
+            faans_of(&with_flowers(&[
+                Flower::Orchid,
+                Flower::Summer,
+                Flower::Winter
+            ])),
+            vec![AllChows, ConcealedHand, SelfDrawn, SeatFlower, SeatFlower]
+        );
+        assert_eq!(
+            faans_of(&with_flowers(&[
+                Flower::Plum,
+                Flower::Orchid,
+                Flower::Chrysanthemum,
+                Flower::Bamboo,
+            ])),
+            vec![AllChows, ConcealedHand, SelfDrawn, FlowerSet]
+        );
+    }
+
+    #[test]
+    fn total_is_capped_at_max_faan() {
+        // 清一色・平糊・門前清・無花
+        let hand = winning_hand_of("1122334455667m", "7m", Some(Seat::West));
+        let evaluation = evaluate(&hand, 10).unwrap();
+        assert!(evaluation.faans.contains(&Faan::FullFlush));
+        assert_eq!(evaluation.total(13), 10);
+        assert_eq!(evaluation.total(8), 8);
+    }
+
+    #[test]
+    fn limit_hand_is_worth_max_faan() {
+        let hand = winning_hand_of("111m999m555z666z7z", "7z", Some(Seat::West));
+        let evaluation = evaluate(&hand, 10).unwrap();
+        assert_eq!(evaluation.faans, vec![Faan::FourConcealedPungs]);
+        assert_eq!(evaluation.total(10), 10);
+        assert_eq!(evaluation.total(13), 13);
+    }
+
+    #[test]
+    fn completion_on_first_draw_is_a_limit_hand() {
+        let hand = WinningHand {
+            seat: Seat::East,
+            is_first_draw: true,
+            ..winning_hand_of("123m456m789p234s5s", "5s", None)
+        };
+        assert_eq!(faans_of(&hand), vec![Faan::HeavenlyHand]);
+
+        let hand = WinningHand {
+            is_first_draw: true,
+            ..winning_hand_of("123m456m789p234s5s", "5s", Some(Seat::East))
+        };
+        assert_eq!(faans_of(&hand), vec![Faan::EarthlyHand]);
+    }
+
+    #[test]
+    fn supplemental_tile_is_an_extra_faan() {
+        let hand = WinningHand {
+            is_supplemental_tile: true,
+            ..winning_hand_of("123m456m789p234s5s", "5s", None)
+        };
+        assert_eq!(
+            faans_of(&hand),
+            vec![
+                Faan::AllChows,
+                Faan::ConcealedHand,
+                Faan::SelfDrawn,
+                Faan::NoFlowers,
+                Faan::OutWithReplacementTile
+            ]
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use crate::game::{HandJudgementSpec, HandScore, RuleConfig, WinningHand};
+use crate::hk::faan::{evaluate, is_hong_kong_form};
+use crate::mcr::{decompose, McrConcept, Meld, Tile};
+use crate::rule::TileKind;
+
+pub struct HkHandJudgementSpec;
+
+impl HandJudgementSpec<McrConcept> for HkHandJudgementSpec {
+    fn waiting_tiles(&self, concealed_tiles: &[Tile], exposed_melds: &[Meld]) -> Vec<Tile> {
+        TileKind::all()
+            .map(Tile::Normal)
+            .filter(|tile| {
+                decompose(concealed_tiles, exposed_melds, *tile)
+                    .iter()
+                    .any(|d| is_hong_kong_form(&d.form))
+            })
+            .collect()
+    }
+
+    fn is_terminal_or_honor(&self, tile: Tile) -> bool {
+        tile.kind().is_some_and(|kind| kind.is_terminal_or_honor())
+    }
+
+    /// 補花
+    fn is_bonus_tile(&self, tile: Tile) -> bool {
+        tile.is_flower()
+    }
+
+    /// 起糊, reaching `rule.hong_kong.min_faan`. Otherwise 詐糊.
+    fn is_scorable(&self, hand: &WinningHand<McrConcept>, rule: &RuleConfig) -> bool {
+        let scoring = &rule.hong_kong;
+        evaluate(hand, scoring.max_faan)
+            .is_some_and(|evaluation| evaluation.total(scoring.max_faan) >= scoring.min_faan)
+    }
+
+    /// The faan in `basic_points` up to `rule.hong_kong.max_faan`, zero below the minimum (詐糊).
+    fn score(&self, hand: &WinningHand<McrConcept>, rule: &RuleConfig) -> HandScore {
+        let scoring = &rule.hong_kong;
+        let basic_points = match evaluate(hand, scoring.max_faan) {
+            Some(evaluation) => {
+                let faan = evaluation.total(scoring.max_faan);
+                if faan >= scoring.min_faan {
+                    faan as i32
+                } else {
+                    0
+                }
+            }
+            None => 0,
+        };
+        HandScore {
+            basic_points,
+            liable_basic_points: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{RulePreset, Seat};
+    use crate::mcr::{winning_hand_of, Flower};
+
+    #[test]
+    fn hand_below_min_faan_is_not_scorable() {
+        let mut rule = RulePreset::HongKong.rule();
+        // 平糊・門前清, without 無花 for a flower of West
+        let mut hand = winning_hand_of("123m456m789p234s5s", "5s", Some(Seat::West));
+        hand.bonus_tiles.push(Tile::Flower(Flower::Autumn));
+        assert!(!HkHandJudgementSpec.is_scorable(&hand, &rule));
+        assert_eq!(HkHandJudgementSpec.score(&hand, &rule).basic_points, 0);
+
+        rule.hong_kong.min_faan = 1;
+        assert!(HkHandJudgementSpec.is_scorable(&hand, &rule));
+        assert_eq!(HkHandJudgementSpec.score(&hand, &rule).basic_points, 2);
+    }
+
+    #[test]
+    fn limit_hand_is_capped_at_max_faan() {
+        let rule = RulePreset::HongKong.rule();
+        // 十三么
+        let hand = winning_hand_of("19m19p19s1234567z", "1z", None);
+        assert!(HkHandJudgementSpec.is_scorable(&hand, &rule));
+        assert_eq!(
+            HkHandJudgementSpec.score(&hand, &rule).basic_points,
+            rule.hong_kong.max_faan as i32
+        );
+    }
+}
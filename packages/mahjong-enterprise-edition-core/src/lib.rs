@@ -1,3 +1,43 @@
-mod game;
+//! A mahjong game engine.
+//!
+//! [`game::Table`] drives a game with the specs of a rule, e.g. the standard ones in [`rule`] or
+//! those of 国標麻将 in [`mcr`] and 香港麻雀 in [`hk`].
+//!
+//! ```
+//! use mahjong_enterprise_edition_core::game::{
+//!     Action, ParticipantId, RuleConfig, Table, TableStatus,
+//! };
+//! use mahjong_enterprise_edition_core::rule::{
+//!     RandomSeatingSpec, StandardConcept, StandardHandJudgementSpec, StandardTileDealingSpec,
+//! };
+//! use std::sync::Arc;
+//!
+//! let mut table = Table::<StandardConcept>::new(
+//!     RuleConfig::default(),
+//!     Box::new(RandomSeatingSpec),
+//!     Box::new(StandardTileDealingSpec),
+//!     Arc::new(StandardHandJudgementSpec::default()),
+//! )
+//! .unwrap();
+//! let participants: Vec<_> = (0..4).map(|_| ParticipantId::generate()).collect();
+//! for participant in participants.iter() {
+//!     table.join(participant.clone()).unwrap();
+//! }
+//! table.arrange_seats().unwrap();
+//! table.start().unwrap();
+//!
+//! // The dealer has drawn the 14th tile.
+//! let dealer = participants
+//!     .iter()
+//!     .find(|p| table.view_for(p).map_or(false, |v| v.turn == Some(v.seat)))
+//!     .unwrap();
+//! let tile = table.view_for(dealer).unwrap().concealed_tiles[0];
+//! table.submit_action(dealer, Action::Discard(tile)).unwrap();
+//! assert!(matches!(table.status(), TableStatus::Playing(_)));
+//! ```
+
+pub mod game;
+pub mod hk;
+pub mod mcr;
+pub mod rule;
 mod tactics;
-mod rule;
\ No newline at end of file
@@ -0,0 +1,68 @@
+//! 国標麻将 (Mahjong Competition Rules), with the table flow of [`crate::game`].
+//!
+//! Play with [`crate::game::RulePreset::ChineseOfficial`], [`McrTileDealingSpec`] and
+//! [`McrHandJudgementSpec`]. Flowers are extracted as bonus tiles.
+
+use crate::game::Concept;
+#[cfg(test)]
+use crate::game::{Round, Seat, WinningHand};
+
+mod decomposition;
+mod fan;
+mod hand_judgement;
+mod meld;
+mod tile;
+mod tile_dealing;
+
+pub use decomposition::{decompose, McrDecomposition, McrHandForm};
+pub use fan::{evaluate, Fan, FanEvaluation, MIN_FAN};
+pub use hand_judgement::McrHandJudgementSpec;
+pub use meld::Meld;
+pub use tile::{Flower, Tile};
+pub use tile_dealing::McrTileDealingSpec;
+
+pub(crate) use fan::{is_nine_gates, number_of, wind_of_round, wind_of_seat};
+pub(crate) use tile::kinds_of;
+
+/// Tiles and melds of 国標麻将.
+pub struct McrConcept;
+
+impl Concept for McrConcept {
+    type Tile = Tile;
+    type Meld = Meld;
+}
+
+/// South's hand in the East round, dealt by East, in the notation of `rule::tiles_of`.
+#[cfg(test)]
+pub(crate) fn winning_hand_of(
+    concealed_tiles: &str,
+    winning_tile: &str,
+    discarder: Option<Seat>,
+) -> WinningHand<McrConcept> {
+    let tiles_of = |notation| {
+        crate::rule::tiles_of(notation)
+            .iter()
+            .map(|t| Tile::Normal(t.kind()))
+            .collect::<Vec<_>>()
+    };
+    WinningHand {
+        concealed_tiles: tiles_of(concealed_tiles),
+        exposed_melds: vec![],
+        winning_tile: tiles_of(winning_tile)[0],
+        bonus_tiles: vec![],
+        seat: Seat::South,
+        dealer: Seat::East,
+        round: Round::East,
+        discarder,
+        is_ready_declared: false,
+        is_ready_declared_first: false,
+        is_right_after_ready: false,
+        is_last_tile: false,
+        is_supplemental_tile: false,
+        is_first_draw: false,
+        visible_tiles: vec![],
+        reward_indication_tiles: vec![],
+        hidden_reward_indication_tiles: vec![],
+        liable_seat: None,
+    }
+}
@@ -0,0 +1,284 @@
+use crate::mcr::meld::Meld;
+use crate::mcr::tile::Tile;
+use crate::rule::{
+    normal_forms, thirteen_orphans, Group, GroupShape, Histogram, Suit, TileKind, Wait,
+    WinningPosition, TILE_KINDS_COUNT,
+};
+use itertools::Itertools;
+use serde::Serialize;
+
+/// An interpretation of a completed hand of 国標麻将.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+pub struct McrDecomposition {
+    pub form: McrHandForm,
+    pub winning_kind: TileKind,
+    /// Where the winning tile is, unless in knitted tiles or 十三幺.
+    pub winning_position: Option<WinningPosition>,
+    pub wait: Option<Wait>,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum McrHandForm {
+    /// 4面子1雀頭. The groups include the exposed melds, in the order of concealed ones first.
+    Normal { pair: TileKind, groups: Vec<Group> },
+    /// 组合龙 in place of three groups. The suits are of 147, 258 and 369 in order.
+    KnittedStraight {
+        suits: [Suit; 3],
+        pair: TileKind,
+        groups: Vec<Group>,
+    },
+    /// 七对, where four of a kind make two pairs.
+    SevenPairs { pairs: Vec<TileKind> },
+    /// 十三幺. The pair is the kind held twice.
+    ThirteenOrphans { pair: TileKind },
+    /// 全不靠, 14 different kinds of honors and knitted tiles.
+    HonorsAndKnitted { kinds: Vec<TileKind> },
+}
+
+impl McrHandForm {
+    /// Each tile's kind in the form.
+    pub fn kinds(&self) -> Vec<TileKind> {
+        match self {
+            McrHandForm::Normal { pair, groups } => groups
+                .iter()
+                .flat_map(Group::kinds)
+                .chain(vec![*pair; 2])
+                .collect(),
+            McrHandForm::KnittedStraight {
+                suits,
+                pair,
+                groups,
+            } => knitted_kinds(suits)
+                .into_iter()
+                .chain(groups.iter().flat_map(Group::kinds))
+                .chain(vec![*pair; 2])
+                .collect(),
+            McrHandForm::SevenPairs { pairs } => pairs.iter().flat_map(|k| vec![*k; 2]).collect(),
+            McrHandForm::ThirteenOrphans { pair } => TileKind::all()
+                .filter(TileKind::is_terminal_or_honor)
+                .chain(Some(*pair))
+                .collect(),
+            McrHandForm::HonorsAndKnitted { kinds } => kinds.clone(),
+        }
+    }
+}
+
+/// Every interpretation of the hand completed by `winning_tile`. Empty if the hand is not
+/// completed, or holds a flower.
+///
+/// ```
+/// use mahjong_enterprise_edition_core::mcr::{decompose, McrHandForm, Tile};
+/// use mahjong_enterprise_edition_core::rule::{Suit, TileKind, Wind};
+///
+/// // 组合龙 with a triplet of East and a pair of 9 bamboos
+/// let tiles = vec![
+///     TileKind::Suited(Suit::Character, 1),
+///     TileKind::Suited(Suit::Character, 4),
+///     TileKind::Suited(Suit::Character, 7),
+///     TileKind::Suited(Suit::Circle, 2),
+///     TileKind::Suited(Suit::Circle, 5),
+///     TileKind::Suited(Suit::Circle, 8),
+///     TileKind::Suited(Suit::Bamboo, 3),
+///     TileKind::Suited(Suit::Bamboo, 6),
+///     TileKind::Suited(Suit::Bamboo, 9),
+///     TileKind::Suited(Suit::Bamboo, 9),
+///     TileKind::Wind(Wind::East),
+///     TileKind::Wind(Wind::East),
+///     TileKind::Wind(Wind::East),
+/// ]
+/// .into_iter()
+/// .map(Tile::Normal)
+/// .collect::<Vec<_>>();
+/// let winning_tile = Tile::Normal(TileKind::Suited(Suit::Bamboo, 9));
+/// let decompositions = decompose(&tiles, &[], winning_tile);
+/// // The winning tile is either in the knitted tiles or the pair.
+/// assert_eq!(decompositions.len(), 2);
+/// assert!(decompositions
+///     .iter()
+///     .all(|d| matches!(d.form, McrHandForm::KnittedStraight { .. })));
+/// ```
+pub fn decompose(
+    concealed_tiles: &[Tile],
+    exposed_melds: &[Meld],
+    winning_tile: Tile,
+) -> Vec<McrDecomposition> {
+    let winning_kind = match winning_tile.kind() {
+        Some(kind) => kind,
+        None => return vec![],
+    };
+    if concealed_tiles.iter().any(Tile::is_flower) {
+        return vec![];
+    }
+    let mut histogram = [0; TILE_KINDS_COUNT];
+    for kind in concealed_tiles.iter().filter_map(Tile::kind) {
+        histogram[kind.index()] += 1;
+    }
+    histogram[winning_kind.index()] += 1;
+    let exposed_groups = exposed_melds
+        .iter()
+        .map(|meld| Group::from_meld(&meld.0))
+        .collect_vec();
+    let tiles_count = histogram.iter().map(|n| *n as usize).sum::<usize>();
+
+    let mut decompositions = vec![];
+
+    if exposed_groups.len() <= GROUPS_COUNT
+        && tiles_count == PAIR_SIZE + GROUP_SIZE * (GROUPS_COUNT - exposed_groups.len())
+    {
+        for (pair, concealed_groups) in normal_forms(&histogram) {
+            let groups = concealed_groups
+                .into_iter()
+                .chain(exposed_groups.iter().copied())
+                .collect_vec();
+            for (winning_position, wait) in positions_of(pair, &groups, winning_kind) {
+                decompositions.push(McrDecomposition {
+                    form: McrHandForm::Normal {
+                        pair,
+                        groups: groups.clone(),
+                    },
+                    winning_kind,
+                    winning_position: Some(winning_position),
+                    wait: Some(wait),
+                });
+            }
+        }
+    }
+
+    let rest_groups_count = GROUPS_COUNT - KNITTED_GROUPS_COUNT;
+    if exposed_groups.len() <= rest_groups_count
+        && tiles_count
+            == KNITTED_SIZE + PAIR_SIZE + GROUP_SIZE * (rest_groups_count - exposed_groups.len())
+    {
+        for suits in KNITTED_SUITS.iter() {
+            let knitted = knitted_kinds(suits);
+            if knitted.iter().any(|k| histogram[k.index()] == 0) {
+                continue;
+            }
+            let mut rest = histogram;
+            for kind in knitted.iter() {
+                rest[kind.index()] -= 1;
+            }
+            for (pair, concealed_groups) in normal_forms(&rest) {
+                let groups = concealed_groups
+                    .into_iter()
+                    .chain(exposed_groups.iter().copied())
+                    .collect_vec();
+                let form = McrHandForm::KnittedStraight {
+                    suits: *suits,
+                    pair,
+                    groups: groups.clone(),
+                };
+                if knitted.contains(&winning_kind) {
+                    decompositions.push(McrDecomposition {
+                        form: form.clone(),
+                        winning_kind,
+                        winning_position: None,
+                        wait: None,
+                    });
+                }
+                for (winning_position, wait) in positions_of(pair, &groups, winning_kind) {
+                    decompositions.push(McrDecomposition {
+                        form: form.clone(),
+                        winning_kind,
+                        winning_position: Some(winning_position),
+                        wait: Some(wait),
+                    });
+                }
+            }
+        }
+    }
+
+    if exposed_melds.is_empty() && tiles_count == FULL_HAND_SIZE {
+        if histogram.iter().all(|n| n % 2 == 0) {
+            let pairs = TileKind::all()
+                .flat_map(|kind| vec![kind; histogram[kind.index()] as usize / 2])
+                .collect();
+            decompositions.push(McrDecomposition {
+                form: McrHandForm::SevenPairs { pairs },
+                winning_kind,
+                winning_position: Some(WinningPosition::Pair(winning_kind)),
+                wait: Some(Wait::Single),
+            });
+        }
+        if let Some(pair) = thirteen_orphans(&histogram) {
+            decompositions.push(McrDecomposition {
+                form: McrHandForm::ThirteenOrphans { pair },
+                winning_kind,
+                winning_position: None,
+                wait: None,
+            });
+        }
+        if let Some(kinds) = honors_and_knitted(&histogram) {
+            decompositions.push(McrDecomposition {
+                form: McrHandForm::HonorsAndKnitted { kinds },
+                winning_kind,
+                winning_position: None,
+                wait: None,
+            });
+        }
+    }
+
+    decompositions
+}
+
+const GROUPS_COUNT: usize = 4;
+const GROUP_SIZE: usize = 3;
+const PAIR_SIZE: usize = 2;
+const FULL_HAND_SIZE: usize = 14;
+/// 组合龙 takes the place of three groups.
+const KNITTED_GROUPS_COUNT: usize = 3;
+const KNITTED_SIZE: usize = 9;
+
+/// Permutations of the suits for 147, 258 and 369.
+const KNITTED_SUITS: [[Suit; 3]; 6] = [
+    [Suit::Character, Suit::Circle, Suit::Bamboo],
+    [Suit::Character, Suit::Bamboo, Suit::Circle],
+    [Suit::Circle, Suit::Character, Suit::Bamboo],
+    [Suit::Circle, Suit::Bamboo, Suit::Character],
+    [Suit::Bamboo, Suit::Character, Suit::Circle],
+    [Suit::Bamboo, Suit::Circle, Suit::Character],
+];
+
+fn knitted_kinds(suits: &[Suit; 3]) -> Vec<TileKind> {
+    suits
+        .iter()
+        .enumerate()
+        .flat_map(|(i, suit)| (0..3).map(move |j| TileKind::Suited(*suit, (i + 1 + j * 3) as u8)))
+        .collect()
+}
+
+/// Where the winning tile can be, as `rule::decompose` does.
+fn positions_of(
+    pair: TileKind,
+    groups: &[Group],
+    winning_kind: TileKind,
+) -> Vec<(WinningPosition, Wait)> {
+    let mut positions = groups
+        .iter()
+        .enumerate()
+        .filter(|(_, g)| g.is_concealed && g.shape != GroupShape::Quad)
+        .filter(|(_, g)| g.contains(winning_kind))
+        .unique_by(|(_, g)| **g)
+        .map(|(i, g)| (WinningPosition::Group(i), g.wait_on(winning_kind)))
+        .collect_vec();
+    if pair == winning_kind {
+        positions.push((WinningPosition::Pair(pair), Wait::Single));
+    }
+    positions
+}
+
+/// 全不靠
+fn honors_and_knitted(histogram: &Histogram) -> Option<Vec<TileKind>> {
+    if histogram.iter().any(|n| *n > 1) {
+        return None;
+    }
+    let kinds = TileKind::all()
+        .filter(|k| histogram[k.index()] == 1)
+        .collect_vec();
+    let suited = kinds.iter().filter(|k| !k.is_honor()).collect_vec();
+    KNITTED_SUITS
+        .iter()
+        .any(|suits| suited.iter().all(|k| knitted_kinds(suits).contains(k)))
+        .then_some(kinds)
+}
@@ -0,0 +1,1059 @@
+use crate::game::{MeldConcept, Round, Seat, WinningHand};
+use crate::mcr::decomposition::{decompose, McrDecomposition, McrHandForm};
+use crate::mcr::tile::{kinds_of, Tile};
+use crate::mcr::McrConcept;
+use crate::rule::{
+    Dragon, Group, GroupShape, Histogram, Suit, TileKind, Wait, Wind, WinningPosition,
+    TILE_KINDS_COUNT,
+};
+use itertools::Itertools;
+use serde::Serialize;
+
+/// 起和番. Flowers don't count for it.
+pub const MIN_FAN: u32 = 8;
+
+/// 番种 of 国標麻将, the 81 patterns.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Fan {
+    // 88番
+    /// 大四喜
+    BigFourWinds,
+    /// 大三元
+    BigThreeDragons,
+    /// 绿一色
+    AllGreen,
+    /// 九莲宝灯
+    NineGates,
+    /// 四杠
+    FourKongs,
+    /// 连七对
+    SevenShiftedPairs,
+    /// 十三幺
+    ThirteenOrphans,
+    // 64番
+    /// 清幺九
+    AllTerminals,
+    /// 小四喜
+    LittleFourWinds,
+    /// 小三元
+    LittleThreeDragons,
+    /// 字一色
+    AllHonors,
+    /// 四暗刻
+    FourConcealedPungs,
+    /// 一色双龙会
+    PureTerminalChows,
+    // 48番
+    /// 一色四同顺
+    QuadrupleChow,
+    /// 一色四节高
+    FourPureShiftedPungs,
+    // 32番
+    /// 一色四步高
+    FourPureShiftedChows,
+    /// 三杠
+    ThreeKongs,
+    /// 混幺九
+    AllTerminalsAndHonors,
+    // 24番
+    /// 七对
+    SevenPairs,
+    /// 七星不靠
+    GreaterHonorsAndKnittedTiles,
+    /// 全双刻
+    AllEvenPungs,
+    /// 清一色
+    FullFlush,
+    /// 一色三同顺
+    PureTripleChow,
+    /// 一色三节高
+    PureShiftedPungs,
+    /// 全大
+    UpperTiles,
+    /// 全中
+    MiddleTiles,
+    /// 全小
+    LowerTiles,
+    // 16番
+    /// 清龙
+    PureStraight,
+    /// 三色双龙会
+    ThreeSuitedTerminalChows,
+    /// 一色三步高
+    PureShiftedChows,
+    /// 全带五
+    AllFives,
+    /// 三同刻
+    TriplePung,
+    /// 三暗刻
+    ThreeConcealedPungs,
+    // 12番
+    /// 全不靠
+    LesserHonorsAndKnittedTiles,
+    /// 组合龙
+    KnittedStraight,
+    /// 大于五
+    UpperFour,
+    /// 小于五
+    LowerFour,
+    /// 三风刻
+    BigThreeWinds,
+    // 8番
+    /// 花龙
+    MixedStraight,
+    /// 推不倒
+    ReversibleTiles,
+    /// 三色三同顺
+    MixedTripleChow,
+    /// 三色三节高
+    MixedShiftedPungs,
+    /// 无番和
+    ChickenHand,
+    /// 妙手回春
+    LastTileDraw,
+    /// 海底捞月
+    LastTileClaim,
+    /// 杠上开花
+    OutWithReplacementTile,
+    /// 抢杠和. Never awarded, as no 加杠 is made at the table.
+    RobbingTheKong,
+    // 6番
+    /// 碰碰和
+    AllPungs,
+    /// 混一色
+    HalfFlush,
+    /// 三色三步高
+    MixedShiftedChows,
+    /// 五门齐
+    AllTypes,
+    /// 全求人
+    MeldedHand,
+    /// 双暗杠
+    TwoConcealedKongs,
+    /// 双箭刻
+    TwoDragonPungs,
+    // 4番
+    /// 全带幺
+    OutsideHand,
+    /// 不求人
+    FullyConcealedHand,
+    /// 双明杠
+    TwoMeldedKongs,
+    /// 和绝张
+    LastTile,
+    // 2番
+    /// 箭刻
+    DragonPung,
+    /// 圈风刻
+    PrevalentWind,
+    /// 门风刻
+    SeatWind,
+    /// 门前清
+    ConcealedHand,
+    /// 平和
+    AllChows,
+    /// 四归一
+    TileHog,
+    /// 双同刻
+    DoublePung,
+    /// 双暗刻
+    TwoConcealedPungs,
+    /// 暗杠
+    ConcealedKong,
+    /// 断幺
+    AllSimples,
+    // 1番
+    /// 一般高
+    PureDoubleChow,
+    /// 喜相逢
+    MixedDoubleChow,
+    /// 连六
+    ShortStraight,
+    /// 老少副
+    TwoTerminalChows,
+    /// 幺九刻
+    PungOfTerminalsOrHonors,
+    /// 明杠
+    MeldedKong,
+    /// 缺一门
+    OneVoidedSuit,
+    /// 无字
+    NoHonors,
+    /// 边张
+    EdgeWait,
+    /// 嵌张
+    ClosedWait,
+    /// 单钓将
+    SingleWait,
+    /// 自摸
+    SelfDrawn,
+    /// 花牌, each flower. Not counted for `MIN_FAN`.
+    FlowerTiles,
+}
+
+impl Fan {
+    pub fn points(&self) -> u32 {
+        use Fan::*;
+
+        match self {
+            BigFourWinds | BigThreeDragons | AllGreen | NineGates | FourKongs
+            | SevenShiftedPairs | ThirteenOrphans => 88,
+            AllTerminals | LittleFourWinds | LittleThreeDragons | AllHonors
+            | FourConcealedPungs | PureTerminalChows => 64,
+            QuadrupleChow | FourPureShiftedPungs => 48,
+            FourPureShiftedChows | ThreeKongs | AllTerminalsAndHonors => 32,
+            SevenPairs
+            | GreaterHonorsAndKnittedTiles
+            | AllEvenPungs
+            | FullFlush
+            | PureTripleChow
+            | PureShiftedPungs
+            | UpperTiles
+            | MiddleTiles
+            | LowerTiles => 24,
+            PureStraight
+            | ThreeSuitedTerminalChows
+            | PureShiftedChows
+            | AllFives
+            | TriplePung
+            | ThreeConcealedPungs => 16,
+            LesserHonorsAndKnittedTiles
+            | KnittedStraight
+            | UpperFour
+            | LowerFour
+            | BigThreeWinds => 12,
+            MixedStraight
+            | ReversibleTiles
+            | MixedTripleChow
+            | MixedShiftedPungs
+            | ChickenHand
+            | LastTileDraw
+            | LastTileClaim
+            | OutWithReplacementTile
+            | RobbingTheKong => 8,
+            AllPungs | HalfFlush | MixedShiftedChows | AllTypes | MeldedHand
+            | TwoConcealedKongs | TwoDragonPungs => 6,
+            OutsideHand | FullyConcealedHand | TwoMeldedKongs | LastTile => 4,
+            DragonPung | PrevalentWind | SeatWind | ConcealedHand | AllChows | TileHog
+            | DoublePung | TwoConcealedPungs | ConcealedKong | AllSimples => 2,
+            PureDoubleChow
+            | MixedDoubleChow
+            | ShortStraight
+            | TwoTerminalChows
+            | PungOfTerminalsOrHonors
+            | MeldedKong
+            | OneVoidedSuit
+            | NoHonors
+            | EdgeWait
+            | ClosedWait
+            | SingleWait
+            | SelfDrawn
+            | FlowerTiles => 1,
+        }
+    }
+
+    /// 不计. Fans implied by this one, which are not counted with it.
+    fn excluded(&self) -> &'static [Fan] {
+        use Fan::*;
+
+        match self {
+            BigFourWinds => &[
+                BigThreeWinds,
+                LittleFourWinds,
+                AllPungs,
+                PrevalentWind,
+                SeatWind,
+                PungOfTerminalsOrHonors,
+            ],
+            BigThreeDragons => &[LittleThreeDragons, TwoDragonPungs, DragonPung],
+            AllGreen => &[HalfFlush],
+            NineGates => &[
+                FullFlush,
+                ConcealedHand,
+                PungOfTerminalsOrHonors,
+                NoHonors,
+                OneVoidedSuit,
+            ],
+            FourKongs => &[
+                ThreeKongs,
+                TwoMeldedKongs,
+                TwoConcealedKongs,
+                MeldedKong,
+                ConcealedKong,
+                AllPungs,
+                SingleWait,
+            ],
+            SevenShiftedPairs => &[
+                SevenPairs,
+                FullFlush,
+                ConcealedHand,
+                SingleWait,
+                NoHonors,
+                OneVoidedSuit,
+            ],
+            ThirteenOrphans => &[AllTypes, AllTerminalsAndHonors, ConcealedHand, SingleWait],
+            AllTerminals => &[
+                AllTerminalsAndHonors,
+                AllPungs,
+                OutsideHand,
+                PungOfTerminalsOrHonors,
+                NoHonors,
+            ],
+            LittleFourWinds => &[BigThreeWinds],
+            LittleThreeDragons => &[TwoDragonPungs, DragonPung],
+            AllHonors => &[
+                AllTerminalsAndHonors,
+                AllPungs,
+                OutsideHand,
+                PungOfTerminalsOrHonors,
+            ],
+            FourConcealedPungs => &[
+                ThreeConcealedPungs,
+                TwoConcealedPungs,
+                AllPungs,
+                ConcealedHand,
+                FullyConcealedHand,
+            ],
+            PureTerminalChows => &[
+                SevenPairs,
+                FullFlush,
+                AllChows,
+                PureDoubleChow,
+                TwoTerminalChows,
+                NoHonors,
+                OneVoidedSuit,
+            ],
+            QuadrupleChow => &[PureTripleChow, PureDoubleChow, TileHog],
+            FourPureShiftedPungs => &[PureShiftedPungs, AllPungs],
+            FourPureShiftedChows => &[PureShiftedChows, ShortStraight, TwoTerminalChows],
+            ThreeKongs => &[TwoMeldedKongs, TwoConcealedKongs, MeldedKong, ConcealedKong],
+            AllTerminalsAndHonors => &[AllPungs, OutsideHand, PungOfTerminalsOrHonors],
+            SevenPairs => &[ConcealedHand, SingleWait],
+            GreaterHonorsAndKnittedTiles => &[LesserHonorsAndKnittedTiles, AllTypes, ConcealedHand],
+            AllEvenPungs => &[AllPungs, AllSimples, NoHonors],
+            FullFlush => &[NoHonors, OneVoidedSuit],
+            PureTripleChow => &[PureDoubleChow],
+            UpperTiles => &[UpperFour, NoHonors],
+            MiddleTiles => &[AllSimples, NoHonors],
+            LowerTiles => &[LowerFour, NoHonors],
+            PureStraight => &[ShortStraight, TwoTerminalChows],
+            ThreeSuitedTerminalChows => &[AllChows, MixedDoubleChow, TwoTerminalChows, NoHonors],
+            AllFives => &[AllSimples, NoHonors],
+            TriplePung => &[DoublePung],
+            ThreeConcealedPungs => &[TwoConcealedPungs],
+            LesserHonorsAndKnittedTiles => &[AllTypes, ConcealedHand],
+            UpperFour | LowerFour => &[NoHonors],
+            ReversibleTiles => &[OneVoidedSuit],
+            MixedTripleChow => &[MixedDoubleChow],
+            LastTileDraw | OutWithReplacementTile => &[SelfDrawn],
+            RobbingTheKong => &[LastTile],
+            MeldedHand => &[SingleWait],
+            TwoConcealedKongs => &[ConcealedKong],
+            TwoDragonPungs => &[DragonPung],
+            FullyConcealedHand => &[ConcealedHand, SelfDrawn],
+            TwoMeldedKongs => &[MeldedKong],
+            AllChows | AllSimples => &[NoHonors],
+            _ => &[],
+        }
+    }
+}
+
+/// The fans of a completed hand, in the decomposition worth the most.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+pub struct FanEvaluation {
+    pub decomposition: McrDecomposition,
+    /// Repeated for each time counted, e.g. two `DragonPung`s or a `FlowerTiles` for each flower.
+    pub fans: Vec<Fan>,
+}
+
+impl FanEvaluation {
+    pub fn total(&self) -> u32 {
+        self.fans.iter().map(Fan::points).sum()
+    }
+
+    /// 起和, i.e. `MIN_FAN` without flowers.
+    pub fn is_qualified(&self) -> bool {
+        let flowers = self.fans.iter().filter(|f| **f == Fan::FlowerTiles).count() as u32;
+        self.total() - flowers >= MIN_FAN
+    }
+}
+
+/// Evaluates the fans of a winning hand. `None` if the hand is not completed.
+pub fn evaluate(hand: &WinningHand<McrConcept>) -> Option<FanEvaluation> {
+    let decompositions = decompose(
+        &hand.concealed_tiles,
+        &hand.exposed_melds,
+        hand.winning_tile,
+    );
+    let waiting_kinds_count = TileKind::all()
+        .filter(|kind| {
+            !decompose(
+                &hand.concealed_tiles,
+                &hand.exposed_melds,
+                Tile::Normal(*kind),
+            )
+            .is_empty()
+        })
+        .count();
+    let mut concealed_histogram = [0; TILE_KINDS_COUNT];
+    for kind in kinds_of(&hand.concealed_tiles) {
+        concealed_histogram[kind.index()] += 1;
+    }
+    let context = FanContext {
+        seat_wind: wind_of_seat(hand.seat, hand.dealer),
+        prevalent_wind: wind_of_round(hand.round),
+        is_self_drawn: hand.discarder.is_none(),
+        is_last_tile: hand.is_last_tile,
+        is_supplemental_tile: hand.is_supplemental_tile,
+        is_last_of_kind: kinds_of(&hand.visible_tiles)
+            .into_iter()
+            .filter(|kind| hand.winning_tile.kind() == Some(*kind))
+            .count()
+            == 3,
+        is_concealed: hand
+            .exposed_melds
+            .iter()
+            .all(|m| m.0.called_tile().is_none()),
+        is_melded: hand.exposed_melds.len() == 4
+            && hand
+                .exposed_melds
+                .iter()
+                .all(|m| m.0.called_tile().is_some()),
+        waits_on_single_kind: waiting_kinds_count == 1,
+        concealed_histogram,
+    };
+    let flowers = hand.bonus_tiles.iter().filter(|t| t.is_flower()).count();
+
+    decompositions
+        .into_iter()
+        .map(|decomposition| {
+            let fans = fans_of(&decomposition, &context);
+            FanEvaluation {
+                decomposition,
+                fans,
+            }
+        })
+        .max_by_key(FanEvaluation::total)
+        .map(|evaluation| FanEvaluation {
+            fans: evaluation
+                .fans
+                .into_iter()
+                .chain(vec![Fan::FlowerTiles; flowers])
+                .collect(),
+            ..evaluation
+        })
+}
+
+struct FanContext {
+    seat_wind: Wind,
+    prevalent_wind: Wind,
+    is_self_drawn: bool,
+    is_last_tile: bool,
+    is_supplemental_tile: bool,
+    /// The other three of the winning kind are visible on the table.
+    is_last_of_kind: bool,
+    /// No melds called, though 暗杠 may be exposed.
+    is_concealed: bool,
+    /// Every group called.
+    is_melded: bool,
+    /// Only the wait fans of such a hand count.
+    waits_on_single_kind: bool,
+    /// Excluding the winning tile.
+    concealed_histogram: Histogram,
+}
+
+/// The dealer sits at East.
+pub(crate) fn wind_of_seat(seat: Seat, dealer: Seat) -> Wind {
+    wind_of_index((usize::from(seat) + 4 - usize::from(dealer)) % 4)
+}
+
+pub(crate) fn wind_of_round(round: Round) -> Wind {
+    match round {
+        Round::East => Wind::East,
+        Round::South => Wind::South,
+        Round::West => Wind::West,
+        Round::North => Wind::North,
+    }
+}
+
+fn wind_of_index(index: usize) -> Wind {
+    [Wind::East, Wind::South, Wind::West, Wind::North][index]
+}
+
+pub(crate) fn number_of(kind: TileKind) -> Option<(Suit, u8)> {
+    match kind {
+        TileKind::Suited(suit, n) => Some((suit, n)),
+        _ => None,
+    }
+}
+
+fn fans_of(decomposition: &McrDecomposition, context: &FanContext) -> Vec<Fan> {
+    let form = &decomposition.form;
+    let kinds = form.kinds();
+    let mut fans = kind_fans(&kinds);
+
+    match form {
+        McrHandForm::Normal { pair, groups } => {
+            fans.extend(group_fans(*pair, groups, decomposition, context));
+            fans.extend(chow_combination_fans(groups));
+            if groups.iter().all(|g| g.shape == GroupShape::Sequence) && !pair.is_honor() {
+                fans.push(Fan::AllChows);
+            }
+            if is_nine_gates(&context.concealed_histogram) && context.is_concealed {
+                fans.push(Fan::NineGates);
+            }
+        }
+        McrHandForm::KnittedStraight { pair, groups, .. } => {
+            fans.push(Fan::KnittedStraight);
+            fans.extend(group_fans(*pair, groups, decomposition, context));
+        }
+        McrHandForm::SevenPairs { pairs } => {
+            let is_shifted = pairs
+                .iter()
+                .map(|k| number_of(*k))
+                .collect::<Option<Vec<_>>>()
+                .is_some_and(|numbers| {
+                    numbers.iter().map(|(suit, _)| suit).all_equal()
+                        && numbers.windows(2).all(|w| w[1].1 == w[0].1 + 1)
+                });
+            fans.push(if is_shifted {
+                Fan::SevenShiftedPairs
+            } else {
+                Fan::SevenPairs
+            });
+        }
+        McrHandForm::ThirteenOrphans { .. } => fans.push(Fan::ThirteenOrphans),
+        McrHandForm::HonorsAndKnitted { kinds } => {
+            fans.push(if kinds.iter().filter(|k| k.is_honor()).count() == 7 {
+                Fan::GreaterHonorsAndKnittedTiles
+            } else {
+                Fan::LesserHonorsAndKnittedTiles
+            });
+            if kinds.iter().filter(|k| !k.is_honor()).count() == 9 {
+                fans.push(Fan::KnittedStraight);
+            }
+        }
+    }
+
+    // 四归一, four of a kind not as a quad
+    let mut histogram = [0; TILE_KINDS_COUNT];
+    for kind in kinds.iter() {
+        histogram[kind.index()] += 1;
+    }
+    let quads = match form {
+        McrHandForm::Normal { groups, .. } | McrHandForm::KnittedStraight { groups, .. } => groups
+            .iter()
+            .filter(|g| g.shape == GroupShape::Quad)
+            .map(|g| g.kind)
+            .collect_vec(),
+        _ => vec![],
+    };
+    for kind in TileKind::all() {
+        if histogram[kind.index()] == 4 && !quads.contains(&kind) {
+            fans.push(Fan::TileHog);
+        }
+    }
+
+    if context.is_concealed {
+        fans.push(if context.is_self_drawn {
+            Fan::FullyConcealedHand
+        } else {
+            Fan::ConcealedHand
+        });
+    } else if context.is_melded && !context.is_self_drawn {
+        fans.push(Fan::MeldedHand);
+    }
+    if context.is_self_drawn {
+        fans.push(Fan::SelfDrawn);
+    }
+    if context.is_last_tile {
+        fans.push(if context.is_self_drawn {
+            Fan::LastTileDraw
+        } else {
+            Fan::LastTileClaim
+        });
+    }
+    if context.is_supplemental_tile && context.is_self_drawn {
+        fans.push(Fan::OutWithReplacementTile);
+    }
+    if context.is_last_of_kind {
+        fans.push(Fan::LastTile);
+    }
+    if context.waits_on_single_kind {
+        match decomposition.wait {
+            Some(Wait::Edge) => fans.push(Fan::EdgeWait),
+            Some(Wait::Closed) => fans.push(Fan::ClosedWait),
+            Some(Wait::Single) => fans.push(Fan::SingleWait),
+            _ => {}
+        }
+    }
+
+    let excluded = fans
+        .iter()
+        .flat_map(|f| f.excluded())
+        .copied()
+        .collect_vec();
+    let fans = fans
+        .into_iter()
+        .filter(|f| !excluded.contains(f))
+        .collect_vec();
+    if fans.is_empty() {
+        vec![Fan::ChickenHand]
+    } else {
+        fans
+    }
+}
+
+/// Fans told by the kinds of the tiles alone.
+fn kind_fans(kinds: &[TileKind]) -> Vec<Fan> {
+    let mut fans = vec![];
+    let numbers = kinds.iter().filter_map(|k| number_of(*k)).collect_vec();
+    let suits = numbers.iter().map(|(suit, _)| *suit).unique().collect_vec();
+    let has_honors = kinds.iter().any(TileKind::is_honor);
+    let has_winds = kinds.iter().any(|k| matches!(k, TileKind::Wind(_)));
+    let has_dragons = kinds.iter().any(|k| matches!(k, TileKind::Dragon(_)));
+    let all_numbers_in = |range: std::ops::RangeInclusive<u8>| {
+        !has_honors && numbers.iter().all(|(_, n)| range.contains(n))
+    };
+
+    let is_green = |kind: &TileKind| {
+        matches!(
+            kind,
+            TileKind::Suited(Suit::Bamboo, 2)
+                | TileKind::Suited(Suit::Bamboo, 3)
+                | TileKind::Suited(Suit::Bamboo, 4)
+                | TileKind::Suited(Suit::Bamboo, 6)
+                | TileKind::Suited(Suit::Bamboo, 8)
+                | TileKind::Dragon(Dragon::Green)
+        )
+    };
+    if kinds.iter().all(is_green) {
+        fans.push(Fan::AllGreen);
+    }
+    let is_reversible = |kind: &TileKind| match *kind {
+        TileKind::Suited(Suit::Circle, n) => [1, 2, 3, 4, 5, 8, 9].contains(&n),
+        TileKind::Suited(Suit::Bamboo, n) => [2, 4, 5, 6, 8, 9].contains(&n),
+        TileKind::Dragon(Dragon::White) => true,
+        _ => false,
+    };
+    if kinds.iter().all(is_reversible) {
+        fans.push(Fan::ReversibleTiles);
+    }
+
+    if kinds.iter().all(TileKind::is_terminal) {
+        fans.push(Fan::AllTerminals);
+    } else if kinds.iter().all(TileKind::is_honor) {
+        fans.push(Fan::AllHonors);
+    } else if kinds.iter().all(TileKind::is_terminal_or_honor) {
+        fans.push(Fan::AllTerminalsAndHonors);
+    }
+
+    match (suits.len(), has_honors) {
+        (1, false) => fans.push(Fan::FullFlush),
+        (1, true) => fans.push(Fan::HalfFlush),
+        (2, _) => fans.push(Fan::OneVoidedSuit),
+        (3, _) if has_winds && has_dragons => fans.push(Fan::AllTypes),
+        _ => {}
+    }
+    if !has_honors {
+        fans.push(Fan::NoHonors);
+    }
+
+    if all_numbers_in(7..=9) {
+        fans.push(Fan::UpperTiles);
+    } else if all_numbers_in(4..=6) {
+        fans.push(Fan::MiddleTiles);
+    } else if all_numbers_in(1..=3) {
+        fans.push(Fan::LowerTiles);
+    } else if all_numbers_in(6..=9) {
+        fans.push(Fan::UpperFour);
+    } else if all_numbers_in(1..=4) {
+        fans.push(Fan::LowerFour);
+    }
+    if !kinds.iter().any(TileKind::is_terminal_or_honor) {
+        fans.push(Fan::AllSimples);
+    }
+    fans
+}
+
+/// Fans of the pair and the groups other than the combinations of sequences.
+fn group_fans(
+    pair: TileKind,
+    groups: &[Group],
+    decomposition: &McrDecomposition,
+    context: &FanContext,
+) -> Vec<Fan> {
+    let mut fans = vec![];
+    let pungs = groups
+        .iter()
+        .filter(|g| g.shape != GroupShape::Sequence)
+        .collect_vec();
+    let is_dragon = |kind: &TileKind| matches!(kind, TileKind::Dragon(_));
+    let is_wind = |kind: &TileKind| matches!(kind, TileKind::Wind(_));
+    let dragon_pungs = pungs.iter().filter(|g| is_dragon(&g.kind)).count();
+    let wind_pungs = pungs.iter().filter(|g| is_wind(&g.kind)).count();
+
+    match (wind_pungs, is_wind(&pair)) {
+        (4, _) => fans.push(Fan::BigFourWinds),
+        (3, true) => fans.push(Fan::LittleFourWinds),
+        (3, false) => fans.push(Fan::BigThreeWinds),
+        _ => {}
+    }
+    match (dragon_pungs, is_dragon(&pair)) {
+        (3, _) => fans.push(Fan::BigThreeDragons),
+        (2, true) => fans.push(Fan::LittleThreeDragons),
+        (2, false) => fans.push(Fan::TwoDragonPungs),
+        _ => {}
+    }
+    for pung in pungs.iter() {
+        match pung.kind {
+            TileKind::Dragon(_) => fans.push(Fan::DragonPung),
+            TileKind::Wind(wind) => {
+                let is_valued = wind == context.prevalent_wind || wind == context.seat_wind;
+                if wind == context.prevalent_wind {
+                    fans.push(Fan::PrevalentWind);
+                }
+                if wind == context.seat_wind {
+                    fans.push(Fan::SeatWind);
+                }
+                // 三风刻 and 小四喜 take the other winds.
+                if !is_valued && wind_pungs < 3 {
+                    fans.push(Fan::PungOfTerminalsOrHonors);
+                }
+            }
+            kind if kind.is_terminal() => fans.push(Fan::PungOfTerminalsOrHonors),
+            _ => {}
+        }
+    }
+
+    if groups.len() == 4 && pungs.len() == 4 {
+        fans.push(Fan::AllPungs);
+        if pungs
+            .iter()
+            .map(|g| g.kind)
+            .chain(Some(pair))
+            .all(|k| matches!(number_of(k), Some((_, n)) if n % 2 == 0))
+        {
+            fans.push(Fan::AllEvenPungs);
+        }
+    }
+    if groups.len() == 4
+        && groups
+            .iter()
+            .map(|g| g.kinds())
+            .chain(Some(vec![pair]))
+            .all(|kinds| kinds.iter().any(|k| matches!(number_of(*k), Some((_, 5)))))
+    {
+        fans.push(Fan::AllFives);
+    }
+    if groups.len() == 4
+        && groups
+            .iter()
+            .map(|g| g.kinds())
+            .chain(Some(vec![pair]))
+            .all(|kinds| kinds.iter().any(TileKind::is_terminal_or_honor))
+    {
+        fans.push(Fan::OutsideHand);
+    }
+
+    // A triplet completed by a discard is not concealed.
+    let concealed_pungs = pungs
+        .iter()
+        .filter(|g| g.is_concealed)
+        .filter(|g| {
+            context.is_self_drawn
+                || g.shape == GroupShape::Quad
+                || !matches!(
+                    decomposition.winning_position,
+                    Some(WinningPosition::Group(i)) if groups[i] == ***g
+                )
+        })
+        .count();
+    match concealed_pungs {
+        4 => fans.push(Fan::FourConcealedPungs),
+        3 => fans.push(Fan::ThreeConcealedPungs),
+        2 => fans.push(Fan::TwoConcealedPungs),
+        _ => {}
+    }
+    let quads = pungs.iter().filter(|g| g.shape == GroupShape::Quad);
+    let (concealed_quads, melded_quads) = quads.partition::<Vec<&&Group>, _>(|g| g.is_concealed);
+    match concealed_quads.len() + melded_quads.len() {
+        4 => fans.push(Fan::FourKongs),
+        3 => fans.push(Fan::ThreeKongs),
+        _ => {}
+    }
+    match concealed_quads.len() {
+        2 => fans.push(Fan::TwoConcealedKongs),
+        1 => fans.push(Fan::ConcealedKong),
+        _ => {}
+    }
+    match melded_quads.len() {
+        2 => fans.push(Fan::TwoMeldedKongs),
+        1 => fans.push(Fan::MeldedKong),
+        _ => {}
+    }
+
+    let suited_pungs = pungs.iter().filter_map(|g| number_of(g.kind)).collect_vec();
+    for count in (2..=suited_pungs.len()).rev() {
+        let mut found = vec![];
+        for pungs in suited_pungs.iter().combinations(count) {
+            let numbers = pungs.iter().map(|(_, n)| *n).collect_vec();
+            let suits = pungs.iter().map(|(s, _)| *s).collect_vec();
+            let is_same_suit = suits.iter().all_equal();
+            let is_each_suit = suits.iter().unique().count() == suits.len();
+            let is_shifted = numbers
+                .iter()
+                .sorted()
+                .tuple_windows()
+                .all(|(a, b)| *b == *a + 1);
+            match count {
+                4 if is_same_suit && is_shifted => found.push(Fan::FourPureShiftedPungs),
+                3 if is_same_suit && is_shifted => found.push(Fan::PureShiftedPungs),
+                3 if is_each_suit && is_shifted => found.push(Fan::MixedShiftedPungs),
+                3 if is_each_suit && numbers.iter().all_equal() => found.push(Fan::TriplePung),
+                2 if is_each_suit && numbers.iter().all_equal() => found.push(Fan::DoublePung),
+                _ => {}
+            }
+        }
+        if !found.is_empty() {
+            fans.extend(found);
+            break;
+        }
+    }
+
+    let sequences = groups
+        .iter()
+        .filter(|g| g.shape == GroupShape::Sequence)
+        .filter_map(|g| number_of(g.kind))
+        .sorted()
+        .collect_vec();
+    if let [(a, 1), (b, 1), (c, 7), (d, 7)] = sequences[..] {
+        if a == b && c == d && a == c && matches!(number_of(pair), Some((s, 5)) if s == a) {
+            fans.push(Fan::PureTerminalChows);
+        }
+    }
+    if sequences.len() == 4 {
+        let suits = sequences.iter().map(|(s, _)| *s).unique().collect_vec();
+        let terminal_suits = suits
+            .iter()
+            .filter(|s| sequences.contains(&(**s, 1)) && sequences.contains(&(**s, 7)))
+            .count();
+        if suits.len() == 2
+            && terminal_suits == 2
+            && matches!(number_of(pair), Some((s, 5)) if !suits.contains(&s))
+        {
+            fans.push(Fan::ThreeSuitedTerminalChows);
+        }
+        if sequences.iter().map(|(s, _)| s).all_equal() {
+            let starts = sequences.iter().map(|(_, n)| *n).collect_vec();
+            if starts.iter().all_equal() {
+                fans.push(Fan::QuadrupleChow);
+            } else if matches!(common_step(&starts), Some(1) | Some(2)) {
+                fans.push(Fan::FourPureShiftedChows);
+            }
+        }
+    }
+    fans
+}
+
+/// Combinations of two or three sequences, counted under 一次原则: no two combinations share
+/// more than one sequence, a sequence is combined at most twice, and there are fewer
+/// combinations than sequences.
+fn chow_combination_fans(groups: &[Group]) -> Vec<Fan> {
+    let sequences = groups
+        .iter()
+        .filter(|g| g.shape == GroupShape::Sequence)
+        .filter_map(|g| number_of(g.kind))
+        .collect_vec();
+    let candidates = (2..=3)
+        .flat_map(|count| (0..sequences.len()).combinations(count))
+        .filter_map(|indices| {
+            let chosen = indices.iter().map(|i| sequences[*i]).collect_vec();
+            chow_combination_fan(&chosen).map(|fan| (indices, fan))
+        })
+        .collect_vec();
+
+    let mut best = vec![];
+    for bits in 0..1usize << candidates.len() {
+        let chosen = candidates
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| bits & 1 << i != 0)
+            .map(|(_, candidate)| candidate)
+            .collect_vec();
+        if chosen.len() >= sequences.len().max(1) {
+            continue;
+        }
+        let overlaps = chosen
+            .iter()
+            .tuple_combinations()
+            .any(|(a, b)| a.0.iter().filter(|i| b.0.contains(i)).count() > 1);
+        let overused = (0..sequences.len()).any(|i| {
+            chosen
+                .iter()
+                .filter(|(indices, _)| indices.contains(&i))
+                .count()
+                > 2
+        });
+        if overlaps || overused {
+            continue;
+        }
+        let fans = chosen.iter().map(|(_, fan)| *fan).collect_vec();
+        let points = |fans: &[Fan]| fans.iter().map(Fan::points).sum::<u32>();
+        if points(&fans) > points(&best) {
+            best = fans;
+        }
+    }
+    best
+}
+
+fn chow_combination_fan(sequences: &[(Suit, u8)]) -> Option<Fan> {
+    let suits = sequences.iter().map(|(s, _)| *s).collect_vec();
+    let starts = sequences.iter().map(|(_, n)| *n).sorted().collect_vec();
+    let is_same_suit = suits.iter().all_equal();
+    let is_each_suit = suits.iter().unique().count() == suits.len();
+    match (sequences.len(), common_step(&starts)) {
+        (3, Some(3)) if starts[0] == 1 && is_same_suit => Some(Fan::PureStraight),
+        (3, Some(3)) if starts[0] == 1 && is_each_suit => Some(Fan::MixedStraight),
+        (3, Some(0)) if is_same_suit => Some(Fan::PureTripleChow),
+        (3, Some(0)) if is_each_suit => Some(Fan::MixedTripleChow),
+        (3, Some(1)) | (3, Some(2)) if is_same_suit => Some(Fan::PureShiftedChows),
+        (3, Some(1)) if is_each_suit => Some(Fan::MixedShiftedChows),
+        (2, Some(0)) if is_same_suit => Some(Fan::PureDoubleChow),
+        (2, Some(0)) => Some(Fan::MixedDoubleChow),
+        (2, Some(3)) if is_same_suit => Some(Fan::ShortStraight),
+        (2, Some(6)) if is_same_suit => Some(Fan::TwoTerminalChows),
+        _ => None,
+    }
+}
+
+/// The difference between each of the sorted numbers, if the same.
+fn common_step(numbers: &[u8]) -> Option<u8> {
+    let steps = numbers
+        .iter()
+        .tuple_windows()
+        .map(|(a, b)| b - a)
+        .collect_vec();
+    match steps.first() {
+        Some(step) if steps.iter().all_equal() => Some(*step),
+        _ => None,
+    }
+}
+
+/// 九莲宝灯, 1112345678999 of a suit waiting on any of it.
+pub(crate) fn is_nine_gates(concealed_histogram: &Histogram) -> bool {
+    [Suit::Character, Suit::Circle, Suit::Bamboo]
+        .iter()
+        .any(|suit| {
+            (1..=9).all(|n| {
+                let expected = if n == 1 || n == 9 { 3 } else { 1 };
+                concealed_histogram[TileKind::Suited(*suit, n).index()] == expected
+            }) && concealed_histogram.iter().sum::<u8>() == 13
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcr::tile::Flower;
+    use crate::mcr::winning_hand_of;
+
+    fn fans_of_ron(concealed_tiles: &str, winning_tile: &str) -> Vec<Fan> {
+        evaluate(&winning_hand_of(
+            concealed_tiles,
+            winning_tile,
+            Some(Seat::West),
+        ))
+        .unwrap()
+        .fans
+    }
+
+    #[test]
+    fn thirteen_orphans_implies_the_others() {
+        assert_eq!(
+            fans_of_ron("19m19p19s1234567z", "1m"),
+            vec![Fan::ThirteenOrphans]
+        );
+    }
+
+    #[test]
+    fn four_of_a_kind_makes_two_pairs() {
+        assert_eq!(
+            fans_of_ron("1111m2233p4455s6z", "6z"),
+            vec![Fan::SevenPairs, Fan::TileHog]
+        );
+    }
+
+    #[test]
+    fn implied_fans_are_not_counted() {
+        let fans = fans_of_ron("123456789m234p5p", "5p");
+        assert!(fans.contains(&Fan::PureStraight));
+        assert!(!fans.contains(&Fan::ShortStraight));
+        assert!(!fans.contains(&Fan::TwoTerminalChows));
+    }
+
+    #[test]
+    fn honors_and_knitted_tiles_count_with_knitted_straight() {
+        let evaluation = evaluate(&winning_hand_of(
+            "147m258p369s1234z",
+            "5z",
+            Some(Seat::West),
+        ))
+        .unwrap();
+        assert_eq!(
+            evaluation.fans,
+            vec![Fan::LesserHonorsAndKnittedTiles, Fan::KnittedStraight]
+        );
+        assert!(matches!(
+            evaluation.decomposition.form,
+            McrHandForm::HonorsAndKnitted { .. }
+        ));
+    }
+
+    #[test]
+    fn flowers_do_not_count_for_min_fan() {
+        let hand = WinningHand {
+            bonus_tiles: [Flower::Plum, Flower::Orchid, Flower::Spring]
+                .iter()
+                .map(|f| Tile::Flower(*f))
+                .collect(),
+            ..winning_hand_of("123m456m789p234s5s", "5s", Some(Seat::West))
+        };
+        let evaluation = evaluate(&hand).unwrap();
+        assert_eq!(evaluation.total(), MIN_FAN);
+        assert!(!evaluation.is_qualified());
+    }
+
+    #[test]
+    fn supplemental_tile_replaces_self_drawn() {
+        let hand = WinningHand {
+            is_supplemental_tile: true,
+            ..winning_hand_of("123m456m789p234s5s", "5s", None)
+        };
+        let fans = evaluate(&hand).unwrap().fans;
+        assert!(fans.contains(&Fan::OutWithReplacementTile));
+        assert!(!fans.contains(&Fan::SelfDrawn));
+    }
+
+    #[test]
+    fn last_tile_is_the_fourth_visible_one() {
+        let visible_tiles = |notation| {
+            crate::rule::tiles_of(notation)
+                .iter()
+                .map(|t| Tile::Normal(t.kind()))
+                .collect()
+        };
+        let hand = WinningHand {
+            visible_tiles: visible_tiles("111z9p"),
+            ..winning_hand_of("123m456m789p234s1z", "1z", Some(Seat::West))
+        };
+        assert!(evaluate(&hand).unwrap().fans.contains(&Fan::LastTile));
+
+        let hand = WinningHand {
+            visible_tiles: visible_tiles("11z"),
+            ..hand
+        };
+        assert!(!evaluate(&hand).unwrap().fans.contains(&Fan::LastTile));
+    }
+}
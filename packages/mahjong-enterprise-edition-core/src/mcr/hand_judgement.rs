@@ -0,0 +1,68 @@
+use crate::game::{HandJudgementSpec, HandScore, RuleConfig, WinningHand};
+use crate::mcr::decomposition::decompose;
+use crate::mcr::fan::evaluate;
+use crate::mcr::meld::Meld;
+use crate::mcr::tile::Tile;
+use crate::mcr::McrConcept;
+use crate::rule::TileKind;
+
+pub struct McrHandJudgementSpec;
+
+impl HandJudgementSpec<McrConcept> for McrHandJudgementSpec {
+    fn waiting_tiles(&self, concealed_tiles: &[Tile], exposed_melds: &[Meld]) -> Vec<Tile> {
+        TileKind::all()
+            .map(Tile::Normal)
+            .filter(|tile| !decompose(concealed_tiles, exposed_melds, *tile).is_empty())
+            .collect()
+    }
+
+    fn is_terminal_or_honor(&self, tile: Tile) -> bool {
+        tile.kind().is_some_and(|kind| kind.is_terminal_or_honor())
+    }
+
+    /// 补花
+    fn is_bonus_tile(&self, tile: Tile) -> bool {
+        tile.is_flower()
+    }
+
+    /// 起和, reaching `MIN_FAN` without flowers. Otherwise 错和.
+    fn is_scorable(&self, hand: &WinningHand<McrConcept>, _rule: &RuleConfig) -> bool {
+        evaluate(hand).is_some_and(|evaluation| evaluation.is_qualified())
+    }
+
+    /// The fans in `basic_points`, zero below `MIN_FAN` (错和).
+    fn score(&self, hand: &WinningHand<McrConcept>, _rule: &RuleConfig) -> HandScore {
+        let basic_points = match evaluate(hand) {
+            Some(evaluation) if evaluation.is_qualified() => evaluation.total() as i32,
+            _ => 0,
+        };
+        HandScore {
+            basic_points,
+            liable_basic_points: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{RulePreset, Seat};
+    use crate::mcr::winning_hand_of;
+
+    #[test]
+    fn hand_below_min_fan_is_not_scorable() {
+        let rule = RulePreset::ChineseOfficial.rule();
+        // 平和・门前清・单钓将・连六
+        let hand = winning_hand_of("123m456m789p234s5s", "5s", Some(Seat::West));
+        assert!(!McrHandJudgementSpec.is_scorable(&hand, &rule));
+        assert_eq!(McrHandJudgementSpec.score(&hand, &rule).basic_points, 0);
+    }
+
+    #[test]
+    fn hand_of_min_fan_is_scorable() {
+        let rule = RulePreset::ChineseOfficial.rule();
+        // 清龙
+        let hand = winning_hand_of("123456789m234p5p", "5p", Some(Seat::West));
+        assert!(McrHandJudgementSpec.is_scorable(&hand, &rule));
+    }
+}
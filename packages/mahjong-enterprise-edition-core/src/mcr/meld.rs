@@ -0,0 +1,40 @@
+use crate::game::MeldConcept;
+use crate::mcr::tile::Tile;
+use crate::rule;
+
+/// 吃・碰・杠 of normal tiles, as of the standard rule without red fives.
+#[derive(Clone, Debug)]
+pub struct Meld(pub rule::Meld);
+
+impl MeldConcept<Tile> for Meld {
+    fn tiles(&self) -> Vec<Tile> {
+        self.0
+            .tiles()
+            .iter()
+            .map(|t| Tile::Normal(t.kind()))
+            .collect()
+    }
+
+    fn called_tile(&self) -> Option<Tile> {
+        self.0.called_tile().map(|t| Tile::Normal(t.kind()))
+    }
+
+    fn is_sequence(&self) -> bool {
+        self.0.is_sequence()
+    }
+
+    fn is_quad(&self) -> bool {
+        self.0.is_quad()
+    }
+
+    fn is_well_formed(&self) -> bool {
+        self.0.is_well_formed()
+    }
+
+    fn is_swap_calling(&self, tile: Tile) -> bool {
+        match tile {
+            Tile::Normal(kind) => self.0.is_swap_calling(rule::Tile::new(kind)),
+            Tile::Flower(_) => false,
+        }
+    }
+}
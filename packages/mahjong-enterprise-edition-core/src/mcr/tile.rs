@@ -0,0 +1,89 @@
+use crate::rule::{TileKind, Wind};
+use serde::Serialize;
+
+/// A tile of 国標麻将, the 136 normal ones with 8 flowers.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Tile {
+    Normal(TileKind),
+    /// 花牌
+    Flower(Flower),
+}
+
+/// 花牌, one of each.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Flower {
+    /// 梅
+    Plum,
+    /// 蘭
+    Orchid,
+    /// 菊
+    Chrysanthemum,
+    /// 竹
+    Bamboo,
+    /// 春
+    Spring,
+    /// 夏
+    Summer,
+    /// 秋
+    Autumn,
+    /// 冬
+    Winter,
+}
+
+impl Flower {
+    pub const ALL: [Flower; 8] = [
+        Flower::Plum,
+        Flower::Orchid,
+        Flower::Chrysanthemum,
+        Flower::Bamboo,
+        Flower::Spring,
+        Flower::Summer,
+        Flower::Autumn,
+        Flower::Winter,
+    ];
+
+    /// 正花, the wind of the seat the flower belongs to, e.g. East for 梅 and 春.
+    pub fn wind(&self) -> Wind {
+        match self {
+            Flower::Plum | Flower::Spring => Wind::East,
+            Flower::Orchid | Flower::Summer => Wind::South,
+            Flower::Chrysanthemum | Flower::Autumn => Wind::West,
+            Flower::Bamboo | Flower::Winter => Wind::North,
+        }
+    }
+
+    /// 春夏秋冬, otherwise 梅蘭菊竹.
+    pub fn is_season(&self) -> bool {
+        matches!(
+            self,
+            Flower::Spring | Flower::Summer | Flower::Autumn | Flower::Winter
+        )
+    }
+}
+
+impl Tile {
+    /// `None` for a flower.
+    pub fn kind(&self) -> Option<TileKind> {
+        match *self {
+            Tile::Normal(kind) => Some(kind),
+            Tile::Flower(_) => None,
+        }
+    }
+
+    pub fn is_flower(&self) -> bool {
+        matches!(self, Tile::Flower(_))
+    }
+}
+
+impl From<TileKind> for Tile {
+    fn from(kind: TileKind) -> Self {
+        Tile::Normal(kind)
+    }
+}
+
+/// Kinds of the normal tiles, skipping flowers.
+pub(crate) fn kinds_of(tiles: &[Tile]) -> Vec<TileKind> {
+    tiles.iter().filter_map(Tile::kind).collect()
+}
@@ -0,0 +1,32 @@
+use crate::game::{DealtResult, RuleConfig, TileDealingSpec};
+use crate::mcr::tile::{Flower, Tile};
+use crate::mcr::McrConcept;
+use crate::rule::TileKind;
+use rand::seq::SliceRandom;
+
+/// 144 tiles shuffled, with flowers and without ドラ表示牌.
+pub struct McrTileDealingSpec;
+
+/// 補花 and 杠 draw from here.
+const SUPPLEMENTAL_TILES_COUNT: usize = 16;
+const INITIAL_HAND_TILES_COUNT: usize = 13;
+
+impl TileDealingSpec<McrConcept> for McrTileDealingSpec {
+    fn deal(&self, rule: &RuleConfig) -> DealtResult<McrConcept> {
+        let mut tiles = TileKind::all()
+            .flat_map(|kind| vec![Tile::Normal(kind); 4])
+            .chain(Flower::ALL.iter().copied().map(Tile::Flower))
+            .collect::<Vec<_>>();
+        tiles.shuffle(&mut rand::thread_rng());
+
+        let mut deal = |count: usize| tiles.split_off(tiles.len() - count);
+        let supplemental_tiles = deal(SUPPLEMENTAL_TILES_COUNT);
+        let player_tiles = rule
+            .players_count
+            .seats()
+            .map(|seat| (deal(INITIAL_HAND_TILES_COUNT), seat))
+            .collect();
+
+        DealtResult::new(tiles, supplemental_tiles, vec![], player_tiles)
+    }
+}
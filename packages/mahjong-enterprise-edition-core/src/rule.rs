@@ -0,0 +1,77 @@
+use crate::game::Concept;
+#[cfg(test)]
+use crate::game::{Round, Seat, WinningHand};
+
+mod acceptance;
+mod decomposition;
+mod explanation;
+mod fu;
+mod hand_judgement;
+mod meld;
+mod point;
+mod seating;
+mod shanten;
+mod tile;
+mod tile_dealing;
+mod yaku;
+
+pub use acceptance::{visible_histogram, Acceptance, AcceptedTile, DiscardCandidate};
+pub use decomposition::{
+    decompose, Decomposition, Group, GroupShape, HandForm, Wait, WinningPosition,
+};
+pub use explanation::{DoraCount, Language, ScoreExplanation};
+pub use fu::{Fu, FuCalculator, FuItem};
+pub use hand_judgement::StandardHandJudgementSpec;
+pub use meld::Meld;
+pub use point::{
+    BasicPoints, HandValue, Limit, NormalSystem, PointCalculator, PointSystem, Points,
+    UnlimitedSystem,
+};
+pub use seating::RandomSeatingSpec;
+pub use shanten::Shanten;
+pub use tile::{histogram_of, Dragon, Histogram, Suit, Tile, TileKind, Wind, TILE_KINDS_COUNT};
+pub use tile_dealing::{StandardTileDealingSpec, ThreePlayerTileDealingSpec};
+pub use yaku::{NormalYaku, WinContext, Yaku, YakuEvaluation, YakuEvaluator, YakumanYaku};
+
+pub(crate) use decomposition::{normal_forms, thirteen_orphans};
+#[cfg(test)]
+pub(crate) use meld::chow_of;
+#[cfg(test)]
+pub(crate) use tile::tiles_of;
+
+/// Tiles and melds of the standard Japanese rule.
+pub struct StandardConcept;
+
+impl Concept for StandardConcept {
+    type Tile = Tile;
+    type Meld = Meld;
+}
+
+/// South's hand in the East round, dealt by East, in the notation of [`tiles_of`].
+#[cfg(test)]
+pub(crate) fn winning_hand_of(
+    concealed_tiles: &str,
+    winning_tile: &str,
+    discarder: Option<Seat>,
+) -> WinningHand<StandardConcept> {
+    WinningHand {
+        concealed_tiles: tiles_of(concealed_tiles),
+        exposed_melds: vec![],
+        winning_tile: tiles_of(winning_tile)[0],
+        bonus_tiles: vec![],
+        seat: Seat::South,
+        dealer: Seat::East,
+        round: Round::East,
+        discarder,
+        is_ready_declared: false,
+        is_ready_declared_first: false,
+        is_right_after_ready: false,
+        is_last_tile: false,
+        is_supplemental_tile: false,
+        is_first_draw: false,
+        visible_tiles: vec![],
+        reward_indication_tiles: vec![],
+        hidden_reward_indication_tiles: vec![],
+        liable_seat: None,
+    }
+}
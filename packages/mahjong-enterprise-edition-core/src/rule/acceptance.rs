@@ -0,0 +1,189 @@
+use crate::game::{MeldConcept, TableView};
+use crate::rule::meld::Meld;
+use crate::rule::shanten::Shanten;
+use crate::rule::tile::{histogram_of, Histogram, Tile, TileKind, TILE_KINDS_COUNT};
+use crate::rule::StandardConcept;
+use itertools::Itertools;
+
+/// 受け入れ. Tiles which reduce the shanten of a hand awaiting a draw.
+///
+/// ```
+/// use mahjong_enterprise_edition_core::rule::{histogram_of, Acceptance, Suit, Tile, TileKind};
+///
+/// let tiles = [1, 1, 1, 2, 3, 4, 5, 6, 7, 8, 9, 9, 9]
+///     .iter()
+///     .map(|n| Tile::new(TileKind::Suited(Suit::Character, *n)))
+///     .collect::<Vec<_>>();
+/// // 九蓮宝燈 waits on every kind of the suit.
+/// let acceptance = Acceptance::of(&tiles, &[], &histogram_of(&tiles));
+/// assert_eq!(acceptance.waits().unwrap().len(), 9);
+/// assert_eq!(acceptance.total(), 23);
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Acceptance {
+    pub shanten: i8,
+    /// In the order of kinds.
+    pub tiles: Vec<AcceptedTile>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct AcceptedTile {
+    pub kind: TileKind,
+    /// Copies not visible to the seat. Possibly zero.
+    pub remaining: u8,
+}
+
+/// A tile to discard from a hand after a draw, with the acceptance after discarding it.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct DiscardCandidate {
+    pub tile: Tile,
+    pub acceptance: Acceptance,
+}
+
+const COPIES_COUNT: u8 = 4;
+
+impl Acceptance {
+    /// `visible` counts the tiles visible to the seat, including its own hand.
+    pub fn of(concealed_tiles: &[Tile], exposed_melds: &[Meld], visible: &Histogram) -> Acceptance {
+        Acceptance::from_histogram(&histogram_of(concealed_tiles), exposed_melds.len(), visible)
+    }
+
+    pub fn from_histogram(
+        histogram: &Histogram,
+        exposed_melds_count: usize,
+        visible: &Histogram,
+    ) -> Acceptance {
+        let shanten = Shanten::from_histogram(histogram, exposed_melds_count).value();
+        let tiles = TileKind::all()
+            // 純手牌で4枚使っている牌は引けない
+            .filter(|kind| histogram[kind.index()] < COPIES_COUNT)
+            .filter(|kind| {
+                let mut histogram = *histogram;
+                histogram[kind.index()] += 1;
+                Shanten::from_histogram(&histogram, exposed_melds_count).value() < shanten
+            })
+            .map(|kind| AcceptedTile {
+                kind,
+                remaining: COPIES_COUNT.saturating_sub(visible[kind.index()]),
+            })
+            .collect();
+        Acceptance { shanten, tiles }
+    }
+
+    /// The copies remaining in total.
+    pub fn total(&self) -> usize {
+        self.tiles.iter().map(|t| t.remaining as usize).sum()
+    }
+
+    /// 待ち, if the hand is ready.
+    pub fn waits(&self) -> Option<&[AcceptedTile]> {
+        if self.shanten == 0 {
+            Some(&self.tiles)
+        } else {
+            None
+        }
+    }
+
+    /// Candidates from the best: the least shanten, then the most remaining copies.
+    ///
+    /// `concealed_tiles` includes the drawn tile. Physically identical tiles are listed once.
+    pub fn rank_discards(
+        concealed_tiles: &[Tile],
+        exposed_melds: &[Meld],
+        visible: &Histogram,
+    ) -> Vec<DiscardCandidate> {
+        let histogram = histogram_of(concealed_tiles);
+        concealed_tiles
+            .iter()
+            .unique()
+            .map(|tile| {
+                let mut rest = histogram;
+                rest[tile.kind().index()] -= 1;
+                DiscardCandidate {
+                    tile: *tile,
+                    acceptance: Acceptance::from_histogram(&rest, exposed_melds.len(), visible),
+                }
+            })
+            .sorted_by_key(|c| (c.acceptance.shanten, -(c.acceptance.total() as i64)))
+            .collect()
+    }
+}
+
+/// Tiles the seat of the view can see: its own hand, discards, exposed melds, extracted bonus
+/// tiles and reward indication tiles. A called discard is counted once in the meld.
+pub fn visible_histogram(view: &TableView<StandardConcept>) -> Histogram {
+    let mut histogram = [0; TILE_KINDS_COUNT];
+    let seats_tiles = view.seats.iter().flat_map(|seat| {
+        let discarded = seat
+            .discarded_tiles
+            .iter()
+            .filter(|(_, called)| !*called)
+            .map(|(tile, _)| *tile);
+        let melded = seat.exposed_melds.iter().flat_map(|meld| meld.tiles());
+        discarded
+            .chain(melded)
+            .chain(seat.bonus_tiles.iter().copied())
+    });
+    let tiles = view
+        .concealed_tiles
+        .iter()
+        .copied()
+        .chain(view.reward_indication_tiles.iter().copied())
+        .chain(seats_tiles);
+    for tile in tiles {
+        let count = &mut histogram[tile.kind().index()];
+        *count = (*count + 1).min(COPIES_COUNT);
+    }
+    histogram
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule::tiles_of;
+
+    fn acceptance_of(concealed_tiles: &str) -> Acceptance {
+        let tiles = tiles_of(concealed_tiles);
+        Acceptance::of(&tiles, &[], &histogram_of(&tiles))
+    }
+
+    #[test]
+    fn thirteen_orphans_waits_on_thirteen_kinds() {
+        let acceptance = acceptance_of("19m19p19s1234567z");
+        assert_eq!(acceptance.waits().unwrap().len(), 13);
+        assert_eq!(acceptance.total(), 13 * 3);
+    }
+
+    #[test]
+    fn kind_held_four_times_is_not_waited_on() {
+        // 空聴
+        let acceptance = acceptance_of("1111m234p567s789s");
+        assert_eq!(acceptance.shanten, 0);
+        assert_eq!(acceptance.waits(), Some(&[][..]));
+    }
+
+    #[test]
+    fn visible_copies_are_not_remaining() {
+        let tiles = tiles_of("123m456p789s23s55z");
+        let mut visible = histogram_of(&tiles);
+        visible[tiles_of("1s")[0].kind().index()] += 4;
+        let acceptance = Acceptance::of(&tiles, &[], &visible);
+        let remaining = acceptance.tiles.iter().map(|t| t.remaining).collect_vec();
+        assert_eq!(remaining, vec![0, 4]);
+        assert_eq!(acceptance.total(), 4);
+    }
+
+    #[test]
+    fn discard_keeping_the_most_remaining_is_the_best() {
+        let tiles = tiles_of("1239m456p23789s55z");
+        let candidates = Acceptance::rank_discards(&tiles, &[], &histogram_of(&tiles));
+        // 55z are listed once.
+        assert_eq!(candidates.len(), 13);
+        assert_eq!(candidates[0].tile, tiles_of("9m")[0]);
+        assert_eq!(candidates[0].acceptance.shanten, 0);
+        assert_eq!(candidates[0].acceptance.total(), 8);
+        assert!(candidates[1..]
+            .iter()
+            .all(|c| c.acceptance.shanten > 0 || c.acceptance.total() < 8));
+    }
+}
@@ -0,0 +1,404 @@
+use crate::game::MeldConcept;
+use crate::rule::meld::Meld;
+use crate::rule::tile::{histogram_of, Histogram, Tile, TileKind};
+use itertools::Itertools;
+use serde::Serialize;
+
+/// An interpretation of a completed hand. Yaku and fu depend on which one is chosen.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+pub struct Decomposition {
+    pub form: HandForm,
+    pub winning_tile: Tile,
+    /// Where the winning tile is in the form.
+    pub winning_position: WinningPosition,
+    pub wait: Wait,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HandForm {
+    /// 4面子1雀頭. The groups include the exposed melds, in the order of concealed ones first.
+    Normal { pair: TileKind, groups: Vec<Group> },
+    /// 七対子, in the order of kinds.
+    SevenPairs { pairs: Vec<TileKind> },
+    /// 国士無双. The pair is the kind held twice.
+    ThirteenOrphans { pair: TileKind },
+}
+
+/// 面子
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize)]
+pub struct Group {
+    pub shape: GroupShape,
+    /// The lowest kind, for a sequence.
+    pub kind: TileKind,
+    /// Not called, counting 暗槓. A triplet completed by 栄和 is still concealed here.
+    pub is_concealed: bool,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupShape {
+    /// 順子
+    Sequence,
+    /// 刻子
+    Triplet,
+    /// 槓子
+    Quad,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WinningPosition {
+    /// The index of the group in `HandForm::Normal`.
+    Group(usize),
+    /// The pair, or one of the pairs of `HandForm::SevenPairs`.
+    Pair(TileKind),
+}
+
+/// 待ちの形
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Wait {
+    /// 両面
+    BothSides,
+    /// 嵌張
+    Closed,
+    /// 辺張
+    Edge,
+    /// 双碰
+    DualPair,
+    /// 単騎
+    Single,
+    /// 国士無双十三面待ち
+    ThirteenSided,
+}
+
+impl HandForm {
+    /// Each tile's kind in the form.
+    pub fn kinds(&self) -> Vec<TileKind> {
+        match self {
+            HandForm::Normal { pair, groups } => groups
+                .iter()
+                .flat_map(Group::kinds)
+                .chain(vec![*pair; 2])
+                .collect(),
+            HandForm::SevenPairs { pairs } => pairs.iter().flat_map(|k| vec![*k; 2]).collect(),
+            HandForm::ThirteenOrphans { pair } => TileKind::all()
+                .filter(TileKind::is_terminal_or_honor)
+                .chain(Some(*pair))
+                .collect(),
+        }
+    }
+}
+
+impl Group {
+    pub fn kinds(&self) -> Vec<TileKind> {
+        match (self.shape, self.kind) {
+            (GroupShape::Sequence, TileKind::Suited(suit, n)) => {
+                (n..n + 3).map(|n| TileKind::Suited(suit, n)).collect()
+            }
+            (GroupShape::Sequence, _) => unreachable!("A sequence should be suited"),
+            (GroupShape::Triplet, kind) => vec![kind; 3],
+            (GroupShape::Quad, kind) => vec![kind; 4],
+        }
+    }
+
+    pub fn contains(&self, kind: TileKind) -> bool {
+        self.kinds().contains(&kind)
+    }
+
+    pub(crate) fn from_meld(meld: &Meld) -> Group {
+        let kind = meld
+            .tiles()
+            .iter()
+            .map(Tile::kind)
+            .min()
+            .expect("A meld should have tiles");
+        let (shape, is_concealed) = match meld {
+            Meld::Chow { .. } => (GroupShape::Sequence, false),
+            Meld::Pong { .. } => (GroupShape::Triplet, false),
+            Meld::Kong { called_tile, .. } => (GroupShape::Quad, called_tile.is_none()),
+        };
+        Group {
+            shape,
+            kind,
+            is_concealed,
+        }
+    }
+
+    /// The wait when the group is completed by `kind`.
+    pub(crate) fn wait_on(&self, kind: TileKind) -> Wait {
+        match (self.shape, self.kind, kind) {
+            (GroupShape::Sequence, TileKind::Suited(_, low), TileKind::Suited(_, n)) => {
+                if n == low + 1 {
+                    Wait::Closed
+                } else if (n == low + 2 && low == 1) || (n == low && low == 7) {
+                    Wait::Edge
+                } else {
+                    Wait::BothSides
+                }
+            }
+            _ => Wait::DualPair,
+        }
+    }
+}
+
+/// Every interpretation of the hand completed by `winning_tile`.
+///
+/// `concealed_tiles` excludes the winning tile, even if drawn. Exposed melds, including 暗槓,
+/// count as groups. Empty if the hand is not completed.
+///
+/// ```
+/// use mahjong_enterprise_edition_core::rule::{decompose, HandForm, Suit, Tile, TileKind};
+///
+/// // 二盃口, which is also 七対子
+/// let tiles = [1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 9]
+///     .iter()
+///     .map(|n| Tile::new(TileKind::Suited(Suit::Circle, *n)))
+///     .collect::<Vec<_>>();
+/// let winning_tile = Tile::new(TileKind::Suited(Suit::Circle, 9));
+/// let decompositions = decompose(&tiles, &[], winning_tile);
+/// assert_eq!(decompositions.len(), 2);
+/// assert!(decompositions
+///     .iter()
+///     .any(|d| matches!(d.form, HandForm::SevenPairs { .. })));
+/// ```
+pub fn decompose(
+    concealed_tiles: &[Tile],
+    exposed_melds: &[Meld],
+    winning_tile: Tile,
+) -> Vec<Decomposition> {
+    let waiting_histogram = histogram_of(concealed_tiles);
+    let mut histogram = waiting_histogram;
+    histogram[winning_tile.kind().index()] += 1;
+    let exposed_groups = exposed_melds.iter().map(Group::from_meld).collect_vec();
+    let winning_kind = winning_tile.kind();
+
+    let mut decompositions = vec![];
+
+    if exposed_groups.len() <= GROUPS_COUNT
+        && histogram.iter().map(|n| *n as usize).sum::<usize>()
+            == PAIR_SIZE + GROUP_SIZE * (GROUPS_COUNT - exposed_groups.len())
+    {
+        for (pair, concealed_groups) in normal_forms(&histogram) {
+            let groups = concealed_groups
+                .into_iter()
+                .chain(exposed_groups.iter().copied())
+                .collect_vec();
+            let mut positions = groups
+                .iter()
+                .enumerate()
+                .filter(|(_, g)| g.is_concealed && g.shape != GroupShape::Quad)
+                .filter(|(_, g)| g.contains(winning_kind))
+                // Identical groups make the same interpretation.
+                .unique_by(|(_, g)| **g)
+                .map(|(i, g)| (WinningPosition::Group(i), g.wait_on(winning_kind)))
+                .collect_vec();
+            if pair == winning_kind {
+                positions.push((WinningPosition::Pair(pair), Wait::Single));
+            }
+            for (winning_position, wait) in positions {
+                decompositions.push(Decomposition {
+                    form: HandForm::Normal {
+                        pair,
+                        groups: groups.clone(),
+                    },
+                    winning_tile,
+                    winning_position,
+                    wait,
+                });
+            }
+        }
+    }
+
+    if exposed_melds.is_empty() {
+        if let Some(pairs) = seven_pairs(&histogram) {
+            decompositions.push(Decomposition {
+                form: HandForm::SevenPairs { pairs },
+                winning_tile,
+                winning_position: WinningPosition::Pair(winning_kind),
+                wait: Wait::Single,
+            });
+        }
+        if let Some(pair) = thirteen_orphans(&histogram) {
+            let is_thirteen_sided = TileKind::all()
+                .filter(TileKind::is_terminal_or_honor)
+                .all(|kind| waiting_histogram[kind.index()] == 1);
+            decompositions.push(Decomposition {
+                form: HandForm::ThirteenOrphans { pair },
+                winning_tile,
+                winning_position: WinningPosition::Pair(pair),
+                wait: if is_thirteen_sided {
+                    Wait::ThirteenSided
+                } else {
+                    Wait::Single
+                },
+            });
+        }
+    }
+
+    decompositions
+}
+
+const GROUPS_COUNT: usize = 4;
+const GROUP_SIZE: usize = 3;
+const PAIR_SIZE: usize = 2;
+
+/// Every pair and concealed groups making up the histogram.
+pub(crate) fn normal_forms(histogram: &Histogram) -> Vec<(TileKind, Vec<Group>)> {
+    (0..histogram.len())
+        .filter(|i| histogram[*i] >= 2)
+        .flat_map(|i| {
+            let mut rest = *histogram;
+            rest[i] -= 2;
+            let mut found = vec![];
+            collect_groups(&mut rest, &mut vec![], &mut found);
+            found
+                .into_iter()
+                .map(move |groups| (TileKind::from_index(i), groups))
+        })
+        .collect()
+}
+
+/// Groups are taken from the lowest kind, so that each combination is found once.
+fn collect_groups(histogram: &mut Histogram, groups: &mut Vec<Group>, found: &mut Vec<Vec<Group>>) {
+    let i = match histogram.iter().position(|n| *n > 0) {
+        Some(i) => i,
+        None => {
+            found.push(groups.clone());
+            return;
+        }
+    };
+    let kind = TileKind::from_index(i);
+
+    if histogram[i] >= 3 {
+        histogram[i] -= 3;
+        groups.push(Group {
+            shape: GroupShape::Triplet,
+            kind,
+            is_concealed: true,
+        });
+        collect_groups(histogram, groups, found);
+        groups.pop();
+        histogram[i] += 3;
+    }
+
+    if matches!(kind, TileKind::Suited(_, n) if n <= 7)
+        && histogram[i + 1] > 0
+        && histogram[i + 2] > 0
+    {
+        histogram[i] -= 1;
+        histogram[i + 1] -= 1;
+        histogram[i + 2] -= 1;
+        groups.push(Group {
+            shape: GroupShape::Sequence,
+            kind,
+            is_concealed: true,
+        });
+        collect_groups(histogram, groups, found);
+        groups.pop();
+        histogram[i] += 1;
+        histogram[i + 1] += 1;
+        histogram[i + 2] += 1;
+    }
+}
+
+/// 七対子 (同じ牌4枚は2対子と見なさない)
+fn seven_pairs(histogram: &Histogram) -> Option<Vec<TileKind>> {
+    let pairs = TileKind::all()
+        .filter(|kind| histogram[kind.index()] == 2)
+        .collect_vec();
+    (pairs.len() == 7).then_some(pairs)
+}
+
+/// 国士無双
+pub(crate) fn thirteen_orphans(histogram: &Histogram) -> Option<TileKind> {
+    let orphans = TileKind::all()
+        .filter(TileKind::is_terminal_or_honor)
+        .collect_vec();
+    let tiles_count = orphans.iter().map(|k| histogram[k.index()]).sum::<u8>();
+    if tiles_count != 14 || orphans.iter().any(|k| histogram[k.index()] == 0) {
+        return None;
+    }
+    orphans
+        .into_iter()
+        .find(|kind| histogram[kind.index()] == 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule::tiles_of;
+
+    fn decompositions_of(concealed_tiles: &str, winning_tile: &str) -> Vec<Decomposition> {
+        decompose(&tiles_of(concealed_tiles), &[], tiles_of(winning_tile)[0])
+    }
+
+    fn waits_of(concealed_tiles: &str, winning_tile: &str) -> Vec<Wait> {
+        decompositions_of(concealed_tiles, winning_tile)
+            .into_iter()
+            .map(|d| d.wait)
+            .collect()
+    }
+
+    #[test]
+    fn two_double_sequences_is_also_seven_pairs() {
+        let decompositions = decompositions_of("1223344556699p", "1p");
+        assert_eq!(decompositions.len(), 2);
+        assert!(decompositions.iter().any(|d| matches!(
+            &d.form,
+            HandForm::Normal { pair, groups }
+                if *pair == tiles_of("9p")[0].kind()
+                    && groups.iter().all(|g| g.shape == GroupShape::Sequence)
+        )));
+        assert!(decompositions
+            .iter()
+            .any(|d| matches!(&d.form, HandForm::SevenPairs { pairs } if pairs.len() == 7)));
+    }
+
+    #[test]
+    fn same_kind_four_times_is_not_two_pairs() {
+        assert!(decompositions_of("1111223344556z", "6z").is_empty());
+    }
+
+    #[test]
+    fn triplets_and_sequences_are_both_interpretations() {
+        let decompositions = decompositions_of("111222333m456p7s", "7s");
+        assert_eq!(decompositions.len(), 2);
+        assert!(decompositions.iter().all(|d| d.wait == Wait::Single));
+    }
+
+    #[test]
+    fn wait_is_told_by_the_shape_completed() {
+        assert_eq!(waits_of("13m456m789m234p55s", "2m"), vec![Wait::Closed]);
+        assert_eq!(waits_of("12m456m789m234p55s", "3m"), vec![Wait::Edge]);
+        assert_eq!(waits_of("89m123m456m234p55s", "7m"), vec![Wait::Edge]);
+        assert_eq!(waits_of("23m456m789m234p55s", "1m"), vec![Wait::BothSides]);
+        assert_eq!(waits_of("11m456m789m234p55s", "1m"), vec![Wait::DualPair]);
+        assert_eq!(waits_of("123m456m789m234p5s", "5s"), vec![Wait::Single]);
+    }
+
+    #[test]
+    fn thirteen_orphans_waiting_on_thirteen_kinds_is_thirteen_sided() {
+        assert_eq!(
+            waits_of("19m19p19s1234567z", "1m"),
+            vec![Wait::ThirteenSided]
+        );
+        assert_eq!(waits_of("119m19p19s123456z", "7z"), vec![Wait::Single]);
+    }
+
+    #[test]
+    fn exposed_melds_count_as_groups() {
+        let tiles = tiles_of("777z");
+        let pong = Meld::Pong {
+            tiles: [tiles[0], tiles[1], tiles[2]],
+            called_tile: tiles[2],
+        };
+        let decompositions = decompose(&tiles_of("123m456p789s5s"), &[pong], tiles_of("5s")[0]);
+        assert_eq!(decompositions.len(), 1);
+        assert!(matches!(
+            &decompositions[0].form,
+            HandForm::Normal { groups, .. } if groups.len() == 4 && !groups[3].is_concealed
+        ));
+    }
+}
@@ -0,0 +1,633 @@
+use crate::game::{
+    Ledger, LedgerEntry, MeldConcept, PaymentReason, PlayersCount, Round, RuleConfig, Seat,
+    WinningHand,
+};
+use crate::rule::decomposition::{Decomposition, GroupShape, Wait};
+use crate::rule::fu::{Fu, FuItem};
+use crate::rule::meld::Meld;
+use crate::rule::point::{HandValue, Limit};
+use crate::rule::tile::{Dragon, Suit, Tile, TileKind, Wind};
+use crate::rule::yaku::{NormalYaku, WinContext, YakuEvaluator, YakumanYaku};
+use crate::rule::StandardConcept;
+use itertools::Itertools;
+use serde::Serialize;
+use std::fmt::Write;
+
+/// Why a completed hand is worth its points, for UIs. Serializable to JSON.
+///
+/// ```
+/// use mahjong_enterprise_edition_core::game::{RuleConfig, Seat, WinningHand, Round};
+/// use mahjong_enterprise_edition_core::rule::{
+///     Language, ScoreExplanation, StandardConcept, Suit, Tile, TileKind,
+/// };
+///
+/// let tiles = |suit, numbers: &[u8]| {
+///     numbers
+///         .iter()
+///         .map(|n| Tile::new(TileKind::Suited(suit, *n)))
+///         .collect::<Vec<_>>()
+/// };
+/// let concealed_tiles = [
+///     tiles(Suit::Character, &[2, 3, 4, 6, 7]),
+///     tiles(Suit::Circle, &[3, 4, 5, 5, 5]),
+///     tiles(Suit::Bamboo, &[4, 5, 6]),
+/// ]
+/// .concat();
+/// let hand = WinningHand::<StandardConcept> {
+///     concealed_tiles,
+///     exposed_melds: vec![],
+///     winning_tile: Tile::new(TileKind::Suited(Suit::Character, 8)),
+///     bonus_tiles: vec![],
+///     seat: Seat::South,
+///     dealer: Seat::East,
+///     round: Round::East,
+///     discarder: Some(Seat::West),
+///     is_ready_declared: true,
+///     is_ready_declared_first: false,
+///     is_right_after_ready: false,
+///     is_last_tile: false,
+///     is_supplemental_tile: false,
+///     is_first_draw: false,
+///     visible_tiles: vec![],
+///     reward_indication_tiles: vec![Tile::new(TileKind::Suited(Suit::Circle, 4))],
+///     hidden_reward_indication_tiles: vec![],
+///     liable_seat: None,
+/// };
+/// let explanation = ScoreExplanation::of(&hand, &RuleConfig::default()).unwrap();
+/// assert_eq!((explanation.value.han, explanation.value.fu), (6, 30));
+/// assert_eq!(explanation.dora.dora, 3);
+/// assert_eq!(explanation.basic_points, 3000);
+///
+/// let json = serde_json::to_value(&explanation).unwrap();
+/// assert_eq!(json["limit"], "haneman");
+/// assert_eq!(json["yaku"][0][0], "riichi");
+/// assert!(explanation.summary(Language::English).contains("Haneman"));
+/// assert!(explanation.summary(Language::Japanese).contains("跳満"));
+/// ```
+#[derive(Clone, Debug, Serialize)]
+pub struct ScoreExplanation {
+    pub seat: Seat,
+    /// The interpretation of the highest value.
+    pub decomposition: Decomposition,
+    /// Each yaku with its 飜数. Empty if any yakuman is found.
+    pub yaku: Vec<(NormalYaku, u8)>,
+    /// Each yakuman with how many times of a yakuman it is worth.
+    pub yakuman: Vec<(YakumanYaku, u8)>,
+    /// Yaku registered to the evaluator by name, with its 飜数. Empty if any yakuman is found.
+    pub registered_yaku: Vec<(String, u8)>,
+    /// Yakuman registered to the evaluator by name, with how many times of a yakuman it is worth.
+    pub registered_yakuman: Vec<(String, u8)>,
+    pub dora: DoraCount,
+    pub fu: Fu,
+    pub value: HandValue,
+    pub limit: Option<Limit>,
+    /// 基本点
+    pub basic_points: i32,
+    /// Points paid to the seat. Empty until `with_payments`.
+    pub payments: Vec<LedgerEntry>,
+}
+
+/// ドラ by source. They count as 飜 unless the hand is a yakuman.
+#[derive(Copy, Clone, Default, Eq, PartialEq, Debug, Serialize)]
+pub struct DoraCount {
+    /// ドラ, including 槓ドラ
+    pub dora: u8,
+    /// 裏ドラ
+    pub ura_dora: u8,
+    /// 赤ドラ
+    pub red_fives: u8,
+    /// 抜きドラ
+    pub bonus_tiles: u8,
+}
+
+impl DoraCount {
+    pub fn total(&self) -> u8 {
+        self.dora + self.ura_dora + self.red_fives + self.bonus_tiles
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Language {
+    Japanese,
+    English,
+}
+
+impl ScoreExplanation {
+    /// The standard yaku with ドラ, in the normal point system. `None` without yaku.
+    pub fn of(hand: &WinningHand<StandardConcept>, rule: &RuleConfig) -> Option<Self> {
+        Self::evaluated_by(&YakuEvaluator::new(rule), hand, rule)
+    }
+
+    /// The yaku and point system of the evaluator, e.g. with registered yaku or without limits.
+    pub fn evaluated_by(
+        evaluator: &YakuEvaluator,
+        hand: &WinningHand<StandardConcept>,
+        rule: &RuleConfig,
+    ) -> Option<Self> {
+        let dora = dora_count_of(hand, rule.players_count);
+        let evaluation = evaluator.evaluate_with_dora(
+            &hand.concealed_tiles,
+            &hand.exposed_melds,
+            hand.winning_tile,
+            &context_of(hand, rule.players_count),
+            dora.total(),
+        )?;
+        let value = HandValue::of(&evaluation, dora.total());
+        let basic_points = evaluator.point_system().basic_points(&value);
+        Some(ScoreExplanation {
+            seat: hand.seat,
+            decomposition: evaluation.decomposition,
+            yaku: evaluation.yaku,
+            yakuman: evaluation.yakuman,
+            registered_yaku: evaluation.registered_yaku,
+            registered_yakuman: evaluation.registered_yakuman,
+            dora,
+            fu: evaluation.fu,
+            value,
+            limit: basic_points.limit,
+            basic_points: basic_points.points,
+            payments: vec![],
+        })
+    }
+
+    /// Takes the payments to the seat from the ledger of the ended hand.
+    pub fn with_payments(self, ledger: &Ledger) -> Self {
+        let seat = self.seat;
+        ScoreExplanation {
+            payments: ledger
+                .entries
+                .iter()
+                .filter(|entry| entry.payee == seat)
+                .copied()
+                .collect(),
+            ..self
+        }
+    }
+
+    /// One line for each of yaku, ドラ, 符 and payments, then the total.
+    pub fn summary(&self, language: Language) -> String {
+        let ja = language == Language::Japanese;
+        let mut lines = vec![];
+        for (yakuman, multiplier) in &self.yakuman {
+            lines.push(format!(
+                "{} {}",
+                yakuman_name(*yakuman, ja),
+                limit_name(Limit::Yakuman(*multiplier), ja)
+            ));
+        }
+        for (name, multiplier) in &self.registered_yakuman {
+            lines.push(format!(
+                "{} {}",
+                name,
+                limit_name(Limit::Yakuman(*multiplier), ja)
+            ));
+        }
+        for (yaku, han) in &self.yaku {
+            lines.push(han_line(yaku_name(*yaku, ja), *han, ja));
+        }
+        for (name, han) in &self.registered_yaku {
+            lines.push(han_line(name, *han, ja));
+        }
+        if self.value.yakuman == 0 {
+            let dora = [
+                (self.dora.dora, if ja { "ドラ" } else { "Dora" }),
+                (self.dora.ura_dora, if ja { "裏ドラ" } else { "Ura dora" }),
+                (self.dora.red_fives, if ja { "赤ドラ" } else { "Red fives" }),
+                (
+                    self.dora.bonus_tiles,
+                    if ja { "抜きドラ" } else { "Bonus tiles" },
+                ),
+            ];
+            for (count, name) in dora.iter().filter(|(count, _)| *count > 0) {
+                lines.push(han_line(name, *count, ja));
+            }
+            for (item, fu) in &self.fu.items {
+                lines.push(if ja {
+                    format!("{} {}符", fu_item_name(*item, ja), fu)
+                } else {
+                    format!("{} {} fu", fu_item_name(*item, ja), fu)
+                });
+            }
+        }
+
+        let mut total = if self.value.yakuman > 0 {
+            String::new()
+        } else if ja {
+            format!("{}符{}飜 ", self.value.fu, self.value.han)
+        } else {
+            format!("{} fu {} han ", self.value.fu, self.value.han)
+        };
+        if let Some(limit) = self.limit {
+            write!(total, "{} ", limit_name(limit, ja)).unwrap();
+        }
+        if ja {
+            write!(total, "基本点{}", self.basic_points).unwrap();
+        } else {
+            write!(total, "{} basic points", self.basic_points).unwrap();
+        }
+        lines.push(total);
+
+        for entry in &self.payments {
+            let payer = match entry.payer {
+                Some(seat) => seat_name(seat, ja),
+                None => if ja { "卓" } else { "Table" }.to_string(),
+            };
+            lines.push(if ja {
+                format!(
+                    "{} → {} {}点 ({})",
+                    payer,
+                    seat_name(entry.payee, ja),
+                    entry.point,
+                    reason_name(entry.reason, ja)
+                )
+            } else {
+                format!(
+                    "{} -> {} {} ({})",
+                    payer,
+                    seat_name(entry.payee, ja),
+                    entry.point,
+                    reason_name(entry.reason, ja)
+                )
+            });
+        }
+        lines.join("\n")
+    }
+
+    /// 包 for 大三元 and 大四喜, in the part of `basic_points`.
+    pub(crate) fn liable_basic_points(&self) -> i32 {
+        let liable_multiplier = self
+            .yakuman
+            .iter()
+            .filter(|(yakuman, _)| {
+                matches!(yakuman, YakumanYaku::Daisangen | YakumanYaku::Daisuushii)
+            })
+            .map(|(_, multiplier)| *multiplier as i32)
+            .sum::<i32>();
+        self.basic_points * liable_multiplier / (self.value.yakuman as i32).max(1)
+    }
+}
+
+const WINDS: [Wind; 4] = [Wind::East, Wind::South, Wind::West, Wind::North];
+
+fn context_of(hand: &WinningHand<StandardConcept>, players_count: PlayersCount) -> WinContext {
+    let players_count = players_count.get();
+    let seat_index =
+        (usize::from(hand.seat) + players_count - usize::from(hand.dealer)) % players_count;
+    WinContext {
+        is_self_drawn: hand.discarder.is_none(),
+        seat_wind: WINDS[seat_index],
+        round_wind: match hand.round {
+            Round::East => Wind::East,
+            Round::South => Wind::South,
+            Round::West => Wind::West,
+            Round::North => Wind::North,
+        },
+        is_ready_declared: hand.is_ready_declared,
+        is_ready_declared_first: hand.is_ready_declared_first,
+        is_right_after_ready: hand.is_right_after_ready,
+        is_last_tile: hand.is_last_tile,
+        is_supplemental_tile: hand.is_supplemental_tile,
+        // No 加槓 is made at the table.
+        is_robbing_quad: false,
+        is_first_draw: hand.is_first_draw,
+    }
+}
+
+fn dora_count_of(hand: &WinningHand<StandardConcept>, players_count: PlayersCount) -> DoraCount {
+    let tiles = hand
+        .concealed_tiles
+        .iter()
+        .copied()
+        .chain(Some(hand.winning_tile))
+        .chain(hand.exposed_melds.iter().flat_map(Meld::tiles))
+        .chain(hand.bonus_tiles.iter().copied())
+        .collect_vec();
+    let indicated = |indication_tiles: &[Tile]| {
+        indication_tiles
+            .iter()
+            .map(|tile| match tile.kind() {
+                // 三麻 has no 2-8 of characters.
+                TileKind::Suited(Suit::Character, 1) if players_count == PlayersCount::THREE => {
+                    TileKind::Suited(Suit::Character, 9)
+                }
+                kind => kind.indicated_dora(),
+            })
+            .map(|kind| tiles.iter().filter(|t| t.kind() == kind).count())
+            .sum::<usize>() as u8
+    };
+    DoraCount {
+        dora: indicated(&hand.reward_indication_tiles),
+        ura_dora: indicated(&hand.hidden_reward_indication_tiles),
+        red_fives: tiles.iter().filter(|t| t.is_red()).count() as u8,
+        bonus_tiles: hand.bonus_tiles.len() as u8,
+    }
+}
+
+fn han_line(name: &str, han: u8, ja: bool) -> String {
+    if ja {
+        format!("{} {}飜", name, han)
+    } else {
+        format!("{} {} han", name, han)
+    }
+}
+
+fn yaku_name(yaku: NormalYaku, ja: bool) -> &'static str {
+    use NormalYaku::*;
+
+    let (ja_name, en_name) = match yaku {
+        Riichi => ("立直", "Riichi"),
+        DoubleRiichi => ("両立直", "Double riichi"),
+        Ippatsu => ("一発", "Ippatsu"),
+        MenzenTsumo => ("門前清自摸和", "Menzen tsumo"),
+        Pinfu => ("平和", "Pinfu"),
+        Tanyao => ("断么九", "Tanyao"),
+        Iipeikou => ("一盃口", "Iipeikou"),
+        SeatWind => ("自風牌", "Seat wind"),
+        RoundWind => ("場風牌", "Round wind"),
+        WhiteDragon => ("白", "White dragon"),
+        GreenDragon => ("發", "Green dragon"),
+        RedDragon => ("中", "Red dragon"),
+        Haitei => ("海底摸月", "Haitei"),
+        Houtei => ("河底撈魚", "Houtei"),
+        Rinshan => ("嶺上開花", "Rinshan kaihou"),
+        Chankan => ("槍槓", "Chankan"),
+        Chiitoitsu => ("七対子", "Chiitoitsu"),
+        Sanshoku => ("三色同順", "Sanshoku doujun"),
+        Ittsu => ("一気通貫", "Ittsu"),
+        Chanta => ("混全帯么九", "Chanta"),
+        Toitoi => ("対々和", "Toitoi"),
+        Sanankou => ("三暗刻", "Sanankou"),
+        SanshokuDoukou => ("三色同刻", "Sanshoku doukou"),
+        Sankantsu => ("三槓子", "Sankantsu"),
+        Shousangen => ("小三元", "Shousangen"),
+        Honroutou => ("混老頭", "Honroutou"),
+        Ryanpeikou => ("二盃口", "Ryanpeikou"),
+        Honitsu => ("混一色", "Honitsu"),
+        Junchan => ("純全帯么九", "Junchan"),
+        Chinitsu => ("清一色", "Chinitsu"),
+    };
+    if ja {
+        ja_name
+    } else {
+        en_name
+    }
+}
+
+fn yakuman_name(yakuman: YakumanYaku, ja: bool) -> &'static str {
+    use YakumanYaku::*;
+
+    let (ja_name, en_name) = match yakuman {
+        Tenhou => ("天和", "Tenhou"),
+        Chiihou => ("地和", "Chiihou"),
+        KokushiMusou => ("国士無双", "Kokushi musou"),
+        KokushiMusouJuusanmen => ("国士無双十三面待ち", "Kokushi musou juusanmen"),
+        Suuankou => ("四暗刻", "Suuankou"),
+        SuuankouTanki => ("四暗刻単騎", "Suuankou tanki"),
+        Daisangen => ("大三元", "Daisangen"),
+        Shousuushii => ("小四喜", "Shousuushii"),
+        Daisuushii => ("大四喜", "Daisuushii"),
+        Tsuuiisou => ("字一色", "Tsuuiisou"),
+        Ryuuiisou => ("緑一色", "Ryuuiisou"),
+        Chinroutou => ("清老頭", "Chinroutou"),
+        ChuurenPoutou => ("九蓮宝燈", "Chuuren poutou"),
+        JunseiChuurenPoutou => ("純正九蓮宝燈", "Junsei chuuren poutou"),
+        Suukantsu => ("四槓子", "Suukantsu"),
+    };
+    if ja {
+        ja_name
+    } else {
+        en_name
+    }
+}
+
+fn fu_item_name(item: FuItem, ja: bool) -> String {
+    match item {
+        FuItem::Base => (if ja { "副底" } else { "Base" }).to_string(),
+        FuItem::SevenPairs => (if ja { "七対子" } else { "Seven pairs" }).to_string(),
+        FuItem::ConcealedRon => (if ja { "門前加符" } else { "Concealed ron" }).to_string(),
+        FuItem::SelfDrawn => (if ja { "自摸" } else { "Self-drawn" }).to_string(),
+        FuItem::Group(group) => {
+            let shape = match (group.shape, group.is_concealed, ja) {
+                (GroupShape::Quad, true, true) => "暗槓",
+                (GroupShape::Quad, false, true) => "明槓",
+                (_, true, true) => "暗刻",
+                (_, false, true) => "明刻",
+                (GroupShape::Quad, true, false) => "Concealed quad",
+                (GroupShape::Quad, false, false) => "Open quad",
+                (_, true, false) => "Concealed triplet",
+                (_, false, false) => "Open triplet",
+            };
+            format!("{} {}", shape, kind_name(group.kind, ja))
+        }
+        FuItem::Pair(kind) => format!(
+            "{} {}",
+            if ja { "雀頭" } else { "Pair" },
+            kind_name(kind, ja)
+        ),
+        FuItem::Wait(wait) => match (wait, ja) {
+            (Wait::Closed, true) => "嵌張",
+            (Wait::Edge, true) => "辺張",
+            (_, true) => "単騎",
+            (Wait::Closed, false) => "Closed wait",
+            (Wait::Edge, false) => "Edge wait",
+            (_, false) => "Single wait",
+        }
+        .to_string(),
+        FuItem::OpenPinfu => (if ja { "喰い平和" } else { "Open pinfu" }).to_string(),
+    }
+}
+
+fn kind_name(kind: TileKind, ja: bool) -> String {
+    match kind {
+        TileKind::Suited(suit, number) => {
+            let suit = match (suit, ja) {
+                (Suit::Character, true) => "萬",
+                (Suit::Circle, true) => "筒",
+                (Suit::Bamboo, true) => "索",
+                (Suit::Character, false) => "m",
+                (Suit::Circle, false) => "p",
+                (Suit::Bamboo, false) => "s",
+            };
+            format!("{}{}", number, suit)
+        }
+        TileKind::Wind(wind) => wind_name(wind, ja).to_string(),
+        TileKind::Dragon(dragon) => match (dragon, ja) {
+            (Dragon::White, true) => "白",
+            (Dragon::Green, true) => "發",
+            (Dragon::Red, true) => "中",
+            (Dragon::White, false) => "White",
+            (Dragon::Green, false) => "Green",
+            (Dragon::Red, false) => "Red",
+        }
+        .to_string(),
+    }
+}
+
+fn wind_name(wind: Wind, ja: bool) -> &'static str {
+    match (wind, ja) {
+        (Wind::East, true) => "東",
+        (Wind::South, true) => "南",
+        (Wind::West, true) => "西",
+        (Wind::North, true) => "北",
+        (Wind::East, false) => "East",
+        (Wind::South, false) => "South",
+        (Wind::West, false) => "West",
+        (Wind::North, false) => "North",
+    }
+}
+
+fn seat_name(seat: Seat, ja: bool) -> String {
+    let wind = wind_name(WINDS[usize::from(seat)], ja);
+    if ja {
+        format!("{}家", wind)
+    } else {
+        wind.to_string()
+    }
+}
+
+fn limit_name(limit: Limit, ja: bool) -> String {
+    match (limit, ja) {
+        (Limit::Mangan, true) => "満貫".to_string(),
+        (Limit::Haneman, true) => "跳満".to_string(),
+        (Limit::Baiman, true) => "倍満".to_string(),
+        (Limit::Sanbaiman, true) => "三倍満".to_string(),
+        (Limit::Yakuman(1), true) => "役満".to_string(),
+        (Limit::Yakuman(2), true) => "ダブル役満".to_string(),
+        (Limit::Yakuman(times), true) => format!("{}倍役満", times),
+        (Limit::Mangan, false) => "Mangan".to_string(),
+        (Limit::Haneman, false) => "Haneman".to_string(),
+        (Limit::Baiman, false) => "Baiman".to_string(),
+        (Limit::Sanbaiman, false) => "Sanbaiman".to_string(),
+        (Limit::Yakuman(1), false) => "Yakuman".to_string(),
+        (Limit::Yakuman(2), false) => "Double yakuman".to_string(),
+        (Limit::Yakuman(times), false) => format!("{}x yakuman", times),
+    }
+}
+
+fn reason_name(reason: PaymentReason, ja: bool) -> &'static str {
+    match (reason, ja) {
+        (PaymentReason::Completion, true) => "和了",
+        (PaymentReason::Liability, true) => "包",
+        (PaymentReason::Counters, true) => "積み棒",
+        (PaymentReason::Deposits, true) => "供託",
+        (PaymentReason::NotReadyPenalty, true) => "不聴罰符",
+        (PaymentReason::NagashiMangan, true) => "流し満貫",
+        (PaymentReason::Completion, false) => "Completion",
+        (PaymentReason::Liability, false) => "Liability",
+        (PaymentReason::Counters, false) => "Counters",
+        (PaymentReason::Deposits, false) => "Deposits",
+        (PaymentReason::NotReadyPenalty, false) => "Not ready penalty",
+        (PaymentReason::NagashiMangan, false) => "Nagashi mangan",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::HandScore;
+    use crate::rule::yaku::ConcealedAllSimples;
+    use crate::rule::{tiles_of, winning_hand_of};
+
+    #[test]
+    fn dora_are_counted_by_source() {
+        let hand = WinningHand {
+            is_ready_declared: true,
+            reward_indication_tiles: tiles_of("4s"),
+            hidden_reward_indication_tiles: tiles_of("8m"),
+            ..winning_hand_of("234m999m406p678s5s", "5s", Some(Seat::West))
+        };
+        let explanation = ScoreExplanation::of(&hand, &RuleConfig::default()).unwrap();
+        assert_eq!(
+            explanation.dora,
+            DoraCount {
+                dora: 2,
+                ura_dora: 3,
+                red_fives: 1,
+                bonus_tiles: 0,
+            }
+        );
+        // 立直 and ドラ6
+        assert_eq!(explanation.value.han, 7);
+        assert_eq!(explanation.limit, Some(Limit::Haneman));
+    }
+
+    #[test]
+    fn one_of_characters_indicates_nine_with_three_players() {
+        let hand = WinningHand {
+            is_ready_declared: true,
+            reward_indication_tiles: tiles_of("1m"),
+            ..winning_hand_of("234m999m456p678s5s", "5s", Some(Seat::West))
+        };
+        let rule = RuleConfig {
+            players_count: PlayersCount::THREE,
+            ..RuleConfig::default()
+        };
+        assert_eq!(ScoreExplanation::of(&hand, &rule).unwrap().dora.dora, 3);
+        // 2m otherwise
+        let rule = RuleConfig::default();
+        assert_eq!(ScoreExplanation::of(&hand, &rule).unwrap().dora.dora, 1);
+    }
+
+    #[test]
+    fn yakuman_ignores_dora() {
+        let hand = WinningHand {
+            reward_indication_tiles: tiles_of("1z"),
+            ..winning_hand_of("555666777111z2z", "2z", Some(Seat::West))
+        };
+        let explanation = ScoreExplanation::of(&hand, &RuleConfig::default()).unwrap();
+        assert_eq!(explanation.value.han, 0);
+        // 字一色, 大三元 and 四暗刻単騎
+        assert_eq!(explanation.value.yakuman, 4);
+        assert_eq!(explanation.basic_points, 32000);
+        assert!(!explanation.summary(Language::English).contains("Dora"));
+        // 包 is for 大三元 only.
+        assert_eq!(explanation.liable_basic_points(), 8000);
+    }
+
+    #[test]
+    fn summary_lists_payments_to_the_seat() {
+        let rule = RuleConfig::default();
+        let hand = WinningHand {
+            is_ready_declared: true,
+            ..winning_hand_of("234m999m456p678s5s", "5s", Some(Seat::West))
+        };
+        let explanation = ScoreExplanation::of(&hand, &rule).unwrap();
+        let score = HandScore {
+            basic_points: explanation.basic_points,
+            liable_basic_points: 0,
+        };
+        let ledger = Ledger::completion(&rule, &hand, &score, 1, 1000);
+        let explanation = explanation.with_payments(&ledger);
+        assert_eq!(explanation.payments.len(), 3);
+
+        let summary = explanation.summary(Language::English);
+        assert!(summary.contains("Riichi 1 han"));
+        assert!(summary.contains("40 fu 1 han 320 basic points"));
+        assert!(summary.contains("West -> South 1300 (Completion)"));
+        assert!(summary.contains("West -> South 300 (Counters)"));
+        assert!(summary.contains("Table -> South 1000 (Deposits)"));
+        let summary = explanation.summary(Language::Japanese);
+        assert!(summary.contains("40符1飜 基本点320"));
+        assert!(summary.contains("西家 → 南家 1300点 (和了)"));
+    }
+
+    #[test]
+    fn summary_lists_registered_yaku() {
+        let rule = RuleConfig::default();
+        let mut evaluator = YakuEvaluator::new(&rule);
+        evaluator.register(Box::new(ConcealedAllSimples));
+        let hand = winning_hand_of("234m456p678s2288s", "8s", Some(Seat::West));
+        let explanation = ScoreExplanation::evaluated_by(&evaluator, &hand, &rule).unwrap();
+        assert!(explanation.yaku.is_empty());
+        assert_eq!(
+            explanation.registered_yaku,
+            vec![("concealed-all-simples".to_string(), 2)]
+        );
+        assert!(explanation
+            .summary(Language::English)
+            .contains("concealed-all-simples 2 han"));
+        assert!(explanation
+            .summary(Language::Japanese)
+            .contains("concealed-all-simples 2飜"));
+    }
+}
@@ -0,0 +1,363 @@
+use crate::game::RuleConfig;
+use crate::rule::decomposition::{
+    Decomposition, Group, GroupShape, HandForm, Wait, WinningPosition,
+};
+use crate::rule::tile::TileKind;
+use crate::rule::yaku::WinContext;
+use serde::Serialize;
+
+/// 符, itemized.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize)]
+pub struct Fu {
+    /// Each item with its 符. Items worth nothing are omitted.
+    pub items: Vec<(FuItem, u8)>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FuItem {
+    /// 副底
+    Base,
+    /// 七対子, which is always 25 fu.
+    SevenPairs,
+    /// 門前加符
+    ConcealedRon,
+    /// 自摸符
+    SelfDrawn,
+    /// 明刻・暗刻・明槓・暗槓. A triplet completed by 栄和 is not concealed here.
+    Group(Group),
+    /// 役牌の雀頭
+    Pair(TileKind),
+    /// 嵌張・辺張・単騎
+    Wait(Wait),
+    /// 喰い平和. An open hand without other fu is counted as 30 fu.
+    OpenPinfu,
+}
+
+impl Fu {
+    /// The sum of the items.
+    pub fn sum(&self) -> u8 {
+        self.items.iter().map(|(_, fu)| fu).sum()
+    }
+
+    /// Rounded up to ten, except for 七対子.
+    pub fn total(&self) -> u8 {
+        if self
+            .items
+            .iter()
+            .any(|(item, _)| *item == FuItem::SevenPairs)
+        {
+            self.sum()
+        } else {
+            self.sum().div_ceil(10) * 10
+        }
+    }
+}
+
+const BASE_FU: u8 = 20;
+const SEVEN_PAIRS_FU: u8 = 25;
+const CONCEALED_RON_FU: u8 = 10;
+const SELF_DRAWN_FU: u8 = 2;
+const VALUE_PAIR_FU: u8 = 2;
+const WAIT_FU: u8 = 2;
+const OPEN_PINFU_FU: u8 = 10;
+
+/// Counts fu of the standard rule.
+///
+/// ```
+/// use mahjong_enterprise_edition_core::game::RuleConfig;
+/// use mahjong_enterprise_edition_core::rule::{
+///     decompose, FuCalculator, FuItem, Suit, Tile, TileKind, Wait, WinContext, Wind,
+/// };
+///
+/// // 嵌張 on 5p with a concealed triplet of 9s
+/// let tiles = [(1, 2), (1, 3), (1, 4), (2, 4), (2, 6), (3, 9), (3, 9), (3, 9)]
+///     .iter()
+///     .chain([(1, 7), (1, 8), (1, 9), (2, 1), (2, 1)].iter())
+///     .map(|(suit, n)| {
+///         let suit = [Suit::Character, Suit::Circle, Suit::Bamboo][suit - 1];
+///         Tile::new(TileKind::Suited(suit, *n))
+///     })
+///     .collect::<Vec<_>>();
+/// let winning_tile = Tile::new(TileKind::Suited(Suit::Circle, 5));
+/// let context = WinContext {
+///     is_self_drawn: false,
+///     seat_wind: Wind::South,
+///     round_wind: Wind::East,
+///     is_ready_declared: true,
+///     is_ready_declared_first: false,
+///     is_right_after_ready: false,
+///     is_last_tile: false,
+///     is_supplemental_tile: false,
+///     is_robbing_quad: false,
+///     is_first_draw: false,
+/// };
+///
+/// let decomposition = decompose(&tiles, &[], winning_tile).remove(0);
+/// let fu = FuCalculator::new(&RuleConfig::default()).calculate(&decomposition, &context);
+/// assert!(fu.items.contains(&(FuItem::Wait(Wait::Closed), 2)));
+/// // 20 + 10 (門前加符) + 8 (暗刻) + 2 (嵌張)
+/// assert_eq!(fu.sum(), 40);
+/// assert_eq!(fu.total(), 40);
+/// ```
+pub struct FuCalculator {
+    double_wind_pair_fu: u8,
+}
+
+impl FuCalculator {
+    pub fn new(rule: &RuleConfig) -> Self {
+        Self {
+            double_wind_pair_fu: rule.double_wind_pair_fu,
+        }
+    }
+
+    pub fn calculate(&self, decomposition: &Decomposition, context: &WinContext) -> Fu {
+        let (pair, groups) = match decomposition.form {
+            HandForm::SevenPairs { .. } => {
+                return Fu {
+                    items: vec![(FuItem::SevenPairs, SEVEN_PAIRS_FU)],
+                }
+            }
+            HandForm::ThirteenOrphans { .. } => {
+                return Fu {
+                    items: Some((FuItem::Base, BASE_FU))
+                        .into_iter()
+                        .chain(win_item(context, true))
+                        .collect(),
+                }
+            }
+            HandForm::Normal { pair, ref groups } => (pair, groups),
+        };
+        let is_concealed = groups.iter().all(|g| g.is_concealed);
+
+        let mut items = vec![(FuItem::Base, BASE_FU)];
+        for (i, group) in groups.iter().enumerate() {
+            let mut group = *group;
+            // 明刻 on 栄和
+            if !context.is_self_drawn && decomposition.winning_position == WinningPosition::Group(i)
+            {
+                group.is_concealed = false;
+            }
+            let fu = group_fu(&group);
+            if fu > 0 {
+                items.push((FuItem::Group(group), fu));
+            }
+        }
+        let pair_fu = self.pair_fu(pair, context);
+        if pair_fu > 0 {
+            items.push((FuItem::Pair(pair), pair_fu));
+        }
+        let wait_fu = match decomposition.wait {
+            Wait::Closed | Wait::Edge | Wait::Single => WAIT_FU,
+            _ => 0,
+        };
+        if wait_fu > 0 {
+            items.push((FuItem::Wait(decomposition.wait), wait_fu));
+        }
+
+        // 平和自摸 has no 自摸符.
+        let is_pinfu = is_concealed && items.len() == 1 && decomposition.wait == Wait::BothSides;
+        if !(is_pinfu && context.is_self_drawn) {
+            items.extend(win_item(context, is_concealed));
+        }
+        if !is_concealed && items.len() == 1 {
+            items.push((FuItem::OpenPinfu, OPEN_PINFU_FU));
+        }
+
+        Fu { items }
+    }
+
+    fn pair_fu(&self, pair: TileKind, context: &WinContext) -> u8 {
+        match pair {
+            TileKind::Dragon(_) => VALUE_PAIR_FU,
+            TileKind::Wind(wind) if wind == context.seat_wind && wind == context.round_wind => {
+                self.double_wind_pair_fu
+            }
+            TileKind::Wind(wind) if wind == context.seat_wind || wind == context.round_wind => {
+                VALUE_PAIR_FU
+            }
+            _ => 0,
+        }
+    }
+}
+
+/// 自摸符 or 門前加符. Nothing for an open hand completed by 栄和.
+fn win_item(context: &WinContext, is_concealed: bool) -> Option<(FuItem, u8)> {
+    if context.is_self_drawn {
+        Some((FuItem::SelfDrawn, SELF_DRAWN_FU))
+    } else if is_concealed {
+        Some((FuItem::ConcealedRon, CONCEALED_RON_FU))
+    } else {
+        None
+    }
+}
+
+/// 明刻 2, doubled for 么九牌 and for 暗刻, and quadrupled for 槓子.
+fn group_fu(group: &Group) -> u8 {
+    let base = match group.shape {
+        GroupShape::Sequence => return 0,
+        GroupShape::Triplet => 2,
+        GroupShape::Quad => 8,
+    };
+    let terminal_or_honor = if group.kind.is_terminal_or_honor() {
+        2
+    } else {
+        1
+    };
+    let concealed = if group.is_concealed { 2 } else { 1 };
+    base * terminal_or_honor * concealed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule::decomposition::decompose;
+    use crate::rule::meld::Meld;
+    use crate::rule::tile::Wind;
+    use crate::rule::{chow_of, tiles_of};
+
+    fn context(is_self_drawn: bool, seat_wind: Wind) -> WinContext {
+        WinContext {
+            is_self_drawn,
+            seat_wind,
+            round_wind: Wind::East,
+            is_ready_declared: false,
+            is_ready_declared_first: false,
+            is_right_after_ready: false,
+            is_last_tile: false,
+            is_supplemental_tile: false,
+            is_robbing_quad: false,
+            is_first_draw: false,
+        }
+    }
+
+    /// Fu of each interpretation.
+    fn fu_of(
+        rule: &RuleConfig,
+        tiles: &str,
+        exposed_melds: &[Meld],
+        winning_tile: &str,
+        context: &WinContext,
+    ) -> Vec<Fu> {
+        let calculator = FuCalculator::new(rule);
+        decompose(&tiles_of(tiles), exposed_melds, tiles_of(winning_tile)[0])
+            .iter()
+            .map(|d| calculator.calculate(d, context))
+            .collect()
+    }
+
+    #[test]
+    fn seven_pairs_is_not_rounded_up() {
+        let fu = fu_of(
+            &RuleConfig::default(),
+            "1133557799m224p",
+            &[],
+            "4p",
+            &context(false, Wind::South),
+        );
+        let fu = fu
+            .iter()
+            .find(|fu| fu.items == vec![(FuItem::SevenPairs, 25)])
+            .unwrap();
+        assert_eq!(fu.total(), 25);
+    }
+
+    #[test]
+    fn pinfu_has_no_self_drawn_fu() {
+        let rule = RuleConfig::default();
+        let tiles = "23m456m789p234s99s";
+        let self_drawn = fu_of(&rule, tiles, &[], "1m", &context(true, Wind::South));
+        assert_eq!(self_drawn[0].total(), 20);
+        let ron = fu_of(&rule, tiles, &[], "1m", &context(false, Wind::South));
+        assert_eq!(ron[0].total(), 30);
+    }
+
+    #[test]
+    fn open_hand_without_fu_is_thirty() {
+        let chow = chow_of("345s", "3s");
+        let fu = fu_of(
+            &RuleConfig::default(),
+            "23m456m789p99s",
+            &[chow],
+            "1m",
+            &context(false, Wind::South),
+        );
+        assert!(fu[0].items.contains(&(FuItem::OpenPinfu, 10)));
+        assert_eq!(fu[0].total(), 30);
+    }
+
+    #[test]
+    fn triplet_completed_by_ron_is_open() {
+        let rule = RuleConfig::default();
+        let tiles = "55m99m456p789p234s";
+        let ron = fu_of(&rule, tiles, &[], "5m", &context(false, Wind::South));
+        // 20 + 10 (門前加符) + 2 (明刻)
+        assert_eq!(ron[0].sum(), 32);
+        let self_drawn = fu_of(&rule, tiles, &[], "5m", &context(true, Wind::South));
+        // 20 + 4 (暗刻) + 2 (自摸符)
+        assert_eq!(self_drawn[0].sum(), 26);
+        assert_eq!(self_drawn[0].total(), 30);
+    }
+
+    #[test]
+    fn double_wind_pair_follows_the_rule() {
+        let tiles = "123m456p789s234s1z";
+        let fu = fu_of(
+            &RuleConfig::default(),
+            tiles,
+            &[],
+            "1z",
+            &context(false, Wind::East),
+        );
+        assert!(fu[0]
+            .items
+            .contains(&(FuItem::Pair(TileKind::Wind(Wind::East)), 4)));
+
+        let rule = RuleConfig {
+            double_wind_pair_fu: 2,
+            ..RuleConfig::default()
+        };
+        let fu = fu_of(&rule, tiles, &[], "1z", &context(false, Wind::East));
+        assert!(fu[0]
+            .items
+            .contains(&(FuItem::Pair(TileKind::Wind(Wind::East)), 2)));
+    }
+
+    #[test]
+    fn concealed_quad_of_terminals_is_thirty_two() {
+        let tiles = tiles_of("9999p");
+        let quad = Meld::Kong {
+            tiles: [tiles[0], tiles[1], tiles[2], tiles[3]],
+            called_tile: None,
+        };
+        let fu = fu_of(
+            &RuleConfig::default(),
+            "23m456m789s55s",
+            &[quad],
+            "1m",
+            &context(true, Wind::South),
+        );
+        assert!(fu[0].items.contains(&(
+            FuItem::Group(Group {
+                shape: GroupShape::Quad,
+                kind: tiles[0].kind(),
+                is_concealed: true,
+            }),
+            32
+        )));
+        // 20 + 32 + 2 (自摸符)
+        assert_eq!(fu[0].total(), 60);
+    }
+
+    #[test]
+    fn thirteen_orphans_has_base_and_win_fu_only() {
+        let fu = fu_of(
+            &RuleConfig::default(),
+            "19m19p19s1234567z",
+            &[],
+            "1m",
+            &context(false, Wind::South),
+        );
+        assert_eq!(fu[0].total(), 30);
+    }
+}
@@ -0,0 +1,166 @@
+use crate::game::{HandJudgementSpec, HandScore, RuleConfig, WinningHand};
+use crate::rule::decomposition::decompose;
+use crate::rule::explanation::ScoreExplanation;
+use crate::rule::meld::Meld;
+use crate::rule::shanten::Shanten;
+use crate::rule::tile::{histogram_of, Tile, TileKind, Wind};
+use crate::rule::yaku::YakuEvaluator;
+use crate::rule::StandardConcept;
+use itertools::Itertools;
+
+/// Scores with the standard yaku, or with a configured evaluator, e.g. with registered yaku or
+/// another point system.
+#[derive(Default)]
+pub struct StandardHandJudgementSpec {
+    /// Built from the rule of each hand if `None`.
+    evaluator: Option<YakuEvaluator>,
+}
+
+impl StandardHandJudgementSpec {
+    /// `evaluator` should be built from the rule of the table.
+    pub fn with_evaluator(evaluator: YakuEvaluator) -> Self {
+        Self {
+            evaluator: Some(evaluator),
+        }
+    }
+}
+
+impl HandJudgementSpec<StandardConcept> for StandardHandJudgementSpec {
+    fn waiting_tiles(&self, concealed_tiles: &[Tile], exposed_melds: &[Meld]) -> Vec<Tile> {
+        if Shanten::of(concealed_tiles, exposed_melds).value() != 0 {
+            return vec![];
+        }
+        let histogram = histogram_of(concealed_tiles);
+        TileKind::all()
+            // 純手牌で4枚使っている牌は待ちにならない
+            .filter(|kind| histogram[kind.index()] < 4)
+            .filter(|kind| !decompose(concealed_tiles, exposed_melds, Tile::new(*kind)).is_empty())
+            .map(Tile::new)
+            .collect()
+    }
+
+    /// Compares kinds, so that a red five completes the hand as well as a normal one.
+    fn is_waiting_on(&self, concealed_tiles: &[Tile], exposed_melds: &[Meld], tile: Tile) -> bool {
+        self.waiting_tiles(concealed_tiles, exposed_melds)
+            .iter()
+            .any(|t| t.kind() == tile.kind())
+    }
+
+    fn is_completed(&self, concealed_tiles: &[Tile], exposed_melds: &[Meld]) -> bool {
+        Shanten::of(concealed_tiles, exposed_melds).value() == -1
+    }
+
+    fn is_terminal_or_honor(&self, tile: Tile) -> bool {
+        tile.kind().is_terminal_or_honor()
+    }
+
+    fn is_wind(&self, tile: Tile) -> bool {
+        matches!(tile.kind(), TileKind::Wind(_))
+    }
+
+    fn is_bonus_tile(&self, tile: Tile) -> bool {
+        tile.kind() == TileKind::Wind(Wind::North)
+    }
+
+    /// The third triplet of dragons for 大三元, or the fourth one of winds for 大四喜.
+    fn is_liable_meld(&self, exposed_melds: &[Meld]) -> bool {
+        let honor_of = |meld: &Meld| match meld {
+            Meld::Pong { tiles, .. } => Some(tiles[0].kind()),
+            Meld::Kong { tiles, .. } => Some(tiles[0].kind()),
+            Meld::Chow { .. } => None,
+        };
+        let is_dragon = |kind: &TileKind| matches!(kind, TileKind::Dragon(_));
+        let is_wind = |kind: &TileKind| matches!(kind, TileKind::Wind(_));
+        let kinds = exposed_melds.iter().filter_map(honor_of).collect_vec();
+        match kinds.last() {
+            Some(kind) if is_dragon(kind) => kinds.iter().filter(|k| is_dragon(k)).count() == 3,
+            Some(kind) if is_wind(kind) => kinds.iter().filter(|k| is_wind(k)).count() == 4,
+            _ => false,
+        }
+    }
+
+    /// The yaku of the evaluator with ドラ, in its point system.
+    fn score(&self, hand: &WinningHand<StandardConcept>, rule: &RuleConfig) -> HandScore {
+        let explanation = match &self.evaluator {
+            Some(evaluator) => ScoreExplanation::evaluated_by(evaluator, hand, rule),
+            None => ScoreExplanation::of(hand, rule),
+        };
+        match explanation {
+            Some(explanation) => HandScore {
+                basic_points: explanation.basic_points,
+                liable_basic_points: match hand.liable_seat {
+                    Some(_) => explanation.liable_basic_points(),
+                    None => 0,
+                },
+            },
+            None => HandScore {
+                basic_points: 0,
+                liable_basic_points: 0,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Seat;
+    use crate::rule::point::UnlimitedSystem;
+    use crate::rule::{tiles_of, winning_hand_of};
+
+    fn pong_of(notation: &str) -> Meld {
+        let tiles = tiles_of(notation);
+        Meld::Pong {
+            tiles: [tiles[0], tiles[1], tiles[2]],
+            called_tile: tiles[2],
+        }
+    }
+
+    #[test]
+    fn meld_deciding_big_three_dragons_or_four_winds_is_liable() {
+        let spec = StandardHandJudgementSpec::default();
+        let dragons = [pong_of("555z"), pong_of("666z"), pong_of("777z")];
+        assert!(!spec.is_liable_meld(&dragons[..2]));
+        assert!(spec.is_liable_meld(&dragons));
+
+        let winds = [
+            pong_of("111z"),
+            pong_of("222z"),
+            pong_of("333z"),
+            pong_of("444z"),
+        ];
+        assert!(!spec.is_liable_meld(&winds[..3]));
+        assert!(spec.is_liable_meld(&winds));
+
+        // Not the meld deciding it.
+        let melds = [
+            pong_of("555z"),
+            pong_of("666z"),
+            pong_of("777z"),
+            pong_of("111m"),
+        ];
+        assert!(!spec.is_liable_meld(&melds));
+    }
+
+    #[test]
+    fn hand_is_scored_by_the_point_system_of_the_evaluator() {
+        let rule = RuleConfig::default();
+        // 立直 and ドラ3 of 30符
+        let hand = WinningHand {
+            is_ready_declared: true,
+            reward_indication_tiles: tiles_of("4p"),
+            ..winning_hand_of("234m67m34555p456s", "8m", Some(Seat::West))
+        };
+        assert_eq!(
+            StandardHandJudgementSpec::default()
+                .score(&hand, &rule)
+                .basic_points,
+            3000
+        );
+
+        let mut evaluator = YakuEvaluator::new(&rule);
+        evaluator.set_point_system(Box::new(UnlimitedSystem));
+        let spec = StandardHandJudgementSpec::with_evaluator(evaluator);
+        assert_eq!(spec.score(&hand, &rule).basic_points, 30 * 2i32.pow(6 + 2));
+    }
+}
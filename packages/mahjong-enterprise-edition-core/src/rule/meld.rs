@@ -0,0 +1,186 @@
+use crate::game::MeldConcept;
+use crate::rule::tile::{Tile, TileKind};
+use itertools::Itertools;
+
+#[derive(Clone, Debug)]
+pub enum Meld {
+    /// 刻子
+    Pong { tiles: [Tile; 3], called_tile: Tile },
+    /// 順子
+    Chow { tiles: [Tile; 3], called_tile: Tile },
+    /// 槓子
+    Kong {
+        tiles: [Tile; 4],
+        called_tile: Option<Tile>,
+    },
+}
+
+impl MeldConcept<Tile> for Meld {
+    fn tiles(&self) -> Vec<Tile> {
+        use Meld::*;
+
+        match self {
+            Pong { tiles, .. } | Chow { tiles, .. } => tiles.to_vec(),
+            Kong { tiles, .. } => tiles.to_vec(),
+        }
+    }
+
+    fn called_tile(&self) -> Option<Tile> {
+        use Meld::*;
+
+        match *self {
+            Pong { called_tile, .. } | Chow { called_tile, .. } => Some(called_tile),
+            Kong { called_tile, .. } => called_tile,
+        }
+    }
+
+    fn is_sequence(&self) -> bool {
+        matches!(self, Meld::Chow { .. })
+    }
+
+    fn is_quad(&self) -> bool {
+        matches!(self, Meld::Kong { .. })
+    }
+
+    fn is_well_formed(&self) -> bool {
+        let tiles = self.tiles();
+        if let Some(called_tile) = self.called_tile() {
+            if !tiles.contains(&called_tile) {
+                return false;
+            }
+        }
+        match self {
+            Meld::Pong { .. } | Meld::Kong { .. } => tiles.iter().map(|t| t.kind()).all_equal(),
+            Meld::Chow { .. } => {
+                let numbers = tiles
+                    .iter()
+                    .map(|t| match t.kind() {
+                        TileKind::Suited(suit, n) => Some((suit, n)),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>();
+                match numbers {
+                    Some(numbers) => {
+                        numbers.iter().map(|(suit, _)| suit).all_equal()
+                            && numbers
+                                .iter()
+                                .map(|(_, n)| *n)
+                                .sorted()
+                                .tuple_windows()
+                                .all(|(a, b)| b == a + 1)
+                    }
+                    None => false,
+                }
+            }
+        }
+    }
+
+    /// 現物食い替え and, for a sequence called at its end, 筋食い替え.
+    fn is_swap_calling(&self, tile: Tile) -> bool {
+        use Meld::*;
+
+        match *self {
+            Pong { called_tile, .. } => tile.kind() == called_tile.kind(),
+            Chow { tiles, called_tile } => {
+                if tile.kind() == called_tile.kind() {
+                    return true;
+                }
+                match (called_tile.kind(), tile.kind()) {
+                    (TileKind::Suited(suit, called), TileKind::Suited(tile_suit, n))
+                        if suit == tile_suit =>
+                    {
+                        let numbers = tiles.iter().filter_map(|t| match t.kind() {
+                            TileKind::Suited(_, n) => Some(n),
+                            _ => None,
+                        });
+                        let (min, max) =
+                            numbers.fold((9, 1), |(min, max), n| (min.min(n), max.max(n)));
+                        (called == min && max < 9 && n == max + 1)
+                            || (called == max && min > 1 && n == min - 1)
+                    }
+                    _ => false,
+                }
+            }
+            Kong { .. } => false,
+        }
+    }
+}
+
+/// 順子 in the notation of `tiles_of`, called with `called_tile`.
+#[cfg(test)]
+pub(crate) fn chow_of(notation: &str, called_tile: &str) -> Meld {
+    let tiles = crate::rule::tiles_of(notation);
+    Meld::Chow {
+        tiles: [tiles[0], tiles[1], tiles[2]],
+        called_tile: crate::rule::tiles_of(called_tile)[0],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule::tiles_of;
+
+    fn pong_of(notation: &str) -> Meld {
+        let tiles = tiles_of(notation);
+        Meld::Pong {
+            tiles: [tiles[0], tiles[1], tiles[2]],
+            called_tile: tiles[0],
+        }
+    }
+
+    fn is_swap_calling(meld: &Meld, tile: &str) -> bool {
+        meld.is_swap_calling(tiles_of(tile)[0])
+    }
+
+    #[test]
+    fn current_tile_is_swap_calling() {
+        let pong = pong_of("555m");
+        assert!(is_swap_calling(&pong, "5m"));
+        assert!(!is_swap_calling(&pong, "6m"));
+
+        let chow = chow_of("456m", "5m");
+        assert!(is_swap_calling(&chow, "5m"));
+        // Called in the middle, no 筋
+        assert!(!is_swap_calling(&chow, "3m"));
+        assert!(!is_swap_calling(&chow, "7m"));
+    }
+
+    #[test]
+    fn suji_of_the_other_end_is_swap_calling() {
+        let chow = chow_of("456m", "4m");
+        assert!(is_swap_calling(&chow, "7m"));
+        assert!(!is_swap_calling(&chow, "1m"));
+        assert!(!is_swap_calling(&chow, "7p"));
+
+        let chow = chow_of("456m", "6m");
+        assert!(is_swap_calling(&chow, "3m"));
+        assert!(!is_swap_calling(&chow, "9m"));
+    }
+
+    #[test]
+    fn terminal_end_has_no_suji() {
+        let swap_calling_of = |meld: &Meld| {
+            tiles_of("123456789m123456789p123456789s1234567z")
+                .into_iter()
+                .filter(|t| meld.is_swap_calling(*t))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(swap_calling_of(&chow_of("789m", "7m")), tiles_of("7m"));
+        assert_eq!(swap_calling_of(&chow_of("123m", "3m")), tiles_of("3m"));
+        assert_eq!(swap_calling_of(&chow_of("789m", "9m")), tiles_of("69m"));
+        assert_eq!(swap_calling_of(&chow_of("123m", "1m")), tiles_of("14m"));
+    }
+
+    #[test]
+    fn shape_of_the_tiles_is_checked() {
+        assert!(chow_of("534s", "3s").is_well_formed());
+        assert!(!chow_of("135s", "3s").is_well_formed());
+        assert!(!chow_of("5s1m9p", "5s").is_well_formed());
+        assert!(!chow_of("123z", "1z").is_well_formed());
+        assert!(!chow_of("345s", "6s").is_well_formed());
+
+        assert!(pong_of("505m").is_well_formed());
+        assert!(!pong_of("5s5m5p").is_well_formed());
+    }
+}
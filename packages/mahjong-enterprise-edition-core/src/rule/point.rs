@@ -0,0 +1,275 @@
+use crate::game::{round_up, RuleConfig};
+use crate::rule::yaku::YakuEvaluation;
+use serde::Serialize;
+
+/// What a completed hand is worth before paying.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize)]
+pub struct HandValue {
+    /// 飜数, including ドラ.
+    pub han: u8,
+    /// 符, rounded.
+    pub fu: u8,
+    /// How many times of a yakuman, or zero.
+    pub yakuman: u8,
+}
+
+impl HandValue {
+    /// ドラ don't count with yakuman.
+    pub fn of(evaluation: &YakuEvaluation, dora_count: u8) -> HandValue {
+        let yakuman = evaluation.yakuman_multiplier();
+        HandValue {
+            han: if yakuman > 0 {
+                0
+            } else {
+                evaluation.han() + dora_count
+            },
+            fu: evaluation.fu.total(),
+            yakuman,
+        }
+    }
+}
+
+/// 満貫以上
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Limit {
+    /// 満貫
+    Mangan,
+    /// 跳満
+    Haneman,
+    /// 倍満
+    Baiman,
+    /// 三倍満
+    Sanbaiman,
+    /// 役満, with how many times. 数え役満 is one.
+    Yakuman(u8),
+}
+
+/// 基本点
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct BasicPoints {
+    pub points: i32,
+    pub limit: Option<Limit>,
+}
+
+/// 得点系. How a hand value turns into basic points, a quarter of what a non-dealer gets on 栄和.
+pub trait PointSystem: Send + Sync {
+    fn basic_points(&self, value: &HandValue) -> BasicPoints;
+}
+
+const MANGAN_POINTS: i32 = 2000;
+const YAKUMAN_POINTS: i32 = 8000;
+/// 30符4飜・60符3飜
+const ROUNDED_UP_POINTS: i32 = 1920;
+
+/// 通常システム, with limits from 満貫 to 役満.
+pub struct NormalSystem {
+    rounds_up_to_mangan: bool,
+    counts_yakuman: bool,
+}
+
+impl NormalSystem {
+    pub fn new(rule: &RuleConfig) -> Self {
+        Self {
+            rounds_up_to_mangan: rule.rounds_up_to_mangan,
+            counts_yakuman: rule.counts_yakuman,
+        }
+    }
+}
+
+impl PointSystem for NormalSystem {
+    fn basic_points(&self, value: &HandValue) -> BasicPoints {
+        let points = raw_points(value.fu, value.han);
+        let limit = match value.han {
+            _ if value.yakuman > 0 => Some(Limit::Yakuman(value.yakuman)),
+            13..=u8::MAX if self.counts_yakuman => Some(Limit::Yakuman(1)),
+            11..=u8::MAX => Some(Limit::Sanbaiman),
+            8..=10 => Some(Limit::Baiman),
+            6..=7 => Some(Limit::Haneman),
+            5 => Some(Limit::Mangan),
+            _ if points >= MANGAN_POINTS => Some(Limit::Mangan),
+            _ if self.rounds_up_to_mangan && points == ROUNDED_UP_POINTS => Some(Limit::Mangan),
+            _ => None,
+        };
+        BasicPoints {
+            points: limit.map_or(points, |limit| limit_points(&limit)),
+            limit,
+        }
+    }
+}
+
+/// 青天井. No limits but 役満.
+pub struct UnlimitedSystem;
+
+impl PointSystem for UnlimitedSystem {
+    fn basic_points(&self, value: &HandValue) -> BasicPoints {
+        if value.yakuman > 0 {
+            let limit = Limit::Yakuman(value.yakuman);
+            BasicPoints {
+                points: limit_points(&limit),
+                limit: Some(limit),
+            }
+        } else {
+            BasicPoints {
+                points: raw_points(value.fu, value.han),
+                limit: None,
+            }
+        }
+    }
+}
+
+/// 符×2^(飜+2), saturating.
+fn raw_points(fu: u8, han: u8) -> i32 {
+    let exponent = (han as u32 + 2).min(i32::BITS - 2);
+    (fu as i32).saturating_mul(1 << exponent)
+}
+
+fn limit_points(limit: &Limit) -> i32 {
+    match limit {
+        Limit::Mangan => MANGAN_POINTS,
+        Limit::Haneman => MANGAN_POINTS * 3 / 2,
+        Limit::Baiman => MANGAN_POINTS * 2,
+        Limit::Sanbaiman => MANGAN_POINTS * 3,
+        Limit::Yakuman(times) => YAKUMAN_POINTS * *times as i32,
+    }
+}
+
+/// Points each payer pays, rounded up to 100, before 積み棒 and 供託.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Points {
+    pub basic_points: BasicPoints,
+    /// Paid by the discarder on 栄和.
+    pub ron: i32,
+    /// Paid by the dealer on 自摸和. Zero if the winner is the dealer.
+    pub self_drawn_by_dealer: i32,
+    /// Paid by each non-dealer on 自摸和.
+    pub self_drawn_by_non_dealer: i32,
+}
+
+/// 点数計算
+///
+/// ```
+/// use mahjong_enterprise_edition_core::game::RuleConfig;
+/// use mahjong_enterprise_edition_core::rule::{
+///     HandValue, Limit, NormalSystem, PointCalculator, UnlimitedSystem,
+/// };
+///
+/// let rule = RuleConfig::default();
+/// let calculator = PointCalculator::new(Box::new(NormalSystem::new(&rule)));
+/// let value = HandValue { han: 3, fu: 40, yakuman: 0 };
+/// let points = calculator.calculate(&value, false);
+/// assert_eq!(points.ron, 5200);
+/// assert_eq!((points.self_drawn_by_dealer, points.self_drawn_by_non_dealer), (2600, 1300));
+///
+/// let value = HandValue { han: 4, fu: 40, yakuman: 0 };
+/// let points = calculator.calculate(&value, true);
+/// assert_eq!(points.basic_points.limit, Some(Limit::Mangan));
+/// assert_eq!(points.ron, 12000);
+///
+/// let calculator = PointCalculator::new(Box::new(UnlimitedSystem));
+/// assert_eq!(calculator.calculate(&value, true).ron, 15400);
+/// ```
+pub struct PointCalculator {
+    system: Box<dyn PointSystem>,
+}
+
+impl PointCalculator {
+    pub fn new(system: Box<dyn PointSystem>) -> Self {
+        Self { system }
+    }
+
+    pub fn calculate(&self, value: &HandValue, is_dealer: bool) -> Points {
+        let basic_points = self.system.basic_points(value);
+        let pay = |times: i32| round_up(basic_points.points.saturating_mul(times));
+        if is_dealer {
+            Points {
+                basic_points,
+                ron: pay(6),
+                self_drawn_by_dealer: 0,
+                self_drawn_by_non_dealer: pay(2),
+            }
+        } else {
+            Points {
+                basic_points,
+                ron: pay(4),
+                self_drawn_by_dealer: pay(2),
+                self_drawn_by_non_dealer: pay(1),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(han: u8, fu: u8) -> HandValue {
+        HandValue {
+            han,
+            fu,
+            yakuman: 0,
+        }
+    }
+
+    fn ron_of(rule: &RuleConfig, value: &HandValue) -> i32 {
+        PointCalculator::new(Box::new(NormalSystem::new(rule)))
+            .calculate(value, false)
+            .ron
+    }
+
+    #[test]
+    fn self_drawn_payments_are_rounded_up_each() {
+        let calculator = PointCalculator::new(Box::new(NormalSystem::new(&RuleConfig::default())));
+        let points = calculator.calculate(&value(2, 20), false);
+        assert_eq!(
+            (points.self_drawn_by_dealer, points.self_drawn_by_non_dealer),
+            (700, 400)
+        );
+        let points = calculator.calculate(&value(2, 20), true);
+        assert_eq!(points.self_drawn_by_non_dealer, 700);
+        assert_eq!(points.self_drawn_by_dealer, 0);
+    }
+
+    #[test]
+    fn limits_start_at_each_han() {
+        let rule = RuleConfig::default();
+        assert_eq!(ron_of(&rule, &value(4, 30)), 7700);
+        assert_eq!(ron_of(&rule, &value(4, 40)), 8000);
+        assert_eq!(ron_of(&rule, &value(5, 30)), 8000);
+        assert_eq!(ron_of(&rule, &value(6, 30)), 12000);
+        assert_eq!(ron_of(&rule, &value(8, 30)), 16000);
+        assert_eq!(ron_of(&rule, &value(11, 30)), 24000);
+        assert_eq!(ron_of(&rule, &value(13, 30)), 32000);
+    }
+
+    #[test]
+    fn optional_limits_follow_the_rule() {
+        let rule = RuleConfig {
+            rounds_up_to_mangan: true,
+            counts_yakuman: false,
+            ..RuleConfig::default()
+        };
+        assert_eq!(ron_of(&rule, &value(4, 30)), 8000);
+        assert_eq!(ron_of(&rule, &value(3, 60)), 8000);
+        assert_eq!(ron_of(&rule, &value(13, 30)), 24000);
+    }
+
+    #[test]
+    fn yakuman_are_multiplied() {
+        let value = HandValue {
+            han: 0,
+            fu: 30,
+            yakuman: 2,
+        };
+        assert_eq!(ron_of(&RuleConfig::default(), &value), 64000);
+        let points = PointCalculator::new(Box::new(UnlimitedSystem)).calculate(&value, true);
+        assert_eq!(points.ron, 96000);
+    }
+
+    #[test]
+    fn unlimited_points_saturate() {
+        let points = UnlimitedSystem.basic_points(&value(u8::MAX - 2, 110));
+        assert_eq!(points.points, i32::MAX);
+        assert_eq!(points.limit, None);
+    }
+}
@@ -0,0 +1,13 @@
+use crate::game::{ParticipantId, SeatingSpec};
+use rand::seq::SliceRandom;
+
+/// 場決め by lot.
+pub struct RandomSeatingSpec;
+
+impl SeatingSpec for RandomSeatingSpec {
+    fn arrange(&self, participants: &[ParticipantId]) -> Vec<ParticipantId> {
+        let mut arranged = participants.to_vec();
+        arranged.shuffle(&mut rand::thread_rng());
+        arranged
+    }
+}
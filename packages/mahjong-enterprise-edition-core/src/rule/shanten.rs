@@ -0,0 +1,260 @@
+use crate::rule::meld::Meld;
+use crate::rule::tile::{histogram_of, Histogram, Tile, TileKind};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// 向聴数 of each form. `0` is ready (聴牌) and `-1` is completed.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Shanten {
+    /// 4面子1雀頭
+    pub normal: i8,
+    /// 七対子. `None` with exposed melds.
+    pub seven_pairs: Option<i8>,
+    /// 国士無双. `None` with exposed melds.
+    pub thirteen_orphans: Option<i8>,
+}
+
+impl Shanten {
+    /// ```
+    /// use mahjong_enterprise_edition_core::rule::{Shanten, Suit, Tile, TileKind};
+    ///
+    /// let tiles = [1, 2, 3, 4, 5, 6, 7, 8, 9, 9, 9, 5, 6]
+    ///     .iter()
+    ///     .map(|n| Tile::new(TileKind::Suited(Suit::Bamboo, *n)))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(Shanten::of(&tiles, &[]).value(), 0);
+    /// ```
+    pub fn of(concealed_tiles: &[Tile], exposed_melds: &[Meld]) -> Shanten {
+        Shanten::from_histogram(&histogram_of(concealed_tiles), exposed_melds.len())
+    }
+
+    /// Each exposed meld, including 暗槓, reduces the groups to make.
+    pub fn from_histogram(histogram: &Histogram, exposed_melds_count: usize) -> Shanten {
+        let is_concealed = exposed_melds_count == 0;
+        Shanten {
+            normal: normal_shanten(histogram, exposed_melds_count),
+            seven_pairs: if is_concealed {
+                Some(seven_pairs_shanten(histogram))
+            } else {
+                None
+            },
+            thirteen_orphans: if is_concealed {
+                Some(thirteen_orphans_shanten(histogram))
+            } else {
+                None
+            },
+        }
+    }
+
+    /// The least of the forms.
+    pub fn value(&self) -> i8 {
+        [self.seven_pairs, self.thirteen_orphans]
+            .iter()
+            .flatten()
+            .fold(self.normal, |min, n| min.min(*n))
+    }
+}
+
+const GROUPS_COUNT: usize = 4;
+const SUIT_SIZE: usize = 9;
+const HONORS_OFFSET: usize = 27;
+const HONORS_COUNT: usize = 7;
+
+/// The most partial groups (塔子・対子) for each (with a pair as 雀頭, groups) in a part of kinds.
+/// `-1` if impossible.
+type PartTable = [[i8; GROUPS_COUNT + 1]; 2];
+
+thread_local! {
+    /// Parts are cached by their counts, since the same ones come up again and again.
+    static PART_TABLES: RefCell<HashMap<u32, PartTable>> = RefCell::new(HashMap::new());
+}
+
+fn normal_shanten(histogram: &Histogram, exposed_melds_count: usize) -> i8 {
+    let groups_needed = GROUPS_COUNT.saturating_sub(exposed_melds_count);
+
+    let mut best: PartTable = [[-1; GROUPS_COUNT + 1]; 2];
+    best[0][0] = 0;
+    let parts = (0..3)
+        .map(|i| &histogram[i * SUIT_SIZE..(i + 1) * SUIT_SIZE])
+        .chain(std::iter::once(
+            &histogram[HONORS_OFFSET..HONORS_OFFSET + HONORS_COUNT],
+        ));
+    for (i, counts) in parts.enumerate() {
+        let part = part_table(counts, i < 3);
+        let mut combined: PartTable = [[-1; GROUPS_COUNT + 1]; 2];
+        for h1 in 0..2 {
+            for m1 in 0..=GROUPS_COUNT {
+                if best[h1][m1] < 0 {
+                    continue;
+                }
+                for h2 in 0..2 - h1 {
+                    for m2 in 0..=GROUPS_COUNT - m1 {
+                        if part[h2][m2] >= 0 {
+                            let t = &mut combined[h1 + h2][m1 + m2];
+                            *t = (*t).max(best[h1][m1] + part[h2][m2]);
+                        }
+                    }
+                }
+            }
+        }
+        best = combined;
+    }
+
+    let value = (0..=1)
+        .flat_map(|h| (0..=groups_needed).map(move |m| (h, m)))
+        .filter(|(h, m)| best[*h][*m] >= 0)
+        .map(|(h, m)| {
+            let partial_groups = (best[h][m] as usize).min(groups_needed - m);
+            (2 * m + partial_groups + h) as i8
+        })
+        .max()
+        .unwrap_or(0);
+    2 * groups_needed as i8 - value
+}
+
+fn part_table(counts: &[u8], is_suit: bool) -> PartTable {
+    let key = counts
+        .iter()
+        .fold(is_suit as u32, |key, n| key * 5 + *n as u32);
+    PART_TABLES.with(|tables| {
+        if let Some(table) = tables.borrow().get(&key) {
+            return *table;
+        }
+        let mut counts = counts.to_vec();
+        let mut table: PartTable = [[-1; GROUPS_COUNT + 1]; 2];
+        search_part(&mut counts, 0, is_suit, (0, 0, 0), &mut table);
+        tables.borrow_mut().insert(key, table);
+        table
+    })
+}
+
+/// Takes groups, partial groups and a pair from the lowest kind, recording the best of each.
+fn search_part(
+    counts: &mut [u8],
+    from: usize,
+    is_suit: bool,
+    (pair, groups, partial_groups): (usize, usize, i8),
+    table: &mut PartTable,
+) {
+    let i = match (from..counts.len()).find(|i| counts[*i] > 0) {
+        Some(i) => i,
+        None => {
+            let best = &mut table[pair][groups.min(GROUPS_COUNT)];
+            *best = (*best).max(partial_groups.min(GROUPS_COUNT as i8));
+            return;
+        }
+    };
+    let state = (pair, groups, partial_groups);
+    let has =
+        |counts: &[u8], offset: usize| is_suit && i + offset < SUIT_SIZE && counts[i + offset] > 0;
+
+    let mut take = |counts: &mut [u8], taken: &[usize], next: (usize, usize, i8)| {
+        for offset in taken {
+            counts[i + offset] -= 1;
+        }
+        search_part(counts, i, is_suit, next, table);
+        for offset in taken {
+            counts[i + offset] += 1;
+        }
+    };
+
+    // 面子
+    if counts[i] >= 3 {
+        take(counts, &[0, 0, 0], (pair, groups + 1, partial_groups));
+    }
+    if has(counts, 1) && has(counts, 2) {
+        take(counts, &[0, 1, 2], (pair, groups + 1, partial_groups));
+    }
+    // 雀頭・対子
+    if counts[i] >= 2 {
+        if pair == 0 {
+            take(counts, &[0, 0], (1, groups, partial_groups));
+        }
+        take(counts, &[0, 0], (pair, groups, partial_groups + 1));
+    }
+    // 両面・辺張・嵌張
+    if has(counts, 1) {
+        take(counts, &[0, 1], (pair, groups, partial_groups + 1));
+    }
+    if has(counts, 2) {
+        take(counts, &[0, 2], (pair, groups, partial_groups + 1));
+    }
+    // 孤立牌
+    take(counts, &[0], state);
+}
+
+/// 七対子. The same kind of 4 tiles is not two pairs.
+fn seven_pairs_shanten(histogram: &Histogram) -> i8 {
+    let kinds = histogram.iter().filter(|n| **n > 0).count() as i8;
+    let pairs = histogram.iter().filter(|n| **n >= 2).count() as i8;
+    6 - pairs + (7 - kinds).max(0)
+}
+
+/// 国士無双
+fn thirteen_orphans_shanten(histogram: &Histogram) -> i8 {
+    let orphans = TileKind::all().filter(TileKind::is_terminal_or_honor);
+    let (kinds, has_pair) = orphans.fold((0, false), |(kinds, has_pair), kind| {
+        let n = histogram[kind.index()];
+        (kinds + (n > 0) as i8, has_pair || n >= 2)
+    });
+    13 - kinds - has_pair as i8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule::tiles_of;
+
+    fn shanten_of(concealed_tiles: &str) -> Shanten {
+        Shanten::of(&tiles_of(concealed_tiles), &[])
+    }
+
+    #[test]
+    fn completed_hand_is_minus_one() {
+        assert_eq!(shanten_of("123m456p789s11122z").value(), -1);
+        assert_eq!(shanten_of("11223344556677z").value(), -1);
+        assert_eq!(shanten_of("19m19p19s12345677z").value(), -1);
+    }
+
+    #[test]
+    fn each_form_is_counted() {
+        let shanten = shanten_of("1122334455667z");
+        assert_eq!(shanten.seven_pairs, Some(0));
+        assert_eq!(shanten.normal, 3);
+        assert_eq!(shanten.value(), 0);
+
+        let shanten = shanten_of("19m19p19s123456z5m");
+        assert_eq!(shanten.thirteen_orphans, Some(1));
+        assert_eq!(shanten.value(), 1);
+    }
+
+    #[test]
+    fn same_kind_four_times_is_not_two_pairs() {
+        // 0 if 1z counted as two pairs
+        assert_eq!(shanten_of("1111223344556z").seven_pairs, Some(2));
+    }
+
+    #[test]
+    fn partial_groups_beyond_groups_needed_are_not_counted() {
+        // 5 塔子 for 4 groups
+        assert_eq!(shanten_of("12m45m78m12p45p9s1z").normal, 4);
+    }
+
+    #[test]
+    fn exposed_melds_reduce_groups_needed() {
+        let tiles = tiles_of("777z");
+        let pong = Meld::Pong {
+            tiles: [tiles[0], tiles[1], tiles[2]],
+            called_tile: tiles[2],
+        };
+        let melds = vec![pong; 4];
+        let shanten = Shanten::of(&tiles_of("123m456p789s5z"), &melds[..1]);
+        assert_eq!(shanten.normal, 0);
+        assert_eq!(shanten.seven_pairs, None);
+        assert_eq!(shanten.thirteen_orphans, None);
+
+        // 裸単騎
+        assert_eq!(Shanten::of(&tiles_of("5z"), &melds).value(), 0);
+        assert_eq!(Shanten::of(&tiles_of("55z"), &melds).value(), -1);
+    }
+}
@@ -0,0 +1,175 @@
+use serde::Serialize;
+
+/// Number of distinct tile kinds (萬子・筒子・索子 9 each, 風牌 4, 三元牌 3).
+pub const TILE_KINDS_COUNT: usize = 34;
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Suit {
+    /// 萬子
+    Character,
+    /// 筒子
+    Circle,
+    /// 索子
+    Bamboo,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Wind {
+    East,
+    South,
+    West,
+    North,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Dragon {
+    White,
+    Green,
+    Red,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TileKind {
+    Suited(Suit, u8),
+    Wind(Wind),
+    Dragon(Dragon),
+}
+
+impl TileKind {
+    pub fn index(&self) -> usize {
+        use TileKind::*;
+
+        match *self {
+            Suited(suit, number) => suit as usize * 9 + (number as usize - 1),
+            Wind(wind) => 27 + wind as usize,
+            Dragon(dragon) => 31 + dragon as usize,
+        }
+    }
+
+    pub fn from_index(index: usize) -> TileKind {
+        use self::Dragon::*;
+        use self::Wind::*;
+        use Suit::*;
+        use TileKind::*;
+
+        match index {
+            0..=8 => Suited(Character, (index + 1) as u8),
+            9..=17 => Suited(Circle, (index - 9 + 1) as u8),
+            18..=26 => Suited(Bamboo, (index - 18 + 1) as u8),
+            27 => Wind(East),
+            28 => Wind(South),
+            29 => Wind(West),
+            30 => Wind(North),
+            31 => Dragon(White),
+            32 => Dragon(Green),
+            33 => Dragon(Red),
+            _ => panic!("Invalid value: {}", index),
+        }
+    }
+
+    pub fn all() -> impl Iterator<Item = TileKind> {
+        (0..TILE_KINDS_COUNT).map(TileKind::from_index)
+    }
+
+    pub fn is_honor(&self) -> bool {
+        !matches!(self, TileKind::Suited(_, _))
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, TileKind::Suited(_, 1) | TileKind::Suited(_, 9))
+    }
+
+    /// 么九牌
+    pub fn is_terminal_or_honor(&self) -> bool {
+        self.is_terminal() || self.is_honor()
+    }
+
+    /// ドラ indicated by this kind, the next one in the cycle of the suit, winds or dragons.
+    pub fn indicated_dora(&self) -> TileKind {
+        match *self {
+            TileKind::Suited(suit, n) => TileKind::Suited(suit, n % 9 + 1),
+            TileKind::Wind(wind) => TileKind::from_index(27 + (wind as usize + 1) % 4),
+            TileKind::Dragon(dragon) => TileKind::from_index(31 + (dragon as usize + 1) % 3),
+        }
+    }
+}
+
+/// A physical tile. Red fives (赤ドラ) differ from the normal ones, though they are the same kind
+/// for every rule except counting rewards.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize)]
+pub struct Tile {
+    kind: TileKind,
+    is_red: bool,
+}
+
+impl Tile {
+    pub fn new(kind: TileKind) -> Tile {
+        Tile {
+            kind,
+            is_red: false,
+        }
+    }
+
+    pub fn red_five(suit: Suit) -> Tile {
+        Tile {
+            kind: TileKind::Suited(suit, 5),
+            is_red: true,
+        }
+    }
+
+    pub fn kind(&self) -> TileKind {
+        self.kind
+    }
+
+    pub fn is_red(&self) -> bool {
+        self.is_red
+    }
+}
+
+impl From<TileKind> for Tile {
+    fn from(kind: TileKind) -> Self {
+        Tile::new(kind)
+    }
+}
+
+/// Counts of each tile kind, indexed by `TileKind::index`.
+pub type Histogram = [u8; TILE_KINDS_COUNT];
+
+pub fn histogram_of(tiles: &[Tile]) -> Histogram {
+    let mut histogram = [0; TILE_KINDS_COUNT];
+    for tile in tiles {
+        histogram[tile.kind().index()] += 1;
+    }
+    histogram
+}
+
+/// Tiles in the usual notation, e.g. `"123m405p789s1122z"` with `0` for a red five and `1z`-`7z`
+/// for 東南西北白發中.
+#[cfg(test)]
+pub(crate) fn tiles_of(notation: &str) -> Vec<Tile> {
+    let mut tiles = vec![];
+    let mut numbers = vec![];
+    for c in notation.chars() {
+        if let Some(number) = c.to_digit(10) {
+            numbers.push(number as u8);
+            continue;
+        }
+        for number in numbers.drain(..) {
+            tiles.push(match (c, number) {
+                ('m', 0) => Tile::red_five(Suit::Character),
+                ('p', 0) => Tile::red_five(Suit::Circle),
+                ('s', 0) => Tile::red_five(Suit::Bamboo),
+                ('m', n) => Tile::new(TileKind::Suited(Suit::Character, n)),
+                ('p', n) => Tile::new(TileKind::Suited(Suit::Circle, n)),
+                ('s', n) => Tile::new(TileKind::Suited(Suit::Bamboo, n)),
+                ('z', n @ 1..=7) => Tile::new(TileKind::from_index(27 + n as usize - 1)),
+                _ => panic!("Invalid notation: {}", notation),
+            });
+        }
+    }
+    tiles
+}
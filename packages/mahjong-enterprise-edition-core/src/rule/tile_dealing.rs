@@ -0,0 +1,115 @@
+use crate::game::{DealtResult, PlayersCount, RedFives, RuleConfig, TileDealingSpec};
+use crate::rule::tile::{Suit, Tile, TileKind};
+use crate::rule::StandardConcept;
+use rand::seq::SliceRandom;
+
+/// 136 tiles shuffled, with red fives as configured.
+pub struct StandardTileDealingSpec;
+
+/// 三麻. 108 tiles without 2-8 of characters, with red fives of circles and bamboos as configured.
+pub struct ThreePlayerTileDealingSpec;
+
+/// 嶺上牌
+const SUPPLEMENTAL_TILES_COUNT: usize = 4;
+/// 嶺上牌, also drawn for 抜きドラ
+const THREE_PLAYER_SUPPLEMENTAL_TILES_COUNT: usize = 8;
+/// ドラ表示牌・裏ドラ表示牌
+const REWARD_INDICATION_TILES_COUNT: usize = 10;
+const INITIAL_HAND_TILES_COUNT: usize = 13;
+
+impl TileDealingSpec<StandardConcept> for StandardTileDealingSpec {
+    fn deal(&self, rule: &RuleConfig) -> DealtResult<StandardConcept> {
+        deal_shuffled(
+            all_tiles(&rule.red_fives),
+            SUPPLEMENTAL_TILES_COUNT,
+            rule.players_count,
+        )
+    }
+}
+
+impl TileDealingSpec<StandardConcept> for ThreePlayerTileDealingSpec {
+    fn deal(&self, rule: &RuleConfig) -> DealtResult<StandardConcept> {
+        let tiles = all_tiles(&rule.red_fives)
+            .into_iter()
+            .filter(|tile| {
+                !matches!(tile.kind(), TileKind::Suited(Suit::Character, n) if (2..=8).contains(&n))
+            })
+            .collect();
+        deal_shuffled(
+            tiles,
+            THREE_PLAYER_SUPPLEMENTAL_TILES_COUNT,
+            rule.players_count,
+        )
+    }
+}
+
+fn deal_shuffled(
+    tiles: Vec<Tile>,
+    supplemental_tiles_count: usize,
+    players_count: PlayersCount,
+) -> DealtResult<StandardConcept> {
+    let mut tiles = tiles;
+    tiles.shuffle(&mut rand::thread_rng());
+
+    let mut deal = |count: usize| tiles.split_off(tiles.len() - count);
+    let supplemental_tiles = deal(supplemental_tiles_count);
+    let reward_indication_tiles = deal(REWARD_INDICATION_TILES_COUNT);
+    let player_tiles = players_count
+        .seats()
+        .map(|seat| (deal(INITIAL_HAND_TILES_COUNT), seat))
+        .collect();
+
+    DealtResult::new(
+        tiles,
+        supplemental_tiles,
+        reward_indication_tiles,
+        player_tiles,
+    )
+}
+
+fn all_tiles(red_fives: &RedFives) -> Vec<Tile> {
+    TileKind::all()
+        .flat_map(|kind| match kind {
+            TileKind::Suited(suit, 5) => {
+                let red_count = match suit {
+                    Suit::Character => red_fives.characters,
+                    Suit::Circle => red_fives.circles,
+                    Suit::Bamboo => red_fives.bamboos,
+                } as usize;
+                [
+                    vec![Tile::red_five(suit); red_count],
+                    vec![Tile::new(kind); 4 - red_count],
+                ]
+                .concat()
+            }
+            _ => vec![Tile::new(kind); 4],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn three_players_are_dealt_without_2_to_8_of_characters() {
+        let rule = RuleConfig::three_players();
+        let dealt = ThreePlayerTileDealingSpec.deal(&rule);
+        assert_eq!(dealt.wall_tiles.len(), 108 - 8 - 10 - 13 * 3);
+        assert_eq!(dealt.supplemental_tiles.len(), 8);
+        assert_eq!(dealt.player_tiles.len(), 3);
+
+        let tiles: Vec<_> = dealt
+            .wall_tiles
+            .iter()
+            .chain(&dealt.supplemental_tiles)
+            .chain(&dealt.reward_indication_tiles)
+            .chain(dealt.player_tiles.iter().flat_map(|(tiles, _)| tiles))
+            .collect();
+        assert_eq!(tiles.len(), 108);
+        assert!(!tiles.iter().any(|t| matches!(
+            t.kind(),
+            TileKind::Suited(Suit::Character, n) if (2..=8).contains(&n)
+        )));
+    }
+}
@@ -0,0 +1,785 @@
+use crate::game::RuleConfig;
+use crate::rule::decomposition::{
+    decompose, Decomposition, Group, GroupShape, HandForm, Wait, WinningPosition,
+};
+use crate::rule::fu::{Fu, FuCalculator};
+use crate::rule::meld::Meld;
+use crate::rule::point::{HandValue, NormalSystem, PointSystem};
+use crate::rule::tile::{Dragon, Suit, Tile, TileKind, Wind};
+use itertools::Itertools;
+use serde::Serialize;
+
+mod plugin;
+mod yakuman;
+
+#[cfg(test)]
+pub(crate) use plugin::tests::ConcealedAllSimples;
+pub use plugin::Yaku;
+pub use yakuman::YakumanYaku;
+
+/// 通常役
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NormalYaku {
+    /// 立直
+    Riichi,
+    /// 両立直
+    DoubleRiichi,
+    /// 一発
+    Ippatsu,
+    /// 門前清自摸和
+    MenzenTsumo,
+    /// 平和
+    Pinfu,
+    /// 断么九
+    Tanyao,
+    /// 一盃口
+    Iipeikou,
+    /// 自風牌
+    SeatWind,
+    /// 場風牌
+    RoundWind,
+    /// 白
+    WhiteDragon,
+    /// 發
+    GreenDragon,
+    /// 中
+    RedDragon,
+    /// 海底摸月
+    Haitei,
+    /// 河底撈魚
+    Houtei,
+    /// 嶺上開花
+    Rinshan,
+    /// 槍槓
+    Chankan,
+    /// 七対子
+    Chiitoitsu,
+    /// 三色同順
+    Sanshoku,
+    /// 一気通貫
+    Ittsu,
+    /// 混全帯么九
+    Chanta,
+    /// 対々和
+    Toitoi,
+    /// 三暗刻
+    Sanankou,
+    /// 三色同刻
+    SanshokuDoukou,
+    /// 三槓子
+    Sankantsu,
+    /// 小三元
+    Shousangen,
+    /// 混老頭
+    Honroutou,
+    /// 二盃口
+    Ryanpeikou,
+    /// 混一色
+    Honitsu,
+    /// 純全帯么九
+    Junchan,
+    /// 清一色
+    Chinitsu,
+}
+
+impl NormalYaku {
+    /// 飜数. `None` if the yaku needs a concealed hand (門前限定).
+    pub fn han(&self, is_concealed: bool) -> Option<u8> {
+        use NormalYaku::*;
+
+        let (concealed, open) = match self {
+            Riichi | Ippatsu | MenzenTsumo | Pinfu | Iipeikou => (1, None),
+            Tanyao | SeatWind | RoundWind | WhiteDragon | GreenDragon | RedDragon => (1, Some(1)),
+            Haitei | Houtei | Rinshan | Chankan => (1, Some(1)),
+            DoubleRiichi | Chiitoitsu => (2, None),
+            Sanshoku | Ittsu | Chanta => (2, Some(1)),
+            Toitoi | Sanankou | SanshokuDoukou | Sankantsu | Shousangen | Honroutou => (2, Some(2)),
+            Ryanpeikou => (3, None),
+            Honitsu | Junchan => (3, Some(2)),
+            Chinitsu => (6, Some(5)),
+        };
+        if is_concealed {
+            Some(concealed)
+        } else {
+            open
+        }
+    }
+}
+
+/// What happened around the completion, which the hand itself doesn't tell.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct WinContext {
+    /// 自摸和了, otherwise 栄和
+    pub is_self_drawn: bool,
+    /// 自風
+    pub seat_wind: Wind,
+    /// 場風
+    pub round_wind: Wind,
+    /// 立直
+    pub is_ready_declared: bool,
+    /// 両立直, declared in the first turn without calls before.
+    pub is_ready_declared_first: bool,
+    /// 一発, in a turn after declaring ready without calls.
+    pub is_right_after_ready: bool,
+    /// The last tile of the wall, for 海底・河底.
+    pub is_last_tile: bool,
+    /// 嶺上牌
+    pub is_supplemental_tile: bool,
+    /// 槍槓
+    pub is_robbing_quad: bool,
+    /// Before the own first discard without calls before, for 天和・地和 and 人和.
+    pub is_first_draw: bool,
+}
+
+/// The yaku found in an interpretation of a completed hand.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct YakuEvaluation {
+    pub decomposition: Decomposition,
+    /// Each yaku with its 飜数. Empty if any yakuman is found.
+    pub yaku: Vec<(NormalYaku, u8)>,
+    /// Each yakuman with how many times of a yakuman it is worth.
+    pub yakuman: Vec<(YakumanYaku, u8)>,
+    /// Registered yaku by name, with its 飜数. Empty if any yakuman is found.
+    pub registered_yaku: Vec<(String, u8)>,
+    /// Registered yakuman by name, with how many times of a yakuman it is worth.
+    pub registered_yakuman: Vec<(String, u8)>,
+    pub fu: Fu,
+}
+
+impl YakuEvaluation {
+    pub fn han(&self) -> u8 {
+        let registered = self.registered_yaku.iter().map(|(_, han)| han);
+        self.yaku.iter().map(|(_, han)| han).chain(registered).sum()
+    }
+
+    /// How many times of a yakuman the hand is worth in total.
+    pub fn yakuman_multiplier(&self) -> u8 {
+        let registered = self
+            .registered_yakuman
+            .iter()
+            .map(|(_, multiplier)| multiplier);
+        self.yakuman
+            .iter()
+            .map(|(_, multiplier)| multiplier)
+            .chain(registered)
+            .sum()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.yaku.is_empty()
+            && self.yakuman.is_empty()
+            && self.registered_yaku.is_empty()
+            && self.registered_yakuman.is_empty()
+    }
+}
+
+/// Finds yaku of the standard rule, following the optional ones of the rule config.
+pub struct YakuEvaluator {
+    allows_open_tanyao: bool,
+    has_ippatsu: bool,
+    has_double_yakuman: bool,
+    stacks_yakuman: bool,
+    fu_calculator: FuCalculator,
+    point_system: Box<dyn PointSystem>,
+    registered: Vec<Box<dyn Yaku>>,
+}
+
+impl YakuEvaluator {
+    pub fn new(rule: &RuleConfig) -> Self {
+        Self {
+            allows_open_tanyao: rule.allows_open_tanyao,
+            has_ippatsu: rule.has_ippatsu,
+            has_double_yakuman: rule.has_double_yakuman,
+            stacks_yakuman: rule.stacks_yakuman,
+            fu_calculator: FuCalculator::new(rule),
+            point_system: Box::new(NormalSystem::new(rule)),
+            registered: vec![],
+        }
+    }
+
+    /// Adds a yaku of a rule pack, found after the standard ones.
+    ///
+    /// ```
+    /// use mahjong_enterprise_edition_core::game::RuleConfig;
+    /// use mahjong_enterprise_edition_core::rule::{
+    ///     Decomposition, Suit, Tile, TileKind, WinContext, Wind, Yaku, YakuEvaluator,
+    /// };
+    ///
+    /// /// 大車輪
+    /// struct BigWheels;
+    ///
+    /// impl Yaku for BigWheels {
+    ///     fn name(&self) -> &str {
+    ///         "big-wheels"
+    ///     }
+    ///
+    ///     fn han(&self, is_concealed: bool) -> Option<u8> {
+    ///         if is_concealed {
+    ///             Some(1)
+    ///         } else {
+    ///             None
+    ///         }
+    ///     }
+    ///
+    ///     fn is_yakuman(&self) -> bool {
+    ///         true
+    ///     }
+    ///
+    ///     fn applies(&self, decomposition: &Decomposition, _context: &WinContext) -> bool {
+    ///         let mut kinds = decomposition.form.kinds();
+    ///         kinds.sort();
+    ///         let wheels = (2..=8)
+    ///             .flat_map(|n| vec![TileKind::Suited(Suit::Circle, n); 2])
+    ///             .collect::<Vec<_>>();
+    ///         kinds == wheels
+    ///     }
+    /// }
+    ///
+    /// let tiles = [2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8]
+    ///     .iter()
+    ///     .map(|n| Tile::new(TileKind::Suited(Suit::Circle, *n)))
+    ///     .collect::<Vec<_>>();
+    /// let winning_tile = Tile::new(TileKind::Suited(Suit::Circle, 8));
+    /// let context = WinContext {
+    ///     is_self_drawn: false,
+    ///     seat_wind: Wind::South,
+    ///     round_wind: Wind::East,
+    ///     is_ready_declared: false,
+    ///     is_ready_declared_first: false,
+    ///     is_right_after_ready: false,
+    ///     is_last_tile: false,
+    ///     is_supplemental_tile: false,
+    ///     is_robbing_quad: false,
+    ///     is_first_draw: false,
+    /// };
+    ///
+    /// let mut evaluator = YakuEvaluator::new(&RuleConfig::default());
+    /// evaluator.register(Box::new(BigWheels));
+    /// let evaluation = evaluator.evaluate(&tiles, &[], winning_tile, &context).unwrap();
+    /// assert_eq!(evaluation.registered_yakuman, vec![("big-wheels".to_string(), 1)]);
+    /// // 清一色 and 二盃口 don't count with a yakuman.
+    /// assert!(evaluation.yaku.is_empty());
+    /// ```
+    pub fn register(&mut self, yaku: Box<dyn Yaku>) {
+        self.registered.push(yaku);
+    }
+
+    /// Replaces the normal system of the rule, e.g. with `UnlimitedSystem`. The interpretation
+    /// is chosen by it as well.
+    pub fn set_point_system(&mut self, point_system: Box<dyn PointSystem>) {
+        self.point_system = point_system;
+    }
+
+    pub fn point_system(&self) -> &dyn PointSystem {
+        self.point_system.as_ref()
+    }
+
+    /// The interpretation worth the most in 基本点, by 飜 and then 符 on a tie. `None` if the hand
+    /// is not completed or has no yaku.
+    ///
+    /// `concealed_tiles` excludes the winning tile, even if drawn.
+    ///
+    /// ```
+    /// use mahjong_enterprise_edition_core::game::RuleConfig;
+    /// use mahjong_enterprise_edition_core::rule::{
+    ///     NormalYaku, Suit, Tile, TileKind, WinContext, Wind, YakuEvaluator,
+    /// };
+    ///
+    /// let tiles = [(1, 1), (1, 2), (1, 3), (2, 2), (2, 3), (2, 4), (2, 6), (2, 7), (2, 8)]
+    ///     .iter()
+    ///     .chain([(3, 5), (3, 5), (3, 2), (3, 3)].iter())
+    ///     .map(|(suit, n)| {
+    ///         let suit = [Suit::Character, Suit::Circle, Suit::Bamboo][suit - 1];
+    ///         Tile::new(TileKind::Suited(suit, *n))
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// let winning_tile = Tile::new(TileKind::Suited(Suit::Bamboo, 4));
+    /// let context = WinContext {
+    ///     is_self_drawn: true,
+    ///     seat_wind: Wind::South,
+    ///     round_wind: Wind::East,
+    ///     is_ready_declared: true,
+    ///     is_ready_declared_first: false,
+    ///     is_right_after_ready: false,
+    ///     is_last_tile: false,
+    ///     is_supplemental_tile: false,
+    ///     is_robbing_quad: false,
+    ///     is_first_draw: false,
+    /// };
+    ///
+    /// let evaluator = YakuEvaluator::new(&RuleConfig::default());
+    /// let evaluation = evaluator.evaluate(&tiles, &[], winning_tile, &context).unwrap();
+    /// let yaku = evaluation.yaku.iter().map(|(yaku, _)| *yaku).collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     yaku,
+    ///     vec![NormalYaku::Riichi, NormalYaku::MenzenTsumo, NormalYaku::Pinfu]
+    /// );
+    /// assert!(evaluation.yakuman.is_empty());
+    /// assert_eq!(evaluation.fu.total(), 20);
+    /// ```
+    pub fn evaluate(
+        &self,
+        concealed_tiles: &[Tile],
+        exposed_melds: &[Meld],
+        winning_tile: Tile,
+        context: &WinContext,
+    ) -> Option<YakuEvaluation> {
+        self.evaluate_with_dora(concealed_tiles, exposed_melds, winning_tile, context, 0)
+    }
+
+    /// `evaluate` with the ドラ of the hand, which may reach a limit in one interpretation
+    /// but not in another.
+    pub fn evaluate_with_dora(
+        &self,
+        concealed_tiles: &[Tile],
+        exposed_melds: &[Meld],
+        winning_tile: Tile,
+        context: &WinContext,
+        dora_count: u8,
+    ) -> Option<YakuEvaluation> {
+        decompose(concealed_tiles, exposed_melds, winning_tile)
+            .into_iter()
+            .map(|decomposition| self.evaluate_decomposition(decomposition, context))
+            .filter(|evaluation| !evaluation.is_empty())
+            .max_by_key(|evaluation| self.worth_of(evaluation, dora_count))
+    }
+
+    /// 基本点, and then yakuman, 飜 and 符 on a tie.
+    fn worth_of(&self, evaluation: &YakuEvaluation, dora_count: u8) -> (i32, u8, u8, u8) {
+        let value = HandValue::of(evaluation, dora_count);
+        (
+            self.point_system.basic_points(&value).points,
+            evaluation.yakuman_multiplier(),
+            evaluation.han(),
+            evaluation.fu.total(),
+        )
+    }
+
+    /// Yaku in the interpretation, in the order of `NormalYaku` and `YakumanYaku` and then of
+    /// registration. Normal yaku don't count with yakuman.
+    pub fn evaluate_decomposition(
+        &self,
+        decomposition: Decomposition,
+        context: &WinContext,
+    ) -> YakuEvaluation {
+        let is_concealed = match decomposition.form {
+            HandForm::Normal { ref groups, .. } => groups.iter().all(|g| g.is_concealed),
+            _ => true,
+        };
+        let (registered_yakuman, registered_yaku): (Vec<_>, Vec<_>) = self
+            .registered
+            .iter()
+            .map(Box::as_ref)
+            .filter(|yaku| yaku.applies(&decomposition, context))
+            .filter_map(|yaku| yaku.han(is_concealed).map(|han| (yaku, han)))
+            .partition(|(yaku, _)| yaku.is_yakuman());
+
+        let mut yakuman = yakuman::find(&decomposition, context, is_concealed)
+            .into_iter()
+            .filter(|yakuman| {
+                registered_yakuman
+                    .iter()
+                    .all(|(yaku, _)| yaku.combines_with_yakuman(*yakuman))
+            })
+            .map(|yaku| (yaku, yaku.multiplier(self.has_double_yakuman)))
+            .collect_vec();
+        let mut registered_yakuman = named(registered_yakuman);
+        // The first of the largest, without 複合役満.
+        if !self.stacks_yakuman {
+            let largest = yakuman
+                .iter()
+                .map(|(_, multiplier)| *multiplier)
+                .chain(registered_yakuman.iter().map(|(_, multiplier)| *multiplier))
+                .max()
+                .unwrap_or(0);
+            if retain_first_largest(&mut yakuman, largest) {
+                registered_yakuman.clear();
+            } else {
+                retain_first_largest(&mut registered_yakuman, largest);
+            }
+        }
+
+        let (yaku, registered_yaku) = if yakuman.is_empty() && registered_yakuman.is_empty() {
+            let yaku = self
+                .find(&decomposition, context, is_concealed)
+                .into_iter()
+                .filter(|normal_yaku| {
+                    registered_yaku
+                        .iter()
+                        .all(|(yaku, _)| yaku.combines_with(*normal_yaku))
+                })
+                .filter_map(|yaku| yaku.han(is_concealed).map(|han| (yaku, han)))
+                .collect();
+            (yaku, named(registered_yaku))
+        } else {
+            (vec![], vec![])
+        };
+        let fu = self.fu_calculator.calculate(&decomposition, context);
+        YakuEvaluation {
+            decomposition,
+            yaku,
+            yakuman,
+            registered_yaku,
+            registered_yakuman,
+            fu,
+        }
+    }
+
+    fn find(
+        &self,
+        decomposition: &Decomposition,
+        context: &WinContext,
+        is_concealed: bool,
+    ) -> Vec<NormalYaku> {
+        use NormalYaku::*;
+
+        let mut found = vec![];
+        let mut add = |yaku: NormalYaku, applies: bool| {
+            if applies {
+                found.push(yaku);
+            }
+        };
+
+        // 状況役
+        add(DoubleRiichi, context.is_ready_declared_first);
+        add(
+            Riichi,
+            context.is_ready_declared && !context.is_ready_declared_first,
+        );
+        add(
+            Ippatsu,
+            self.has_ippatsu && context.is_ready_declared && context.is_right_after_ready,
+        );
+        add(MenzenTsumo, is_concealed && context.is_self_drawn);
+        add(
+            Haitei,
+            context.is_last_tile && context.is_self_drawn && !context.is_supplemental_tile,
+        );
+        add(Houtei, context.is_last_tile && !context.is_self_drawn);
+        add(Rinshan, context.is_supplemental_tile);
+        add(Chankan, context.is_robbing_quad);
+
+        let kinds = decomposition.form.kinds();
+        add(
+            Tanyao,
+            (is_concealed || self.allows_open_tanyao)
+                && kinds.iter().all(|k| !k.is_terminal_or_honor()),
+        );
+        add(Honroutou, kinds.iter().all(TileKind::is_terminal_or_honor));
+        let suits = kinds.iter().filter_map(suit_of).unique().count();
+        let has_honors = kinds.iter().any(TileKind::is_honor);
+        add(Chinitsu, suits == 1 && !has_honors);
+        add(Honitsu, suits == 1 && has_honors);
+
+        match decomposition.form {
+            HandForm::Normal { pair, ref groups } => {
+                for yaku in find_in_normal_form(pair, groups, decomposition, context, is_concealed)
+                {
+                    add(yaku, true);
+                }
+            }
+            HandForm::SevenPairs { .. } => add(Chiitoitsu, true),
+            // Only a yakuman.
+            HandForm::ThirteenOrphans { .. } => {}
+        }
+
+        found.sort();
+        found
+    }
+}
+
+fn find_in_normal_form(
+    pair: TileKind,
+    groups: &[Group],
+    decomposition: &Decomposition,
+    context: &WinContext,
+    is_concealed: bool,
+) -> Vec<NormalYaku> {
+    use NormalYaku::*;
+
+    let mut found = vec![];
+    let mut add = |yaku: NormalYaku, applies: bool| {
+        if applies {
+            found.push(yaku);
+        }
+    };
+
+    let sequences = groups
+        .iter()
+        .filter(|g| g.shape == GroupShape::Sequence)
+        .map(|g| g.kind)
+        .collect_vec();
+    let triplets = groups
+        .iter()
+        .filter(|g| g.shape != GroupShape::Sequence)
+        .map(|g| g.kind)
+        .collect_vec();
+    let is_value_pair = pair == TileKind::Wind(context.seat_wind)
+        || pair == TileKind::Wind(context.round_wind)
+        || matches!(pair, TileKind::Dragon(_));
+
+    add(
+        Pinfu,
+        is_concealed
+            && triplets.is_empty()
+            && !is_value_pair
+            && decomposition.wait == Wait::BothSides,
+    );
+
+    // 一盃口・二盃口
+    let identical_sequences = sequences
+        .iter()
+        .sorted()
+        .group_by(|k| **k)
+        .into_iter()
+        .map(|(_, same)| same.count() / 2)
+        .sum::<usize>();
+    add(Ryanpeikou, is_concealed && identical_sequences == 2);
+    add(Iipeikou, is_concealed && identical_sequences == 1);
+
+    // 役牌
+    add(
+        SeatWind,
+        triplets.contains(&TileKind::Wind(context.seat_wind)),
+    );
+    add(
+        RoundWind,
+        triplets.contains(&TileKind::Wind(context.round_wind)),
+    );
+    add(
+        WhiteDragon,
+        triplets.contains(&TileKind::Dragon(Dragon::White)),
+    );
+    add(
+        GreenDragon,
+        triplets.contains(&TileKind::Dragon(Dragon::Green)),
+    );
+    add(RedDragon, triplets.contains(&TileKind::Dragon(Dragon::Red)));
+
+    add(Sanshoku, in_all_suits(&sequences));
+    add(SanshokuDoukou, in_all_suits(&triplets));
+    add(
+        Ittsu,
+        [Suit::Character, Suit::Circle, Suit::Bamboo]
+            .iter()
+            .any(|suit| {
+                [1, 4, 7]
+                    .iter()
+                    .all(|n| sequences.contains(&TileKind::Suited(*suit, *n)))
+            }),
+    );
+
+    // 全帯
+    let all_with_terminal_or_honor = groups
+        .iter()
+        .all(|g| g.kinds().iter().any(TileKind::is_terminal_or_honor))
+        && pair.is_terminal_or_honor();
+    let has_honors = pair.is_honor() || triplets.iter().any(TileKind::is_honor);
+    add(
+        Chanta,
+        all_with_terminal_or_honor && !sequences.is_empty() && has_honors,
+    );
+    add(
+        Junchan,
+        all_with_terminal_or_honor && !sequences.is_empty() && !has_honors,
+    );
+
+    add(Toitoi, sequences.is_empty());
+    // A triplet completed by 栄和 is not concealed.
+    let concealed_triplets = groups
+        .iter()
+        .enumerate()
+        .filter(|(_, g)| g.shape != GroupShape::Sequence && g.is_concealed)
+        .filter(|(i, _)| {
+            context.is_self_drawn || decomposition.winning_position != WinningPosition::Group(*i)
+        })
+        .count();
+    add(Sanankou, concealed_triplets == 3);
+    add(
+        Sankantsu,
+        groups
+            .iter()
+            .filter(|g| g.shape == GroupShape::Quad)
+            .count()
+            == 3,
+    );
+    add(
+        Shousangen,
+        matches!(pair, TileKind::Dragon(_))
+            && triplets
+                .iter()
+                .filter(|k| matches!(k, TileKind::Dragon(_)))
+                .count()
+                == 2,
+    );
+
+    found
+}
+
+fn named(found: Vec<(&dyn Yaku, u8)>) -> Vec<(String, u8)> {
+    found
+        .into_iter()
+        .map(|(yaku, han)| (yaku.name().to_string(), han))
+        .collect()
+}
+
+/// Keeps the first one of the largest multiplier only. `false` if none is.
+fn retain_first_largest<T>(yakuman: &mut Vec<(T, u8)>, largest: u8) -> bool {
+    match yakuman
+        .iter()
+        .position(|(_, multiplier)| *multiplier == largest)
+    {
+        Some(i) => {
+            let first = yakuman.swap_remove(i);
+            *yakuman = vec![first];
+            true
+        }
+        None => {
+            yakuman.clear();
+            false
+        }
+    }
+}
+
+fn suit_of(kind: &TileKind) -> Option<Suit> {
+    match kind {
+        TileKind::Suited(suit, _) => Some(*suit),
+        _ => None,
+    }
+}
+
+/// Whether groups of the same number are in all the suits.
+fn in_all_suits(kinds: &[TileKind]) -> bool {
+    (1..=9).any(|n| {
+        [Suit::Character, Suit::Circle, Suit::Bamboo]
+            .iter()
+            .all(|suit| kinds.contains(&TileKind::Suited(*suit, n)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule::fu::FuItem;
+    use crate::rule::{chow_of, tiles_of};
+
+    /// 栄和 or 自摸和 of South in the East round, without anything else.
+    pub(super) fn context(is_self_drawn: bool) -> WinContext {
+        WinContext {
+            is_self_drawn,
+            seat_wind: Wind::South,
+            round_wind: Wind::East,
+            is_ready_declared: false,
+            is_ready_declared_first: false,
+            is_right_after_ready: false,
+            is_last_tile: false,
+            is_supplemental_tile: false,
+            is_robbing_quad: false,
+            is_first_draw: false,
+        }
+    }
+
+    fn yaku_of(evaluation: &YakuEvaluation) -> Vec<NormalYaku> {
+        evaluation.yaku.iter().map(|(yaku, _)| *yaku).collect()
+    }
+
+    fn evaluation_of(yaku: Vec<(NormalYaku, u8)>, fu: u8) -> YakuEvaluation {
+        let winning_tile = tiles_of("5s")[0];
+        YakuEvaluation {
+            decomposition: decompose(&tiles_of("123m456m789p22s34s"), &[], winning_tile).remove(0),
+            yaku,
+            yakuman: vec![],
+            registered_yaku: vec![],
+            registered_yakuman: vec![],
+            fu: Fu {
+                items: vec![(FuItem::Base, fu)],
+            },
+        }
+    }
+
+    #[test]
+    fn interpretation_of_more_basic_points_is_worth_more() {
+        let evaluator = YakuEvaluator::new(&RuleConfig::default());
+        // 880 and 480
+        let more_fu = evaluation_of(vec![(NormalYaku::Tanyao, 1)], 110);
+        let more_han = evaluation_of(vec![(NormalYaku::Tanyao, 1), (NormalYaku::Pinfu, 1)], 30);
+        assert!(evaluator.worth_of(&more_fu, 0) > evaluator.worth_of(&more_han, 0));
+        // 満貫 both, then by 飜
+        assert!(evaluator.worth_of(&more_fu, 3) < evaluator.worth_of(&more_han, 3));
+    }
+
+    #[test]
+    fn two_double_sequences_is_chosen_over_seven_pairs() {
+        let evaluator = YakuEvaluator::new(&RuleConfig::default());
+        let tiles = tiles_of("112233m556677p9s");
+        let evaluation = evaluator
+            .evaluate(&tiles, &[], tiles_of("9s")[0], &context(false))
+            .unwrap();
+        assert_eq!(yaku_of(&evaluation), vec![NormalYaku::Ryanpeikou]);
+        assert_eq!(evaluation.fu.total(), 40);
+    }
+
+    #[test]
+    fn open_tanyao_follows_the_rule() {
+        let tiles = tiles_of("234m456p678s5s");
+        let melds = [chow_of("345s", "3s")];
+        let winning_tile = tiles_of("5s")[0];
+        let evaluator = YakuEvaluator::new(&RuleConfig::default());
+        let evaluation = evaluator
+            .evaluate(&tiles, &melds, winning_tile, &context(false))
+            .unwrap();
+        assert_eq!(yaku_of(&evaluation), vec![NormalYaku::Tanyao]);
+
+        let rule = RuleConfig {
+            allows_open_tanyao: false,
+            ..RuleConfig::default()
+        };
+        let evaluator = YakuEvaluator::new(&rule);
+        assert!(evaluator
+            .evaluate(&tiles, &melds, winning_tile, &context(false))
+            .is_none());
+    }
+
+    #[test]
+    fn triplet_completed_by_ron_is_not_concealed() {
+        let evaluator = YakuEvaluator::new(&RuleConfig::default());
+        let tiles = tiles_of("111m222p33s456s77z");
+        let winning_tile = tiles_of("3s")[0];
+        let self_drawn = evaluator
+            .evaluate(&tiles, &[], winning_tile, &context(true))
+            .unwrap();
+        assert!(yaku_of(&self_drawn).contains(&NormalYaku::Sanankou));
+
+        let context = WinContext {
+            is_ready_declared: true,
+            ..context(false)
+        };
+        let ron = evaluator
+            .evaluate(&tiles, &[], winning_tile, &context)
+            .unwrap();
+        assert_eq!(yaku_of(&ron), vec![NormalYaku::Riichi]);
+    }
+
+    #[test]
+    fn pinfu_needs_both_sides_wait_and_no_value_pair() {
+        let evaluator = YakuEvaluator::new(&RuleConfig::default());
+        let yaku_of_self_drawn = |tiles: &str, winning_tile: &str| {
+            evaluator
+                .evaluate(
+                    &tiles_of(tiles),
+                    &[],
+                    tiles_of(winning_tile)[0],
+                    &context(true),
+                )
+                .map(|e| yaku_of(&e))
+                .unwrap_or_default()
+        };
+        assert!(yaku_of_self_drawn("23m456m789p234s99s", "1m").contains(&NormalYaku::Pinfu));
+        assert!(!yaku_of_self_drawn("13m456m789p234s99s", "2m").contains(&NormalYaku::Pinfu));
+        // 場風
+        assert!(!yaku_of_self_drawn("23m456m789p234s11z", "1m").contains(&NormalYaku::Pinfu));
+        // 客風
+        assert!(yaku_of_self_drawn("23m456m789p234s33z", "1m").contains(&NormalYaku::Pinfu));
+    }
+}
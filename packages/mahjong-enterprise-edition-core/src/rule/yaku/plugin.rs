@@ -0,0 +1,103 @@
+use crate::rule::decomposition::Decomposition;
+use crate::rule::yaku::{NormalYaku, WinContext, YakumanYaku};
+
+/// A yaku which a rule pack adds to the evaluator, e.g. ローカル役 like 人和 and 大車輪.
+pub trait Yaku: Send + Sync {
+    /// Distinct among the registered ones.
+    fn name(&self) -> &str;
+
+    /// 飜数, or how many times of a yakuman it is worth. `None` if the yaku needs a concealed
+    /// hand (門前限定), and less than the concealed one for 喰い下がり.
+    fn han(&self, is_concealed: bool) -> Option<u8>;
+
+    /// 役満. Normal yaku don't count with it.
+    fn is_yakuman(&self) -> bool {
+        false
+    }
+
+    /// Whether a standard yaku counts with this one. Otherwise this one overrides it.
+    fn combines_with(&self, _yaku: NormalYaku) -> bool {
+        true
+    }
+
+    /// Whether a standard yakuman counts with this one. Otherwise this one overrides it.
+    fn combines_with_yakuman(&self, _yakuman: YakumanYaku) -> bool {
+        true
+    }
+
+    fn applies(&self, decomposition: &Decomposition, context: &WinContext) -> bool;
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::game::RuleConfig;
+    use crate::rule::yaku::tests::context;
+    use crate::rule::yaku::YakuEvaluator;
+    use crate::rule::{chow_of, tiles_of};
+
+    /// 断么九 worth 2 飜 when concealed, in place of the standard one.
+    pub(crate) struct ConcealedAllSimples;
+
+    impl Yaku for ConcealedAllSimples {
+        fn name(&self) -> &str {
+            "concealed-all-simples"
+        }
+
+        fn han(&self, is_concealed: bool) -> Option<u8> {
+            if is_concealed {
+                Some(2)
+            } else {
+                None
+            }
+        }
+
+        fn combines_with(&self, yaku: NormalYaku) -> bool {
+            yaku != NormalYaku::Tanyao
+        }
+
+        fn applies(&self, decomposition: &Decomposition, _context: &WinContext) -> bool {
+            decomposition
+                .form
+                .kinds()
+                .iter()
+                .all(|k| !k.is_terminal_or_honor())
+        }
+    }
+
+    fn evaluator() -> YakuEvaluator {
+        let mut evaluator = YakuEvaluator::new(&RuleConfig::default());
+        evaluator.register(Box::new(ConcealedAllSimples));
+        evaluator
+    }
+
+    #[test]
+    fn registered_yaku_overrides_standard_one() {
+        let tiles = tiles_of("234m456p678s2288s");
+        let evaluation = evaluator()
+            .evaluate(&tiles, &[], tiles_of("8s")[0], &context(false))
+            .unwrap();
+        assert!(evaluation.yaku.is_empty());
+        assert_eq!(
+            evaluation.registered_yaku,
+            vec![("concealed-all-simples".to_string(), 2)]
+        );
+        assert_eq!(evaluation.han(), 2);
+    }
+
+    #[test]
+    fn registered_yaku_of_concealed_hand_does_not_override_when_open() {
+        let chow = chow_of("345s", "3s");
+        let evaluation = evaluator()
+            .evaluate(
+                &tiles_of("234m456p2288s"),
+                &[chow],
+                tiles_of("8s")[0],
+                &context(false),
+            )
+            .unwrap();
+        assert!(evaluation.registered_yaku.is_empty());
+        // The standard one counts instead.
+        assert_eq!(evaluation.yaku, vec![(NormalYaku::Tanyao, 1)]);
+    }
+}
@@ -0,0 +1,275 @@
+use crate::rule::decomposition::{Decomposition, GroupShape, HandForm, Wait, WinningPosition};
+use crate::rule::tile::{Dragon, Suit, TileKind, Wind};
+use crate::rule::yaku::WinContext;
+use itertools::Itertools;
+use serde::Serialize;
+
+/// 役満役
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum YakumanYaku {
+    /// 天和
+    Tenhou,
+    /// 地和
+    Chiihou,
+    /// 国士無双
+    KokushiMusou,
+    /// 国士無双十三面待ち
+    KokushiMusouJuusanmen,
+    /// 四暗刻
+    Suuankou,
+    /// 四暗刻単騎
+    SuuankouTanki,
+    /// 大三元
+    Daisangen,
+    /// 小四喜
+    Shousuushii,
+    /// 大四喜
+    Daisuushii,
+    /// 字一色
+    Tsuuiisou,
+    /// 緑一色
+    Ryuuiisou,
+    /// 清老頭
+    Chinroutou,
+    /// 九蓮宝燈
+    ChuurenPoutou,
+    /// 純正九蓮宝燈
+    JunseiChuurenPoutou,
+    /// 四槓子
+    Suukantsu,
+}
+
+impl YakumanYaku {
+    /// How many times of a yakuman it is worth.
+    pub fn multiplier(&self, has_double_yakuman: bool) -> u8 {
+        use YakumanYaku::*;
+
+        match self {
+            KokushiMusouJuusanmen | SuuankouTanki | Daisuushii | JunseiChuurenPoutou
+                if has_double_yakuman =>
+            {
+                2
+            }
+            _ => 1,
+        }
+    }
+}
+
+const WINDS: [Wind; 4] = [Wind::East, Wind::South, Wind::West, Wind::North];
+const DRAGONS: [Dragon; 3] = [Dragon::White, Dragon::Green, Dragon::Red];
+/// 九蓮宝燈 before the last tile
+const NINE_GATES: [u8; 9] = [3, 1, 1, 1, 1, 1, 1, 1, 3];
+
+pub(super) fn find(
+    decomposition: &Decomposition,
+    context: &WinContext,
+    is_concealed: bool,
+) -> Vec<YakumanYaku> {
+    use YakumanYaku::*;
+
+    let mut found = vec![];
+    let mut add = |yaku: YakumanYaku, applies: bool| {
+        if applies {
+            found.push(yaku);
+        }
+    };
+
+    // 天和・地和, the first draw without calls before
+    let is_first_completion = context.is_first_draw && context.is_self_drawn && is_concealed;
+    let is_dealer = context.seat_wind == Wind::East;
+    add(Tenhou, is_first_completion && is_dealer);
+    add(Chiihou, is_first_completion && !is_dealer);
+
+    let kinds = decomposition.form.kinds();
+    add(Tsuuiisou, kinds.iter().all(TileKind::is_honor));
+    add(Chinroutou, kinds.iter().all(TileKind::is_terminal));
+    add(
+        Ryuuiisou,
+        kinds.iter().all(|kind| match kind {
+            TileKind::Suited(Suit::Bamboo, n) => [2, 3, 4, 6, 8].contains(n),
+            TileKind::Dragon(Dragon::Green) => true,
+            _ => false,
+        }),
+    );
+
+    match decomposition.form {
+        HandForm::ThirteenOrphans { .. } => {
+            if decomposition.wait == Wait::ThirteenSided {
+                add(KokushiMusouJuusanmen, true);
+            } else {
+                add(KokushiMusou, true);
+            }
+        }
+        HandForm::Normal { pair, ref groups } => {
+            let triplets = groups
+                .iter()
+                .filter(|g| g.shape != GroupShape::Sequence)
+                .map(|g| g.kind)
+                .collect_vec();
+            let concealed_triplets = groups
+                .iter()
+                .enumerate()
+                .filter(|(_, g)| g.shape != GroupShape::Sequence && g.is_concealed)
+                // A triplet completed by 栄和 is not concealed.
+                .filter(|(i, _)| {
+                    context.is_self_drawn
+                        || decomposition.winning_position != WinningPosition::Group(*i)
+                })
+                .count();
+            if concealed_triplets == 4 {
+                add(SuuankouTanki, decomposition.wait == Wait::Single);
+                add(Suuankou, decomposition.wait != Wait::Single);
+            }
+
+            add(
+                Daisangen,
+                DRAGONS
+                    .iter()
+                    .all(|d| triplets.contains(&TileKind::Dragon(*d))),
+            );
+            let wind_triplets = WINDS
+                .iter()
+                .filter(|w| triplets.contains(&TileKind::Wind(**w)))
+                .count();
+            add(Daisuushii, wind_triplets == 4);
+            add(
+                Shousuushii,
+                wind_triplets == 3 && matches!(pair, TileKind::Wind(_)),
+            );
+            add(
+                Suukantsu,
+                groups
+                    .iter()
+                    .filter(|g| g.shape == GroupShape::Quad)
+                    .count()
+                    == 4,
+            );
+
+            // 九蓮宝燈, without 暗槓
+            let has_quads = groups.iter().any(|g| g.shape == GroupShape::Quad);
+            if is_concealed && !has_quads {
+                if let Some(is_pure) = nine_gates(&kinds, decomposition.winning_tile.kind()) {
+                    add(JunseiChuurenPoutou, is_pure);
+                    add(ChuurenPoutou, !is_pure);
+                }
+            }
+        }
+        HandForm::SevenPairs { .. } => {}
+    }
+
+    found.sort();
+    found
+}
+
+/// Whether the 14 kinds form 九蓮宝燈, and purely so (九面待ち) if `Some(true)`.
+fn nine_gates(kinds: &[TileKind], winning_kind: TileKind) -> Option<bool> {
+    let suit = match kinds.first() {
+        Some(TileKind::Suited(suit, _)) => *suit,
+        _ => return None,
+    };
+    let mut counts = [0u8; 9];
+    for kind in kinds {
+        match kind {
+            TileKind::Suited(s, n) if *s == suit => counts[*n as usize - 1] += 1,
+            _ => return None,
+        }
+    }
+    if counts.iter().zip(NINE_GATES.iter()).any(|(n, min)| n < min) {
+        return None;
+    }
+    match winning_kind {
+        TileKind::Suited(_, n) => {
+            counts[n as usize - 1] -= 1;
+            Some(counts == NINE_GATES)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::RuleConfig;
+    use crate::rule::tiles_of;
+    use crate::rule::yaku::tests::context;
+    use crate::rule::yaku::YakuEvaluator;
+
+    fn yakuman_of(rule: &RuleConfig, tiles: &str, winning_tile: &str) -> Vec<(YakumanYaku, u8)> {
+        YakuEvaluator::new(rule)
+            .evaluate(
+                &tiles_of(tiles),
+                &[],
+                tiles_of(winning_tile)[0],
+                &context(false),
+            )
+            .map(|e| e.yakuman)
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn thirteen_sided_wait_is_double_if_the_rule_says() {
+        let rule = RuleConfig::default();
+        assert_eq!(
+            yakuman_of(&rule, "19m19p19s1234567z", "9s"),
+            vec![(YakumanYaku::KokushiMusouJuusanmen, 2)]
+        );
+        assert_eq!(
+            yakuman_of(&rule, "119m19p19s123456z", "7z"),
+            vec![(YakumanYaku::KokushiMusou, 1)]
+        );
+
+        let rule = RuleConfig {
+            has_double_yakuman: false,
+            ..RuleConfig::default()
+        };
+        assert_eq!(
+            yakuman_of(&rule, "19m19p19s1234567z", "9s"),
+            vec![(YakumanYaku::KokushiMusouJuusanmen, 1)]
+        );
+    }
+
+    #[test]
+    fn four_concealed_triplets_by_ron_on_dual_pair_is_not_yakuman() {
+        let rule = RuleConfig::default();
+        assert_eq!(yakuman_of(&rule, "111m222p333s4477z", "4z"), vec![]);
+        assert_eq!(
+            yakuman_of(&rule, "111m222p333s444z7z", "7z"),
+            vec![(YakumanYaku::SuuankouTanki, 2)]
+        );
+    }
+
+    #[test]
+    fn nine_gates_waiting_on_nine_kinds_is_pure() {
+        let rule = RuleConfig::default();
+        assert_eq!(
+            yakuman_of(&rule, "1112345678999m", "5m"),
+            vec![(YakumanYaku::JunseiChuurenPoutou, 2)]
+        );
+        assert_eq!(
+            yakuman_of(&rule, "1112345678899m", "9m"),
+            vec![(YakumanYaku::ChuurenPoutou, 1)]
+        );
+    }
+
+    #[test]
+    fn yakuman_are_stacked_if_the_rule_says() {
+        // 字一色, 大三元 and 四暗刻単騎
+        let tiles = "555666777111z2z";
+        let rule = RuleConfig::default();
+        let multipliers = yakuman_of(&rule, tiles, "2z")
+            .into_iter()
+            .map(|(_, multiplier)| multiplier)
+            .collect::<Vec<_>>();
+        assert_eq!(multipliers, vec![2, 1, 1]);
+
+        let rule = RuleConfig {
+            stacks_yakuman: false,
+            ..RuleConfig::default()
+        };
+        assert_eq!(
+            yakuman_of(&rule, tiles, "2z"),
+            vec![(YakumanYaku::SuuankouTanki, 2)]
+        );
+    }
+}